@@ -18,7 +18,7 @@ fn sym_enc(pw: &[u8], mut msg: Message) {
 fn key_gen_enc_dec(pw: &[u8], mut msg: Message) {
     let key_pair = KeyPair::new(pw, "test key".to_string(), BIT_SECURITY);
     msg.key_encrypt(&key_pair.pub_key, BIT_SECURITY);
-    let _ = msg.key_decrypt(&key_pair.priv_key);
+    let _ = msg.key_decrypt(&key_pair);
 }
 
 /// Signature generation + verification roundtrip
@@ -53,7 +53,7 @@ fn bench_key_gen_enc_dec(c: &mut Criterion) {
     c.bench_function("e448 + SHA3-224 Asymmetric enc + dec", |b| {
         b.iter(|| {
             key_gen_enc_dec(
-                &KeyPair::new(&get_random_bytes(32), "test key".to_string(), BIT_SECURITY).priv_key,
+                &get_random_bytes(32),
                 Message::new(get_random_bytes(5242880)),
             )
         });
@@ -1,4 +1,94 @@
-use crate::aes::aes_constants::{CMDS, GF_MUL_TABLE, INV_CMDS, INV_SBOX, RCON, SBOX};
+use crate::{
+    aes::{
+        aes_constants::{CMDS, GF_MUL_TABLE, INV_CMDS, INV_SBOX, RCON, SBOX},
+        aes_ni,
+    },
+    BitLength, OperationError,
+};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Length of the random IV stored in `Message.sym_nonce` by AES-CTR.
+pub const CTR_IV_LEN: usize = 16;
+/// Number of IV bytes that prefix every AES-CTR counter block. The remaining
+/// 8 bytes of each block hold the 64-bit block counter.
+pub const CTR_NONCE_LEN: usize = 8;
+
+/// The implementation used to run the AES block cipher.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Backend {
+    /// Table-driven FIPS 197 implementation, available everywhere.
+    Portable,
+    /// x86_64 AES-NI instructions, see [`aes_ni`].
+    AesNi,
+}
+
+impl Backend {
+    /// Selects the fastest backend supported by the running CPU.
+    pub fn detect() -> Self {
+        if aes_ni::is_supported() {
+            Backend::AesNi
+        } else {
+            Backend::Portable
+        }
+    }
+}
+
+/// The AES variant selected by the length of a symmetric key. It is stored
+/// with every AES ciphertext so decryption can reject keys of the wrong size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AesKeySize {
+    Aes128,
+    Aes192,
+    Aes256,
+}
+
+impl AesKeySize {
+    /// Length in bytes of the authentication subkey derived alongside the
+    /// encryption key. It does not depend on the AES variant.
+    pub const MAC_KEY_LEN: usize = 64;
+
+    /// Maps a key length in bytes to the matching AES variant.
+    /// ## Errors:
+    /// * [`OperationError::UnsupportedAESKeyLength`] unless `len` is 16, 24 or 32.
+    pub fn from_key_len(len: usize) -> Result<Self, OperationError> {
+        match len {
+            16 => Ok(AesKeySize::Aes128),
+            24 => Ok(AesKeySize::Aes192),
+            32 => Ok(AesKeySize::Aes256),
+            _ => Err(OperationError::UnsupportedAESKeyLength),
+        }
+    }
+
+    /// Length in bytes of the AES encryption key.
+    pub fn enc_key_len(&self) -> usize {
+        self.bit_length() as usize / 8
+    }
+
+    /// Length in bytes of the authentication subkey.
+    pub fn mac_key_len(&self) -> usize {
+        Self::MAC_KEY_LEN
+    }
+
+    // (n_w, n_r) as defined in FIPS 197 table 3.
+    fn schedule_params(&self) -> (u32, u32) {
+        match self {
+            AesKeySize::Aes128 => (4, 10),
+            AesKeySize::Aes192 => (6, 12),
+            AesKeySize::Aes256 => (8, 14),
+        }
+    }
+}
+
+impl BitLength for AesKeySize {
+    fn bit_length(&self) -> u64 {
+        match self {
+            AesKeySize::Aes128 => 128,
+            AesKeySize::Aes192 => 192,
+            AesKeySize::Aes256 => 256,
+        }
+    }
+}
 
 pub struct AES {
     pub round_key: Vec<u8>,
@@ -6,30 +96,79 @@ pub struct AES {
     n_w: u32, // Number of words in state.
     #[allow(dead_code)]
     n_r: u32, // Number of rounds.
+    backend: Backend,
+    inv_round_key: Vec<u8>, // Equivalent inverse cipher schedule, AES-NI only.
 }
 
 // FIPS 197 compliant functions.
 impl AES {
-    pub fn new(key: &[u8]) -> Self {
-        // Convert bytes to bits
-        let key_length = key.len() * 8;
-        // set values of n_w and n_r based on key bit length.
-        let (n_w, n_r) = match key_length {
-            128 => (4, 10),
-            192 => (6, 12),
-            256 => (8, 14),
-            _ => panic!("Unsupported key length"),
-        };
+    /// Expands `key` and selects the hardware backend when the CPU supports it.
+    /// ## Errors:
+    /// * [`OperationError::UnsupportedAESKeyLength`] unless `key` is 16, 24 or 32 bytes.
+    pub fn new(key: &[u8]) -> Result<Self, OperationError> {
+        Self::with_backend(key, Backend::detect())
+    }
+
+    /// Expands `key` for use with the requested `backend`. Falls back to
+    /// [`Backend::Portable`] if the CPU does not support the requested backend.
+    /// ## Errors:
+    /// * [`OperationError::UnsupportedAESKeyLength`] unless `key` is 16, 24 or 32 bytes.
+    pub fn with_backend(key: &[u8], backend: Backend) -> Result<Self, OperationError> {
+        // set values of n_w and n_r based on key length.
+        let (n_w, n_r) = AesKeySize::from_key_len(key.len())?.schedule_params();
 
         // Initilize round_key vector based on size needed.
         let mut round_key = key.to_vec();
         // Generate all key rounds.
         Self::key_expansion(&mut round_key, n_w, n_r);
 
-        AES {
+        let backend = match backend {
+            Backend::AesNi if aes_ni::is_supported() => Backend::AesNi,
+            _ => Backend::Portable,
+        };
+
+        let inv_round_key = match backend {
+            #[cfg(target_arch = "x86_64")]
+            // SAFETY: AES-NI support was checked above.
+            Backend::AesNi => unsafe { aes_ni::inverse_key_schedule(&round_key) },
+            _ => vec![],
+        };
+
+        Ok(AES {
             round_key,
             n_w,
             n_r,
+            backend,
+            inv_round_key,
+        })
+    }
+
+    /// The backend this key schedule dispatches to.
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    /// Encrypts the 16-byte block of `input` starting at `block_index` in place
+    /// using the selected backend.
+    pub fn encrypt(&self, input: &mut [u8], block_index: usize) {
+        match self.backend {
+            #[cfg(target_arch = "x86_64")]
+            // SAFETY: the AesNi backend is only selected when the CPU supports it.
+            Backend::AesNi => unsafe { aes_ni::encrypt_block(input, block_index, &self.round_key) },
+            _ => Self::encrypt_block(input, block_index, &self.round_key),
+        }
+    }
+
+    /// Decrypts the 16-byte block of `input` starting at `block_index` in place
+    /// using the selected backend.
+    pub fn decrypt(&self, input: &mut [u8], block_index: usize) {
+        match self.backend {
+            #[cfg(target_arch = "x86_64")]
+            // SAFETY: the AesNi backend is only selected when the CPU supports it.
+            Backend::AesNi => unsafe {
+                aes_ni::decrypt_block(input, block_index, &self.inv_round_key)
+            },
+            _ => Self::decrypt_block(input, block_index, &self.round_key),
         }
     }
 
@@ -215,7 +354,7 @@ impl AES {
                 temp[j] = key[i as usize - 4 + j];
             }
 
-            if i / 4 % n_w == 0 {
+            if (i / 4).is_multiple_of(n_w) {
                 Self::rot_word(&mut temp);
                 Self::sub_word(&mut temp);
                 Self::rcon(&mut temp, (i / (n_w * 4)) as usize - 1);
@@ -274,6 +413,60 @@ pub fn remove_pcks7_padding(input: &mut Vec<u8>) {
     input.truncate(amount_to_remove)
 }
 
+/// Builds the AES-CTR counter block for block number `block_index`:
+/// `nonce || u64_be(block_index)`. Messages are addressed by `u64` byte offsets,
+/// so they are limited to 2⁶⁴ bytes, or 2⁶⁰ blocks, and the counter never wraps.
+pub fn ctr_block(nonce: &[u8], block_index: u64) -> [u8; 16] {
+    let mut block = [0u8; 16];
+    block[..CTR_NONCE_LEN].copy_from_slice(&nonce[..CTR_NONCE_LEN]);
+    block[CTR_NONCE_LEN..].copy_from_slice(&block_index.to_be_bytes());
+    block
+}
+
+/// XORs the AES-CTR keystream for message bytes `offset .. offset + data.len()`
+/// into `data`. `offset` does not need to be block aligned.
+///
+/// Returns [`OperationError::AESCTRCounterOverflow`] instead of wrapping
+/// around when `offset + data.len()` exceeds `u64::MAX`, the last byte
+/// addressable by `offset`. This is the only limit on the message length, as
+/// the block counter of [`ctr_block`] has room for 2⁶⁴ blocks.
+pub fn apply_ctr_keystream(
+    aes: &AES,
+    nonce: &[u8],
+    offset: u64,
+    data: &mut [u8],
+) -> Result<(), OperationError> {
+    offset
+        .checked_add(data.len() as u64)
+        .ok_or(OperationError::AESCTRCounterOverflow)?;
+
+    let skip = (offset % 16) as usize;
+    let mut first_block = offset / 16;
+
+    // Finish the partial block the range starts in.
+    let head_len = if skip == 0 {
+        0
+    } else {
+        (16 - skip).min(data.len())
+    };
+    let (head, tail) = data.split_at_mut(head_len);
+    if !head.is_empty() {
+        let mut keystream = ctr_block(nonce, first_block);
+        aes.encrypt(&mut keystream, 0);
+        xor_blocks(head, &keystream[skip..]);
+        first_block += 1;
+    }
+
+    // Parallelize the remaining, block aligned, data
+    tail.par_chunks_mut(16).enumerate().for_each(|(i, block)| {
+        let mut keystream = ctr_block(nonce, first_block + i as u64);
+        aes.encrypt(&mut keystream, 0);
+        xor_blocks(block, &keystream);
+    });
+
+    Ok(())
+}
+
 pub fn xor_blocks(a: &mut [u8], b: &[u8]) {
     // Determine the minimum length between the two blocks
     let len = a.len().min(b.len());
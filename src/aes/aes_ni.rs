@@ -0,0 +1,100 @@
+//! Hardware AES backend built on the x86_64 AES-NI instruction set.
+//!
+//! The functions in this module consume the same FIPS 197 key schedule that
+//! [`AES::new`](crate::aes::aes_functions::AES::new) produces for the portable
+//! implementation, so both backends are interchangeable block for block. They
+//! must only be called after [`is_supported`](crate::aes::aes_ni::is_supported)
+//! has returned `true`;
+//! [`AES`](crate::aes::aes_functions::AES) performs this check once when the
+//! key schedule is created and dispatches accordingly.
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::{
+    __m128i, _mm_aesdec_si128, _mm_aesdeclast_si128, _mm_aesenc_si128, _mm_aesenclast_si128,
+    _mm_aesimc_si128, _mm_loadu_si128, _mm_storeu_si128, _mm_xor_si128,
+};
+
+/// Returns `true` if the running CPU supports the AES-NI instruction set.
+pub fn is_supported() -> bool {
+    #[cfg(target_arch = "x86_64")]
+    {
+        is_x86_feature_detected!("aes") && is_x86_feature_detected!("sse2")
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        false
+    }
+}
+
+/// Encrypts the 16-byte block of `input` starting at `block_index` in place.
+///
+/// # Safety
+/// The CPU must support AES-NI, see [`is_supported`].
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "aes,sse2")]
+pub unsafe fn encrypt_block(input: &mut [u8], block_index: usize, round_keys: &[u8]) {
+    let block = &mut input[block_index..block_index + 16];
+    let nr = round_keys.len() / 16 - 1;
+
+    let mut state = _mm_loadu_si128(block.as_ptr() as *const __m128i);
+    state = _mm_xor_si128(state, load_round_key(round_keys, 0));
+    for round in 1..nr {
+        state = _mm_aesenc_si128(state, load_round_key(round_keys, round));
+    }
+    state = _mm_aesenclast_si128(state, load_round_key(round_keys, nr));
+
+    _mm_storeu_si128(block.as_mut_ptr() as *mut __m128i, state);
+}
+
+/// Decrypts the 16-byte block of `input` starting at `block_index` in place.
+/// `inv_round_keys` must be the schedule returned by [`inverse_key_schedule`].
+///
+/// # Safety
+/// The CPU must support AES-NI, see [`is_supported`].
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "aes,sse2")]
+pub unsafe fn decrypt_block(input: &mut [u8], block_index: usize, inv_round_keys: &[u8]) {
+    let block = &mut input[block_index..block_index + 16];
+    let nr = inv_round_keys.len() / 16 - 1;
+
+    let mut state = _mm_loadu_si128(block.as_ptr() as *const __m128i);
+    state = _mm_xor_si128(state, load_round_key(inv_round_keys, 0));
+    for round in 1..nr {
+        state = _mm_aesdec_si128(state, load_round_key(inv_round_keys, round));
+    }
+    state = _mm_aesdeclast_si128(state, load_round_key(inv_round_keys, nr));
+
+    _mm_storeu_si128(block.as_mut_ptr() as *mut __m128i, state);
+}
+
+/// Derives the key schedule for the Equivalent Inverse Cipher (FIPS 197 5.3.5)
+/// expected by `AESDEC`: the round keys in reverse order with InvMixColumns
+/// applied to every round key except the first and the last.
+///
+/// # Safety
+/// The CPU must support AES-NI, see [`is_supported`].
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "aes,sse2")]
+pub unsafe fn inverse_key_schedule(round_keys: &[u8]) -> Vec<u8> {
+    let nr = round_keys.len() / 16 - 1;
+    let mut inv_round_keys = vec![0u8; round_keys.len()];
+
+    for round in 0..=nr {
+        let mut key = load_round_key(round_keys, nr - round);
+        if round != 0 && round != nr {
+            key = _mm_aesimc_si128(key);
+        }
+        _mm_storeu_si128(
+            inv_round_keys[round * 16..].as_mut_ptr() as *mut __m128i,
+            key,
+        );
+    }
+
+    inv_round_keys
+}
+
+// Loads round key `round` of an expanded key schedule.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "sse2")]
+unsafe fn load_round_key(round_keys: &[u8], round: usize) -> __m128i {
+    _mm_loadu_si128(round_keys[round * 16..(round + 1) * 16].as_ptr() as *const __m128i)
+}
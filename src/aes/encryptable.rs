@@ -1,15 +1,24 @@
 use crate::{
-    aes::aes_functions::{apply_pcks7_padding, remove_pcks7_padding, xor_blocks, AES},
+    aes::aes_functions::{
+        apply_ctr_keystream, apply_pcks7_padding, remove_pcks7_padding, xor_blocks, AesKeySize,
+        AES, CTR_IV_LEN, CTR_NONCE_LEN,
+    },
     sha3::{aux_functions::byte_utils::get_random_bytes, shake_functions::kmac_xof},
-    Message, OperationError, SecParam,
+    BitLength, Message, OperationError, SecParam,
 };
 use rayon::prelude::*;
 
 pub trait AesEncryptable {
-    fn aes_encrypt_cbc(&mut self, key: &[u8]);
+    fn aes_encrypt_cbc(&mut self, key: &[u8]) -> Result<(), OperationError>;
     fn aes_decrypt_cbc(&mut self, key: &[u8]) -> Result<(), OperationError>;
-    fn aes_encrypt_ctr(&mut self, key: &[u8]);
+    fn aes_encrypt_ctr(&mut self, key: &[u8]) -> Result<(), OperationError>;
     fn aes_decrypt_ctr(&mut self, key: &[u8]) -> Result<(), OperationError>;
+    fn aes_decrypt_ctr_range(
+        &self,
+        key: &[u8],
+        offset: u64,
+        len: usize,
+    ) -> Result<Vec<u8>, OperationError>;
 }
 
 impl AesEncryptable for Message {
@@ -20,29 +29,31 @@ impl AesEncryptable for Message {
     /// * `Message.data` with the result of encryption.
     /// * `Message.digest` with the keyed hash of plaintext.
     /// * `Message.sym_nonce` with the initialization vector (IV).
+    /// * `Message.aes_key_size` with the AES variant selected by `key`.
+    ///
     /// SECURITY NOTE: ciphertext length == plaintext length
     /// ## Algorithm:
     /// * iv ← Random(16)
-    /// * (ke || ka) ← kmac_xof(iv || key, “”, 512, “AES”)
+    /// * (ke || ka) ← kmac_xof(iv || key, “”, 8(|ke| + |ka|), “AES-{128,192,256}-CBC”)
     /// * C1 = encrypt_block(P1 ⊕ IV)
     /// * Cj = encrypt_block(Pj ⊕ Cj-1) for j = 2 … n
+    ///
     /// Here:
     /// - P: Represents plaintext blocks.
     /// - C: Represents ciphertext blocks.
+    /// - |ke|, |ka|: subkey lengths given by [`AesKeySize`].
     /// ## Arguments:
-    /// * `key: &Vec<u8>`: symmetric encryption key.
-    fn aes_encrypt_cbc(&mut self, key: &[u8]) {
+    /// * `key: &Vec<u8>`: symmetric encryption key of 16, 24 or 32 bytes.
+    /// ## Errors:
+    /// * [`OperationError::UnsupportedAESKeyLength`] for any other key length.
+    fn aes_encrypt_cbc(&mut self, key: &[u8]) -> Result<(), OperationError> {
         let iv = get_random_bytes(16);
-        let mut ke_ka = iv.clone();
-        ke_ka.append(&mut key.to_owned());
-        let ke_ka = kmac_xof(&ke_ka, &[], 512, "AES", SecParam::D256);
-        let ke = &ke_ka[..key.len()].to_vec(); // Encryption Key
-        let ka = &ke_ka[key.len()..].to_vec(); // Authentication Key
+        let (key_size, ke, ka) = derive_keys(&iv, key, "CBC")?;
 
-        self.digest = kmac_xof(ka, &self.msg, 512, "AES", SecParam::D256);
+        self.digest = kmac_xof(&ka, &self.msg, 512, "AES", SecParam::D256);
         self.sym_nonce = Some(iv.clone());
 
-        let key_schedule = AES::new(ke);
+        let key_schedule = AES::new(&ke)?;
 
         apply_pcks7_padding(&mut self.msg);
 
@@ -51,11 +62,13 @@ impl AesEncryptable for Message {
                 &mut self.msg[block_index..],
                 self.sym_nonce.as_mut().unwrap(),
             );
-            AES::encrypt_block(&mut self.msg, block_index, &key_schedule.round_key);
+            key_schedule.encrypt(&mut self.msg, block_index);
             *self.sym_nonce.as_mut().unwrap() = self.msg[block_index..block_index + 16].to_vec();
         }
 
         self.sym_nonce = Some(iv);
+        self.aes_key_size = Some(key_size);
+        Ok(())
     }
 
     /// # Symmetric Decryption using AES in CBC Mode
@@ -65,26 +78,37 @@ impl AesEncryptable for Message {
     /// * `Message.data` with the result of decryption.
     /// * `Message.op_result` with the result of verification against the keyed hash.
     /// * `Message.sym_nonce` is used as the initialization vector (IV).
+    ///
     /// SECURITY NOTE: ciphertext length == plaintext length
     /// ## Algorithm:
     /// * iv ← Symmetric nonce (IV)
-    /// * (ke || ka) ← kmac_xof(iv || key, “”, 512, “AES”)
+    /// * (ke || ka) ← kmac_xof(iv || key, “”, 8(|ke| + |ka|), “AES-{128,192,256}-CBC”)
     /// * P1 = decrypt_block(C1) ⊕ IV
     /// * Pj = decrypt_block(Cj) ⊕ Cj-1 for j = 2 … n
+    /// * t’ ← kmac_xof(ka, m, 512, “AES”)
+    ///
     /// Here:
     /// - P: Represents plaintext blocks.
     /// - C: Represents ciphertext blocks.
     /// ## Arguments:
     /// * `key: &Vec<u8>`: symmetric encryption key.
+    /// ## Errors:
+    /// * [`OperationError::AESKeySizeMismatch`] if `key` is not the size recorded
+    ///   in `Message.aes_key_size`.
+    /// * [`OperationError::AESCBCDecryptionFailure`] if the padding or keyed hash
+    ///   does not verify. The ciphertext is left untouched in that case.
     fn aes_decrypt_cbc(&mut self, key: &[u8]) -> Result<(), OperationError> {
-        let iv = self.sym_nonce.clone().unwrap();
-        let mut ke_ka = iv.clone();
-        ke_ka.append(&mut key.to_owned());
-        let ke_ka = kmac_xof(&ke_ka, &[], 512, "AES", SecParam::D256);
-        let ke = &ke_ka[..key.len()].to_vec(); // Encryption Key
-        let ka = &ke_ka[key.len()..].to_vec(); // Authentication Key
+        let iv = self
+            .sym_nonce
+            .clone()
+            .ok_or(OperationError::SymNonceNotSet)?;
+        check_key_size(self.aes_key_size, key)?;
+        let (_, ke, ka) = derive_keys(&iv, key, "CBC")?;
+        if self.msg.is_empty() || !self.msg.len().is_multiple_of(16) {
+            return Err(OperationError::AESCBCDecryptionFailure);
+        }
 
-        let key_schedule = AES::new(ke);
+        let key_schedule = AES::new(&ke)?;
 
         let msg_copy = self.msg.clone();
 
@@ -99,15 +123,24 @@ impl AesEncryptable for Message {
                     &iv // Use IV for the first block
                 };
                 // Decrypt the block in-place without using the output
-                AES::decrypt_block(block, 0, &key_schedule.round_key);
+                key_schedule.decrypt(block, 0);
                 // XOR the decrypted block with the previous ciphertext block
                 xor_blocks(block, xor_block);
             });
 
+        let padding = *self.msg.last().unwrap() as usize;
+        if padding == 0 || padding > 16 {
+            *self.msg = msg_copy.to_vec();
+            return Err(OperationError::AESCBCDecryptionFailure);
+        }
         remove_pcks7_padding(&mut self.msg);
 
-        kmac_xof(ka, &self.msg, 512, "AES", SecParam::D256);
-        Ok(())
+        if self.digest == kmac_xof(&ka, &self.msg, 512, "AES", SecParam::D256) {
+            Ok(())
+        } else {
+            *self.msg = msg_copy.to_vec();
+            Err(OperationError::AESCBCDecryptionFailure)
+        }
     }
 
     /// # Symmetric Encryption using AES in CTR Mode
@@ -117,99 +150,177 @@ impl AesEncryptable for Message {
     /// * `Message.data` with the result of encryption.
     /// * `Message.digest` with the keyed hash of plaintext.
     /// * `Message.sym_nonce` with the initialization vector (IV).
+    /// * `Message.aes_key_size` with the AES variant selected by `key`.
+    ///
     /// SECURITY NOTE: ciphertext length == plaintext length
     /// ## Algorithm:
-    /// * iv ← Random(12)
-    /// * CTR ← u32 counter starting at 0
-    /// * (ke || ka) ← kmac_xof(iv || key, “”, 512, “AES”)
-    /// * C1 = P1 ⊕ encrypt_block(IV || CTR1)
-    /// * Cj = Pj ⊕ encrypt_block(IV || CTRj) for j = 2 … n
+    /// * iv ← Random(16)
+    /// * (ke || ka) ← kmac_xof(iv || key, “”, 8(|ke| + |ka|), “AES-{128,192,256}-CTR”)
+    /// * CTRj ← iv[0..8] || u64_be(j) for j = 0 … n - 1, see [`ctr_block`](crate::aes::aes_functions::ctr_block)
+    /// * Cj = Pj ⊕ encrypt_block(CTRj)
+    ///
     /// Here:
     /// - P: Represents plaintext blocks.
     /// - C: Represents ciphertext blocks.
+    /// - |ke|, |ka|: subkey lengths given by [`AesKeySize`].
     /// ## Arguments:
-    /// * `key: &[u8]`: symmetric encryption key.
-    fn aes_encrypt_ctr(&mut self, key: &[u8]) {
-        let iv = get_random_bytes(12);
-        let counter = 0u32;
-        let counter_bytes = counter.to_be_bytes();
+    /// * `key: &[u8]`: symmetric encryption key of 16, 24 or 32 bytes.
+    /// ## Errors:
+    /// * [`OperationError::UnsupportedAESKeyLength`] for any other key length.
+    /// * [`OperationError::AESCTRCounterOverflow`] if the message extends past
+    ///   the 2⁶⁴ bytes addressable by a `u64` offset, i.e. 2⁶⁰ counter blocks.
+    fn aes_encrypt_ctr(&mut self, key: &[u8]) -> Result<(), OperationError> {
+        let iv = get_random_bytes(CTR_IV_LEN as u64);
+        let (key_size, ke, ka) = derive_keys(&iv, key, "CTR")?;
 
-        let mut ke_ka = iv.clone();
-        ke_ka.extend_from_slice(&counter_bytes);
-        ke_ka.extend_from_slice(key);
-        let ke_ka = kmac_xof(&ke_ka, &[], 512, "AES", SecParam::D256);
+        self.digest = kmac_xof(&ka, &self.msg, 512, "AES", SecParam::D256);
+        apply_ctr_keystream(&AES::new(&ke)?, &iv[..CTR_NONCE_LEN], 0, &mut self.msg)?;
 
-        let (ke, ka) = ke_ka.split_at(key.len());
-
-        self.sym_nonce = Some(iv.clone());
-        self.digest = kmac_xof(ka, &self.msg, 512, "AES", SecParam::D256);
-
-        let key_schedule = AES::new(ke);
-
-        // Parallelize encryption for each block
-        self.msg
-            .par_chunks_mut(16)
-            .enumerate()
-            .for_each(|(i, block)| {
-                let mut temp = iv.clone();
-                let counter = i as u32;
-                temp.extend_from_slice(&counter.to_be_bytes());
-
-                AES::encrypt_block(&mut temp, 0, &key_schedule.round_key);
-
-                xor_blocks(block, &temp);
-            });
+        self.sym_nonce = Some(iv);
+        self.aes_key_size = Some(key_size);
+        Ok(())
     }
+
     /// # Symmetric Decryption using AES in CTR Mode
     /// Decrypts a [`Message`] using the AES algorithm in CTR (Counter) mode.
     /// For more information, refer to NIST Special Publication 800-38A.
     /// ## Replaces:
     /// * `Message.data` with the result of decryption.
     /// * `Message.digest` with the keyed hash of plaintext.
+    ///
     /// SECURITY NOTE: ciphertext length == plaintext length
     /// ## Algorithm:
     /// * iv ← Message.sym_nonce
-    /// * CTR ← u32 counter starting at 0
-    /// * (ke || ka) ← kmac_xof(iv || key, “”, 512, “AES”)
-    /// * P1 = C1 ⊕ encrypt_block(IV || CTR1)
-    /// * Pj = Cj ⊕ encrypt_block(IV || CTRj) for j = 2 … n
+    /// * (ke || ka) ← kmac_xof(iv || key, “”, 8(|ke| + |ka|), “AES-{128,192,256}-CTR”)
+    /// * CTRj ← iv[0..8] || u64_be(j) for j = 0 … n - 1, see [`ctr_block`](crate::aes::aes_functions::ctr_block)
+    /// * Pj = Cj ⊕ encrypt_block(CTRj)
+    /// * t’ ← kmac_xof(ka, m, 512, “AES”)
+    ///
     /// Here:
     /// - P: Represents plaintext blocks.
     /// - C: Represents ciphertext blocks.
     /// ## Arguments:
     /// * `key: &[u8]`: symmetric encryption key.
+    /// ## Errors:
+    /// * [`OperationError::AESKeySizeMismatch`] if `key` is not the size recorded
+    ///   in `Message.aes_key_size`.
+    /// * [`OperationError::AESCTRDecryptionFailure`] if the keyed hash does not
+    ///   verify. The ciphertext is left untouched in that case.
     fn aes_decrypt_ctr(&mut self, key: &[u8]) -> Result<(), OperationError> {
         let iv = self
             .sym_nonce
-            .clone()
+            .as_ref()
             .ok_or(OperationError::SymNonceNotSet)?;
-        let counter = 0u32;
-        let counter_bytes = counter.to_be_bytes();
+        if iv.len() != CTR_IV_LEN {
+            return Err(OperationError::InvalidNonceLength);
+        }
+        check_key_size(self.aes_key_size, key)?;
+        let (_, ke, ka) = derive_keys(iv, key, "CTR")?;
 
-        let mut ke_ka = iv.clone();
-        ke_ka.extend_from_slice(&counter_bytes);
-        ke_ka.extend_from_slice(key);
-        let ke_ka = kmac_xof(&ke_ka, &[], 512, "AES", SecParam::D256);
+        let key_schedule = AES::new(&ke)?;
+        apply_ctr_keystream(&key_schedule, &iv[..CTR_NONCE_LEN], 0, &mut self.msg)?;
 
-        let (ke, ka) = ke_ka.split_at(key.len());
+        if self.digest == kmac_xof(&ka, &self.msg, 512, "AES", SecParam::D256) {
+            Ok(())
+        } else {
+            apply_ctr_keystream(&key_schedule, &iv[..CTR_NONCE_LEN], 0, &mut self.msg)?;
+            Err(OperationError::AESCTRDecryptionFailure)
+        }
+    }
 
-        let key_schedule = AES::new(ke);
+    /// # Random-Access Decryption using AES in CTR Mode
+    /// Decrypts `len` bytes of an AES-CTR encrypted [`Message`] starting at byte
+    /// `offset` of the ciphertext, without touching the rest of the message.
+    ///
+    /// SECURITY NOTE: the keyed hash in `Message.digest` covers the whole
+    /// plaintext and cannot be checked against a partial decryption. The
+    /// returned bytes are unauthenticated.
+    /// ## Arguments:
+    /// * `key: &[u8]`: symmetric encryption key.
+    /// * `offset: u64`: byte offset into the ciphertext.
+    /// * `len: usize`: number of bytes to decrypt.
+    /// ## Returns:
+    /// * The plaintext of ciphertext bytes `offset .. offset + len`.
+    fn aes_decrypt_ctr_range(
+        &self,
+        key: &[u8],
+        offset: u64,
+        len: usize,
+    ) -> Result<Vec<u8>, OperationError> {
+        let iv = self
+            .sym_nonce
+            .as_ref()
+            .ok_or(OperationError::SymNonceNotSet)?;
 
-        // Parallelize decryption for each block
-        self.msg
-            .par_chunks_mut(16)
-            .enumerate()
-            .for_each(|(i, block)| {
-                let mut temp = iv.clone();
-                let counter = i as u32;
-                temp.extend_from_slice(&counter.to_be_bytes());
+        let start = usize::try_from(offset).map_err(|_| OperationError::AESCTRRangeOutOfBounds)?;
+        let end = start
+            .checked_add(len)
+            .filter(|end| *end <= self.msg.len())
+            .ok_or(OperationError::AESCTRRangeOutOfBounds)?;
 
-                AES::encrypt_block(&mut temp, 0, &key_schedule.round_key);
+        check_key_size(self.aes_key_size, key)?;
+        let mut out = self.msg[start..end].to_vec();
+        aes_ctr_apply_at(key, iv, offset, &mut out)?;
+        Ok(out)
+    }
+}
 
-                xor_blocks(block, &temp);
-            });
+/// # Seekable AES-CTR
+/// XORs the keystream of an AES-CTR encryption made with
+/// [`AesEncryptable::aes_encrypt_ctr`] into `data`, treating `data` as the
+/// bytes found at `offset` of the message. Because CTR mode is symmetric this
+/// both encrypts and decrypts, so any byte range of a large ciphertext (read
+/// from disk, say) can be decrypted without processing what precedes it.
+///
+/// SECURITY NOTE: no authentication is performed.
+/// ## Arguments:
+/// * `key: &[u8]`: symmetric encryption key.
+/// * `iv: &[u8]`: the 16-byte IV stored in `Message.sym_nonce`.
+/// * `offset: u64`: byte offset of `data` within the message.
+/// * `data: &mut [u8]`: ciphertext or plaintext bytes, replaced in place.
+pub fn aes_ctr_apply_at(
+    key: &[u8],
+    iv: &[u8],
+    offset: u64,
+    data: &mut [u8],
+) -> Result<(), OperationError> {
+    if iv.len() != CTR_IV_LEN {
+        return Err(OperationError::InvalidNonceLength);
+    }
+    let (_, ke, _) = derive_keys(iv, key, "CTR")?;
+    apply_ctr_keystream(&AES::new(&ke)?, &iv[..CTR_NONCE_LEN], offset, data)
+}
+
+/// Derives the encryption and authentication subkeys for `mode`.
+/// * (ke || ka) ← kmac_xof(iv || key, “”, 8(|ke| + |ka|), “AES-{bits}-{mode}”)
+///
+/// Both subkey lengths are fixed by the AES variant, and the variant and mode
+/// are bound into the customization string so that a key of one size can
+/// never produce subkeys for another.
+fn derive_keys(
+    iv: &[u8],
+    key: &[u8],
+    mode: &str,
+) -> Result<(AesKeySize, Vec<u8>, Vec<u8>), OperationError> {
+    let key_size = AesKeySize::from_key_len(key.len())?;
+    let (ke_len, ka_len) = (key_size.enc_key_len(), key_size.mac_key_len());
+
+    let mut ke_ka = iv.to_vec();
+    ke_ka.extend_from_slice(key);
+    let s = format!("AES-{}-{}", key_size.bit_length(), mode);
+    let ke_ka = kmac_xof(&ke_ka, &[], (ke_len + ka_len) * 8, &s, SecParam::D256);
+
+    let (ke, ka) = ke_ka.split_at(ke_len);
+    Ok((key_size, ke.to_vec(), ka.to_vec()))
+}
 
-        kmac_xof(ka, &self.msg, 512, "AES", SecParam::D256);
+/// Rejects decryption keys whose size differs from the one the message was
+/// encrypted with.
+fn check_key_size(expected: Option<AesKeySize>, key: &[u8]) -> Result<(), OperationError> {
+    let expected = expected.ok_or(OperationError::AESKeySizeNotSet)?;
+    if AesKeySize::from_key_len(key.len())? == expected {
         Ok(())
+    } else {
+        Err(OperationError::AESKeySizeMismatch)
     }
 }
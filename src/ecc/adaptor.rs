@@ -0,0 +1,132 @@
+//! Adaptor signatures for the legacy Schnorr [`Signature`](crate::ecc::signable::Signature).
+//!
+//! A [`PreSignature`](crate::ecc::adaptor::PreSignature) is an incomplete
+//! signature bound to an adaptor point 𝑇 = t*𝑮. Anyone can check that it is
+//! valid for 𝑇, but only a holder of the adaptor secret t can
+//! [`adapt`](crate::ecc::adaptor::PreSignature::adapt) it into a signature
+//! that verifies with [`Signable::verify`](crate::ecc::signable::Signable::verify).
+//! Conversely, publishing the adapted signature reveals t to everyone who holds
+//! the pre-signature, see
+//! [`extract`](crate::ecc::adaptor::PreSignature::extract).
+//!
+//! In an atomic swap, Bob picks t and sends 𝑇 to Alice. Each party pre-signs
+//! its own payment under 𝑇 and hands the pre-signature to the other. To claim
+//! Alice's payment, Bob must adapt and publish her pre-signature, which hands
+//! t to Alice, who then adapts Bob's pre-signature to claim his payment.
+//! Either both payments complete or neither does.
+use super::{
+    edwards448::random_scalar,
+    keypair::KeyPair,
+    signable::{challenge, reduce, Signature},
+};
+use crate::{OperationError, SecParam};
+use serde::{Deserialize, Serialize};
+use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+
+/// A Schnorr pre-signature (h, 𝑍̂) under an adaptor point 𝑇, which becomes
+/// the signature (h, 𝑍̂ + t) once the adaptor secret t is known.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PreSignature {
+    /// keyed hash of the message and the adapted nonce point 𝑅 + 𝑇
+    pub h: Vec<u8>,
+    /// Incomplete response 𝑍̂ = k - h*s
+    #[serde(with = "super::edwards448::serde_scalar")]
+    pub z: Scalar,
+    /// adapted nonce point 𝑅 + 𝑇, carried into the adapted signature so that
+    /// [`verify_batch`](crate::ecc::signable::verify_batch) can use it
+    #[serde(with = "super::edwards448::serde_point")]
+    pub u: ExtendedPoint,
+}
+
+impl PreSignature {
+    /// # Pre-Signing
+    /// Pre-signs `msg` under the secret scalar s of `key` and the adaptor
+    /// point 𝑇. The nonce is random, since reusing a nonce for two adaptor
+    /// points reveals s.
+    /// ## Algorithm:
+    /// * k ← Random(912) mod r
+    /// * 𝑅 ← k*𝑮
+    /// * 𝑈 ← 𝑅 + 𝑇
+    /// * h ← kmac_xof(𝑈ₓ, m, 448, “T”)
+    /// * 𝑍̂ ← (k - h*s) mod r
+    /// ## Arguments:
+    /// * msg: &[u8] : the message to sign
+    /// * key: &[`KeyPair`] : the signing key
+    /// * adaptor: &ExtendedPoint : the adaptor point 𝑇
+    /// * d: [`SecParam`] : the security parameter to verify the signature with
+    pub fn sign(msg: &[u8], key: &KeyPair, adaptor: &ExtendedPoint, d: SecParam) -> PreSignature {
+        let k = random_scalar();
+        let u = ExtendedPoint::generator() * k + *adaptor;
+        let h = challenge(&u, msg, d);
+        let z = k.sub_mod(&reduce(&h).mul_mod(key.priv_key.expose()));
+        PreSignature { h, z, u }
+    }
+
+    /// # Pre-Signature Verification
+    /// Checks that the pre-signature adapts to a valid signature on `msg`
+    /// under `pub_key` once the discrete logarithm of `adaptor` is known.
+    /// ## Algorithm:
+    /// * 𝑅 ← 𝑍̂*𝑮 + h*𝑉
+    /// * accept iff 𝑈 = 𝑅 + 𝑇 and h = kmac_xof(𝑈ₓ, m, 448, “T”)
+    /// ## Errors:
+    /// * [`OperationError::SignatureVerificationFailure`] if the pre-signature
+    ///   does not verify.
+    pub fn verify(
+        &self,
+        msg: &[u8],
+        pub_key: &ExtendedPoint,
+        adaptor: &ExtendedPoint,
+        d: SecParam,
+    ) -> Result<(), OperationError> {
+        let r = ExtendedPoint::generator() * self.z + *pub_key * reduce(&self.h);
+        if self.u == r + *adaptor && challenge(&self.u, msg, d) == self.h {
+            Ok(())
+        } else {
+            Err(OperationError::SignatureVerificationFailure)
+        }
+    }
+
+    /// # Adaptation
+    /// Completes the pre-signature with the adaptor secret t.
+    /// ## Algorithm:
+    /// * check t*𝑮 = 𝑇
+    /// * return (h, (𝑍̂ + t) mod r, 𝑈), which satisfies (𝑍̂ + t)*𝑮 + h*𝑉 = 𝑈
+    /// ## Errors:
+    /// * [`OperationError::InvalidAdaptorSecret`] if t is not the discrete
+    ///   logarithm of 𝑇.
+    pub fn adapt(
+        &self,
+        adaptor_secret: &Scalar,
+        adaptor: &ExtendedPoint,
+    ) -> Result<Signature, OperationError> {
+        if ExtendedPoint::generator() * *adaptor_secret != *adaptor {
+            return Err(OperationError::InvalidAdaptorSecret);
+        }
+        Ok(Signature {
+            h: self.h.clone(),
+            z: self.z.add_mod(adaptor_secret),
+            u: Some(self.u),
+        })
+    }
+
+    /// # Secret Extraction
+    /// Recovers the adaptor secret t from a signature adapted from this
+    /// pre-signature.
+    /// ## Algorithm:
+    /// * t ← (𝑍 - 𝑍̂) mod r
+    /// * check t*𝑮 = 𝑇
+    /// ## Errors:
+    /// * [`OperationError::InvalidAdaptorSecret`] if the signature was not
+    ///   adapted from this pre-signature under 𝑇.
+    pub fn extract(
+        &self,
+        sig: &Signature,
+        adaptor: &ExtendedPoint,
+    ) -> Result<Scalar, OperationError> {
+        let t = sig.z.sub_mod(&self.z);
+        if sig.h != self.h || ExtendedPoint::generator() * t != *adaptor {
+            return Err(OperationError::InvalidAdaptorSecret);
+        }
+        Ok(t)
+    }
+}
@@ -0,0 +1,191 @@
+//! Blind signing for the legacy Schnorr [`Signature`](crate::ecc::signable::Signature).
+//!
+//! A user obtains a signature on a message the signer never sees, and the
+//! signer cannot later link the signature to the session that produced it.
+//! This suits anonymous tokens: the user signs a random serial number, and the
+//! service accepts each serial once. The result verifies with
+//! [`Signable::verify`](crate::ecc::signable::Signable::verify) like any other
+//! signature under the signer's key:
+//! 1. The signer opens a session with
+//!    [`BlindSigner::commit`](crate::ecc::blind_schnorr::BlindSigner::commit)
+//!    and sends the nonce point 𝑅.
+//! 2. The user blinds 𝑅 and the message with
+//!    [`BlindingSession::blind`](crate::ecc::blind_schnorr::BlindingSession::blind)
+//!    and sends the blinded challenge.
+//! 3. The signer answers with
+//!    [`BlindSigner::respond`](crate::ecc::blind_schnorr::BlindSigner::respond),
+//!    which closes the session.
+//! 4. The user unblinds the answer with
+//!    [`BlindingSession::unblind`](crate::ecc::blind_schnorr::BlindingSession::unblind).
+//!
+//! # ROS attacks
+//! Blind Schnorr signatures are only secure when the signer runs sessions one
+//! after another. An attacker who opens ℓ sessions concurrently can choose
+//! the challenges of all of them so that ℓ responses yield ℓ + 1 valid
+//! signatures, solving the ROS problem of Benhamouda et al. (Eurocrypt 2021)
+//! in polynomial time once ℓ exceeds log₂ r ≈ 446, and much faster than brute
+//! force for smaller ℓ. This module mitigates the attack as follows:
+//! * A [`BlindSigner`](crate::ecc::blind_schnorr::BlindSigner) holds at most
+//!   one open session. Opening another one before responding or calling
+//!   [`BlindSigner::abort`](crate::ecc::blind_schnorr::BlindSigner::abort)
+//!   fails, and every nonce is used for at most one response.
+//! * Applications must not run several `BlindSigner`s for the same key at the
+//!   same time, e.g. on multiple threads or servers, since this reopens
+//!   concurrent sessions. Serialize issuance behind one signer per key.
+//! * Sessions should be aborted after a short timeout so that a stalled user
+//!   cannot block issuance, and keys should be rotated periodically to bound
+//!   the number of signatures any attack could forge.
+//!
+//! Applications that need concurrent issuance should use a scheme designed for
+//! it instead of this module.
+use super::{
+    edwards448::random_scalar,
+    keypair::{KeyPair, SecretScalar},
+    signable::{challenge, reduce, Signature},
+};
+use crate::{OperationError, SecParam};
+use serde::{Deserialize, Serialize};
+use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+
+/// The signer of the blind protocol, holding the key pair and the nonce of
+/// the open session, if any.
+pub struct BlindSigner {
+    key: KeyPair,
+    nonce: Option<SecretScalar>,
+}
+
+/// The signer's nonce point 𝑅 = k*𝑮 opening a session.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct SignerCommitment(#[serde(with = "super::edwards448::serde_point")] pub ExtendedPoint);
+
+/// The blinded challenge c sent from the user to the signer.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct BlindChallenge(#[serde(with = "super::edwards448::serde_scalar")] pub Scalar);
+
+/// The signer's response 𝑍 = k - c*s to a blinded challenge.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct BlindResponse(#[serde(with = "super::edwards448::serde_scalar")] pub Scalar);
+
+/// The user's secret state between blinding and unblinding. The blinding
+/// factor α is zeroized when the session is unblinded or dropped.
+pub struct BlindingSession {
+    pub_key: ExtendedPoint,
+    commitment: ExtendedPoint,
+    blinded_nonce: ExtendedPoint,
+    alpha: SecretScalar,
+    h: Vec<u8>,
+    challenge: Scalar,
+}
+
+impl BlindSigner {
+    /// Creates a signer issuing blind signatures under `key`.
+    pub fn new(key: KeyPair) -> BlindSigner {
+        BlindSigner { key, nonce: None }
+    }
+
+    /// The public key 𝑉 = s*𝑮 the signatures verify under.
+    pub fn public_key(&self) -> ExtendedPoint {
+        self.key.pub_key
+    }
+
+    /// # Session Opening
+    /// Opens a session and returns the nonce point to send to the user.
+    /// ## Algorithm:
+    /// * k ← Random(912) mod r
+    /// * 𝑅 ← k*𝑮
+    /// ## Errors:
+    /// * [`OperationError::BlindSessionInProgress`] if a session is already
+    ///   open, see the ROS attacks section of the
+    ///   [module documentation](crate::ecc::blind_schnorr).
+    pub fn commit(&mut self) -> Result<SignerCommitment, OperationError> {
+        if self.nonce.is_some() {
+            return Err(OperationError::BlindSessionInProgress);
+        }
+        let k = SecretScalar::new(random_scalar());
+        let commitment = SignerCommitment(ExtendedPoint::generator() * *k.expose());
+        self.nonce = Some(k);
+        Ok(commitment)
+    }
+
+    /// # Blind Signing
+    /// Answers the user's blinded challenge and closes the session. The nonce
+    /// is discarded, so it is never used for a second response.
+    /// ## Algorithm:
+    /// * 𝑍 ← (k - c*s) mod r
+    /// ## Errors:
+    /// * [`OperationError::NoBlindSession`] unless a session is open.
+    pub fn respond(&mut self, challenge: &BlindChallenge) -> Result<BlindResponse, OperationError> {
+        let k = self.nonce.take().ok_or(OperationError::NoBlindSession)?;
+        let s = self.key.priv_key.expose();
+        Ok(BlindResponse(k.expose().sub_mod(&challenge.0.mul_mod(s))))
+    }
+
+    /// Closes the open session without responding, e.g. after a timeout.
+    pub fn abort(&mut self) {
+        self.nonce = None;
+    }
+}
+
+impl BlindingSession {
+    /// # Blinding
+    /// Blinds the signer's nonce point and the message, and returns the
+    /// challenge to send to the signer.
+    /// ## Algorithm:
+    /// * α, β ← Random(912) mod r
+    /// * 𝑅' ← 𝑅 + α*𝑮 + β*𝑉
+    /// * h ← kmac_xof(𝑅'ₓ, m, 448, “T”)
+    /// * c ← (h - β) mod r
+    /// ## Arguments:
+    /// * pub_key: &ExtendedPoint : the signer's public key 𝑉
+    /// * commitment: &[`SignerCommitment`] : the signer's nonce point 𝑅
+    /// * msg: &[u8] : the message to sign, which the signer never sees
+    /// * d: [`SecParam`] : the security parameter to verify the signature with
+    pub fn blind(
+        pub_key: &ExtendedPoint,
+        commitment: &SignerCommitment,
+        msg: &[u8],
+        d: SecParam,
+    ) -> (BlindingSession, BlindChallenge) {
+        let alpha = SecretScalar::new(random_scalar());
+        let beta = SecretScalar::new(random_scalar());
+        let blinded_nonce =
+            commitment.0 + ExtendedPoint::generator() * *alpha.expose() + *pub_key * *beta.expose();
+
+        let h = challenge(&blinded_nonce, msg, d);
+        let challenge = reduce(&h).sub_mod(beta.expose());
+
+        let session = BlindingSession {
+            pub_key: *pub_key,
+            commitment: commitment.0,
+            blinded_nonce,
+            alpha,
+            h,
+            challenge,
+        };
+        (session, BlindChallenge(challenge))
+    }
+
+    /// # Unblinding
+    /// Checks the signer's response and turns it into a signature on the
+    /// message, which verifies with
+    /// [`Signable::verify`](crate::ecc::signable::Signable::verify) under 𝑉
+    /// and the [`SecParam`] passed to [`BlindingSession::blind`].
+    /// ## Algorithm:
+    /// * check 𝑍*𝑮 + c*𝑉 = 𝑅
+    /// * 𝑍' ← (𝑍 + α) mod r
+    /// * return (h, 𝑍'), which satisfies 𝑍'*𝑮 + h*𝑉 = 𝑅'
+    /// ## Errors:
+    /// * [`OperationError::SignatureVerificationFailure`] if the response is
+    ///   not valid for the session.
+    pub fn unblind(self, response: &BlindResponse) -> Result<Signature, OperationError> {
+        let expected = ExtendedPoint::generator() * response.0 + self.pub_key * self.challenge;
+        if expected != self.commitment {
+            return Err(OperationError::SignatureVerificationFailure);
+        }
+        Ok(Signature {
+            h: self.h.clone(),
+            z: response.0.add_mod(self.alpha.expose()),
+            u: Some(self.blinded_nonce),
+        })
+    }
+}
@@ -0,0 +1,399 @@
+//! Ed448 and Ed448ph signatures as specified in RFC 8032 Section 5.2.
+//!
+//! Unlike the [`Signable`](crate::ecc::signable::Signable) Schnorr scheme,
+//! which is kept as the crate's legacy signature format, signatures produced
+//! here interoperate with any RFC 8032 implementation: keys and signatures use
+//! the standard 57-byte encodings and support context strings of up to 255
+//! bytes.
+use super::edwards448::{
+    decode_point, encode_base_multiple, find_invalid, multiscalar_mul, scalar_from_bytes_wide,
+    scalar_from_canonical_bytes, scalar_negate, scalar_to_bytes_le, POINT_BYTES, SCALAR_BYTES,
+};
+use crate::{
+    sha3::{aux_functions::byte_utils::get_random_bytes, shake_functions::shake256},
+    Message, OperationError,
+};
+use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+use zeroize::Zeroize;
+
+/// Length in bytes of an Ed448 secret key (seed).
+pub const SECRET_KEY_BYTES: usize = 57;
+/// Length in bytes of an encoded Ed448 signature.
+pub const SIGNATURE_BYTES: usize = POINT_BYTES + SCALAR_BYTES;
+
+/// An Ed448 secret key together with the values expanded from it.
+#[derive(Clone)]
+pub struct SigningKey {
+    seed: [u8; SECRET_KEY_BYTES],
+    scalar: Scalar,
+    prefix: [u8; SCALAR_BYTES],
+    verifying_key: VerifyingKey,
+}
+
+/// An Ed448 public key.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VerifyingKey {
+    encoded: [u8; POINT_BYTES],
+    point: ExtendedPoint,
+}
+
+/// An Ed448 signature (R, S).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ed448Signature {
+    r: [u8; POINT_BYTES],
+    s: [u8; SCALAR_BYTES],
+}
+
+impl SigningKey {
+    /// # Ed448 Key Generation
+    /// Expands a 57-byte secret key as in RFC 8032 5.2.5.
+    /// ## Algorithm:
+    /// * h ← SHAKE256(seed, 114)
+    /// * s ← h[0..57] with the two lowest bits and the last byte cleared and
+    ///   the highest bit of the second to last byte set
+    /// * prefix ← h[57..114]
+    /// * A ← s*B
+    /// ## Errors:
+    /// * [`OperationError::InvalidSecretLength`] unless `seed` is 57 bytes long.
+    pub fn from_seed(seed: &[u8]) -> Result<SigningKey, OperationError> {
+        let seed: [u8; SECRET_KEY_BYTES] = seed
+            .try_into()
+            .map_err(|_| OperationError::InvalidSecretLength)?;
+
+        let h = shake256(&seed, 8 * 2 * SCALAR_BYTES);
+        let mut s_bytes = [0u8; SCALAR_BYTES];
+        s_bytes.copy_from_slice(&h[..SCALAR_BYTES]);
+        s_bytes[0] &= 0xFC;
+        s_bytes[55] |= 0x80;
+        s_bytes[56] = 0;
+        let scalar = scalar_from_bytes_wide(&s_bytes);
+
+        let mut prefix = [0u8; SCALAR_BYTES];
+        prefix.copy_from_slice(&h[SCALAR_BYTES..]);
+
+        let verifying_key = VerifyingKey {
+            encoded: encode_base_multiple(&scalar),
+            point: ExtendedPoint::generator() * scalar,
+        };
+
+        Ok(SigningKey {
+            seed,
+            scalar,
+            prefix,
+            verifying_key,
+        })
+    }
+
+    /// Generates a signing key from a random 57-byte seed.
+    pub fn generate() -> SigningKey {
+        Self::from_seed(&get_random_bytes(SECRET_KEY_BYTES as u64)).unwrap()
+    }
+
+    /// The 57-byte secret key this key was expanded from.
+    pub fn to_bytes(&self) -> [u8; SECRET_KEY_BYTES] {
+        self.seed
+    }
+
+    /// The secret scalar s expanded from the seed.
+    pub(crate) fn scalar(&self) -> &Scalar {
+        &self.scalar
+    }
+
+    /// The public key matching this secret key.
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.verifying_key
+    }
+
+    /// # Ed448 Signing
+    /// Signs `msg` under `context` as in RFC 8032 5.2.6.
+    /// ## Algorithm:
+    /// * r ← SHAKE256(dom4(0, context) || prefix || msg, 114) mod L
+    /// * R ← r*B
+    /// * k ← SHAKE256(dom4(0, context) || R || A || msg, 114) mod L
+    /// * S ← (r + k * s) mod L
+    /// ## Errors:
+    /// * [`OperationError::ContextTooLong`] if `context` is longer than 255 bytes.
+    pub fn sign(&self, msg: &[u8], context: &[u8]) -> Result<Ed448Signature, OperationError> {
+        self.sign_with_flag(0, context, msg)
+    }
+
+    /// # Ed448ph Signing
+    /// Signs SHAKE256(`msg`, 64) under `context` with the pre-hash flag set.
+    /// ## Errors:
+    /// * [`OperationError::ContextTooLong`] if `context` is longer than 255 bytes.
+    pub fn sign_prehashed(
+        &self,
+        msg: &[u8],
+        context: &[u8],
+    ) -> Result<Ed448Signature, OperationError> {
+        self.sign_with_flag(1, context, &prehash(msg))
+    }
+
+    fn sign_with_flag(
+        &self,
+        phflag: u8,
+        context: &[u8],
+        m: &[u8],
+    ) -> Result<Ed448Signature, OperationError> {
+        let dom = dom4(phflag, context)?;
+
+        let mut nonce_input = dom.clone();
+        nonce_input.extend_from_slice(&self.prefix);
+        nonce_input.extend_from_slice(m);
+        let r = scalar_from_bytes_wide(&shake256(&nonce_input, 8 * 2 * SCALAR_BYTES));
+
+        let r_bytes = encode_base_multiple(&r);
+        let k = challenge(&dom, &r_bytes, &self.verifying_key.encoded, m);
+        let s = r.add_mod(&k.mul_mod(&self.scalar));
+
+        Ok(Ed448Signature {
+            r: r_bytes,
+            s: scalar_to_bytes_le(&s),
+        })
+    }
+}
+
+impl Drop for SigningKey {
+    fn drop(&mut self) {
+        self.seed.zeroize();
+        self.scalar.val.zeroize();
+        self.prefix.zeroize();
+    }
+}
+
+impl VerifyingKey {
+    /// Decodes a 57-byte Ed448 public key.
+    /// ## Errors:
+    /// * [`OperationError::InvalidPointEncoding`] if `bytes` is not a valid point encoding.
+    pub fn from_bytes(bytes: &[u8]) -> Result<VerifyingKey, OperationError> {
+        let point = decode_point(bytes)?;
+        let mut encoded = [0u8; POINT_BYTES];
+        encoded.copy_from_slice(bytes);
+        Ok(VerifyingKey { encoded, point })
+    }
+
+    /// The 57-byte encoding of this public key.
+    pub fn to_bytes(&self) -> [u8; POINT_BYTES] {
+        self.encoded
+    }
+
+    /// The public key as a point on the twisted curve used by the rest of the crate.
+    pub fn to_point(&self) -> ExtendedPoint {
+        self.point
+    }
+
+    /// # Ed448 Verification
+    /// Verifies `sig` on `msg` under `context` as in RFC 8032 5.2.7.
+    /// ## Algorithm:
+    /// * k ← SHAKE256(dom4(0, context) || R || A || msg, 114) mod L
+    /// * accept iff \[4\]S*B = \[4\]R + \[4\]k*A
+    /// ## Errors:
+    /// * [`OperationError::ContextTooLong`] if `context` is longer than 255 bytes.
+    /// * [`OperationError::SignatureVerificationFailure`] if the signature is
+    ///   malformed or does not verify.
+    pub fn verify(
+        &self,
+        msg: &[u8],
+        sig: &Ed448Signature,
+        context: &[u8],
+    ) -> Result<(), OperationError> {
+        self.verify_with_flag(0, context, msg, sig)
+    }
+
+    /// # Ed448ph Verification
+    /// Verifies an Ed448ph signature produced by [`SigningKey::sign_prehashed`].
+    /// ## Errors:
+    /// * [`OperationError::ContextTooLong`] if `context` is longer than 255 bytes.
+    /// * [`OperationError::SignatureVerificationFailure`] if the signature is
+    ///   malformed or does not verify.
+    pub fn verify_prehashed(
+        &self,
+        msg: &[u8],
+        sig: &Ed448Signature,
+        context: &[u8],
+    ) -> Result<(), OperationError> {
+        self.verify_with_flag(1, context, &prehash(msg), sig)
+    }
+
+    #[allow(non_snake_case)]
+    fn verify_with_flag(
+        &self,
+        phflag: u8,
+        context: &[u8],
+        m: &[u8],
+        sig: &Ed448Signature,
+    ) -> Result<(), OperationError> {
+        let dom = dom4(phflag, context)?;
+        let R = decode_point(&sig.r).map_err(|_| OperationError::SignatureVerificationFailure)?;
+        let S = scalar_from_canonical_bytes(&sig.s)
+            .map_err(|_| OperationError::SignatureVerificationFailure)?;
+        let k = challenge(&dom, &sig.r, &self.encoded, m);
+
+        // The decoded points carry no small-order component, which makes
+        // this the cofactored equation.
+        if ExtendedPoint::generator() * S == R + self.point * k {
+            Ok(())
+        } else {
+            Err(OperationError::SignatureVerificationFailure)
+        }
+    }
+}
+
+impl Ed448Signature {
+    /// Parses a 114-byte signature R || S.
+    /// ## Errors:
+    /// * [`OperationError::InvalidSignatureLength`] unless `bytes` is 114 bytes long.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Ed448Signature, OperationError> {
+        if bytes.len() != SIGNATURE_BYTES {
+            return Err(OperationError::InvalidSignatureLength);
+        }
+        let mut r = [0u8; POINT_BYTES];
+        let mut s = [0u8; SCALAR_BYTES];
+        r.copy_from_slice(&bytes[..POINT_BYTES]);
+        s.copy_from_slice(&bytes[POINT_BYTES..]);
+        Ok(Ed448Signature { r, s })
+    }
+
+    /// The 114-byte encoding R || S.
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_BYTES] {
+        let mut out = [0u8; SIGNATURE_BYTES];
+        out[..POINT_BYTES].copy_from_slice(&self.r);
+        out[POINT_BYTES..].copy_from_slice(&self.s);
+        out
+    }
+}
+
+pub trait Ed448Signable {
+    fn ed448_sign(&mut self, key: &SigningKey, context: &[u8]) -> Result<(), OperationError>;
+    fn ed448_verify(&self, key: &VerifyingKey, context: &[u8]) -> Result<(), OperationError>;
+}
+
+impl Ed448Signable for Message {
+    /// # Ed448 Signatures
+    /// Signs a [`Message`] with RFC 8032 Ed448.
+    /// ## Replaces:
+    /// * `Message.ed448_sig` with the 114-byte signature.
+    /// ## Arguments:
+    /// * key: &[`SigningKey`]: Ed448 secret key.
+    /// * context: &[u8]: context string of at most 255 bytes, may be empty.
+    fn ed448_sign(&mut self, key: &SigningKey, context: &[u8]) -> Result<(), OperationError> {
+        let sig = key.sign(&self.msg, context)?;
+        self.ed448_sig = Some(sig.to_bytes().to_vec());
+        Ok(())
+    }
+
+    /// # Ed448 Verification
+    /// Verifies `Message.ed448_sig` on the message under `key` and `context`.
+    /// ## Errors:
+    /// * [`OperationError::SignatureNotSet`] if the message carries no Ed448 signature.
+    fn ed448_verify(&self, key: &VerifyingKey, context: &[u8]) -> Result<(), OperationError> {
+        let sig = self
+            .ed448_sig
+            .as_ref()
+            .ok_or(OperationError::SignatureNotSet)?;
+        key.verify(&self.msg, &Ed448Signature::from_bytes(sig)?, context)
+    }
+}
+
+/// # Ed448 Batch Verification
+/// Verifies many (message, signature, public key) triples under a shared
+/// `context` at once, accepting exactly when [`VerifyingKey::verify`] accepts
+/// every item, except with probability 2⁻¹²⁸.
+/// ## Algorithm:
+/// * kᵢ ← SHAKE256(dom4(0, context) || Rᵢ || Aᵢ || mᵢ, 114) mod L
+/// * zᵢ ← Random(128)
+/// * accept iff (Σ zᵢSᵢ)*B - Σ zᵢ*Rᵢ - Σ (zᵢkᵢ)*Aᵢ = 𝒪, computed with one
+///   [`multiscalar_mul`] over 2n + 1 points
+/// * if a batch is rejected, its halves are checked the same way until the
+///   invalid items are isolated
+/// ## Errors:
+/// * [`OperationError::ContextTooLong`] if `context` is longer than 255 bytes.
+/// * [`OperationError::BatchVerificationFailure`] with the sorted indices of
+///   all items that are malformed or do not verify.
+#[allow(non_snake_case)]
+pub fn verify_batch(
+    items: &[(&[u8], &Ed448Signature, &VerifyingKey)],
+    context: &[u8],
+) -> Result<(), OperationError> {
+    let dom = dom4(0, context)?;
+    let mut invalid = Vec::new();
+    let mut entries = Vec::with_capacity(items.len());
+    for (index, (msg, sig, key)) in items.iter().enumerate() {
+        match (decode_point(&sig.r), scalar_from_canonical_bytes(&sig.s)) {
+            (Ok(R), Ok(S)) => entries.push(BatchEntry {
+                index,
+                R,
+                S,
+                k: challenge(&dom, &sig.r, &key.encoded, msg),
+                A: key.point,
+            }),
+            _ => invalid.push(index),
+        }
+    }
+
+    invalid.extend(
+        find_invalid(&entries, &batch_equation_holds)
+            .into_iter()
+            .map(|entry| entry.index),
+    );
+    if invalid.is_empty() {
+        Ok(())
+    } else {
+        invalid.sort_unstable();
+        Err(OperationError::BatchVerificationFailure(invalid))
+    }
+}
+
+// A parsed batch item. The decoded points carry no small-order component,
+// so the batch equation holds exactly when every cofactored equation does.
+#[allow(non_snake_case)]
+struct BatchEntry {
+    index: usize,
+    R: ExtendedPoint,
+    S: Scalar,
+    k: Scalar,
+    A: ExtendedPoint,
+}
+
+fn batch_equation_holds(entries: &[BatchEntry]) -> bool {
+    let mut s_sum = Scalar::from(0_u64);
+    let mut scalars = Vec::with_capacity(2 * entries.len() + 1);
+    let mut points = Vec::with_capacity(2 * entries.len() + 1);
+    for entry in entries {
+        let z = scalar_from_bytes_wide(&get_random_bytes(16));
+        s_sum = s_sum.add_mod(&z.mul_mod(&entry.S));
+        scalars.push(scalar_negate(&z));
+        points.push(entry.R);
+        scalars.push(scalar_negate(&z.mul_mod(&entry.k)));
+        points.push(entry.A);
+    }
+    scalars.push(s_sum);
+    points.push(ExtendedPoint::generator());
+    multiscalar_mul(&scalars, &points) == ExtendedPoint::id_point()
+}
+
+// dom4(F, C) = "SigEd448" || octet(F) || octet(|C|) || C
+fn dom4(phflag: u8, context: &[u8]) -> Result<Vec<u8>, OperationError> {
+    if context.len() > 255 {
+        return Err(OperationError::ContextTooLong);
+    }
+    let mut out = b"SigEd448".to_vec();
+    out.push(phflag);
+    out.push(context.len() as u8);
+    out.extend_from_slice(context);
+    Ok(out)
+}
+
+// k = SHAKE256(dom4 || R || A || M, 114) mod L
+fn challenge(dom: &[u8], r: &[u8], a: &[u8], m: &[u8]) -> Scalar {
+    let mut input = dom.to_vec();
+    input.extend_from_slice(r);
+    input.extend_from_slice(a);
+    input.extend_from_slice(m);
+    scalar_from_bytes_wide(&shake256(&input, 8 * 2 * SCALAR_BYTES))
+}
+
+// PH(M) = SHAKE256(M, 64)
+fn prehash(msg: &[u8]) -> Vec<u8> {
+    shake256(msg, 512)
+}
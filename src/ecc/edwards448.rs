@@ -0,0 +1,536 @@
+//! Encoding layer between the twisted Edwards points of `tiny_ed448_goldilocks`
+//! and the edwards448 curve of RFC 8032.
+//!
+//! All group arithmetic in this crate happens on the twisted curve
+//! a = -1, d = -39082, whose generator 𝑮 is the image of the RFC 8032 base
+//! point B under the 4-isogeny φ. The dual isogeny ψ satisfies ψ(φ(P)) = 4P, so
+//! an edwards448 point A of the prime-order subgroup is represented by φ(A) and
+//! recovered for encoding as A = ψ(4⁻¹ * φ(A)). Since s*𝑮 = φ(s*B), the public key
+//! of a [`KeyPair`](crate::ecc::keypair::KeyPair) is already the image of the
+//! RFC 8032 public key belonging to the same secret scalar.
+use crate::{
+    sha3::{aux_functions::byte_utils::get_random_bytes, shake_functions::shake256},
+    OperationError,
+};
+use crypto_bigint::{subtle::ConstantTimeEq, Encoding, NonZero, U1024, U448};
+use tiny_ed448_goldilocks::curve::{
+    extended_edwards::ExtendedPoint,
+    field::{
+        field_element::FieldElement,
+        scalar::{Scalar, R_448},
+    },
+};
+use zeroize::Zeroize;
+
+/// Length in bytes of an encoded edwards448 point.
+pub const POINT_BYTES: usize = 57;
+/// Length in bytes of an encoded scalar.
+pub const SCALAR_BYTES: usize = 57;
+
+/// The prime order r of the curve subgroup.
+pub(crate) const ORDER: U448 = U448::from_be_hex(R_448);
+/// 4⁻¹ mod r, undoes the factor of 4 introduced by ψ ∘ φ.
+pub(crate) const INV_FOUR: U448 = U448::from_be_hex(
+    "0fffffffffffffffffffffffffffffffffffffffffffffffffffffffdf3288fa7113b6d26bb58da4085b309ca37163d548de30a4aad6113d",
+);
+
+/// # Point Encoding
+/// Encodes a point of the prime-order subgroup as the 57-byte RFC 8032
+/// encoding of the edwards448 point it represents.
+/// ## Algorithm:
+/// * (x, y) ← ψ(4⁻¹ * P)
+/// * encoding ← y in 56 little-endian bytes || (x mod 2) << 7
+pub fn encode_point(p: &ExtendedPoint) -> [u8; POINT_BYTES] {
+    compress_dual(&(*p * Scalar::from(INV_FOUR)))
+}
+
+/// Encodes k*𝑮 with a single scalar multiplication, see [`encode_point`].
+pub(crate) fn encode_base_multiple(k: &Scalar) -> [u8; POINT_BYTES] {
+    compress_dual(&(ExtendedPoint::generator() * k.mul_mod(&Scalar::from(INV_FOUR))))
+}
+
+/// # Point Decoding
+/// Decodes a 57-byte RFC 8032 point encoding A and returns φ(A).
+/// φ maps the small-order component of A to the identity, so equations checked
+/// on the decoded points are the cofactored equations of RFC 8032.
+/// ## Errors:
+/// * [`OperationError::InvalidPointEncoding`] if the encoding has the wrong length,
+///   a non-canonical y-coordinate, or does not decode to a point on the curve.
+pub fn decode_point(bytes: &[u8]) -> Result<ExtendedPoint, OperationError> {
+    let (x, y) = decompress(bytes)?;
+    Ok(isogeny(&x, &y))
+}
+
+/// # Validated Point Decoding
+/// Decodes a 57-byte RFC 8032 point encoding that must name a point of the
+/// prime-order subgroup other than the identity, as expected of public keys
+/// and ephemeral points.
+/// ## Algorithm:
+/// * P ← decode_point(bytes), which checks that the point is on the curve
+/// * accept iff P ≠ 𝒪 and encode_point(P) = bytes. φ drops the small-order
+///   component of the decoded point, so re-encoding only reproduces `bytes`
+///   for points of the prime-order subgroup.
+/// ## Errors:
+/// * [`OperationError::InvalidPointEncoding`] if any of the checks fail.
+pub fn decode_point_checked(bytes: &[u8]) -> Result<ExtendedPoint, OperationError> {
+    let p = decode_point(bytes)?;
+    if p == ExtendedPoint::id_point() || encode_point(&p)[..] != bytes[..] {
+        return Err(OperationError::InvalidPointEncoding);
+    }
+    Ok(p)
+}
+
+/// # Nothing-Up-My-Sleeve Generator
+/// Derives a generator of the prime-order subgroup from `label` whose discrete
+/// logarithm to 𝑮 is unknown, such as the second generator of Pedersen
+/// commitments.
+/// ## Algorithm:
+/// * for c = 0, 1, 2, …: P ← decode_point(SHAKE256(label || c, 57)), with c
+///   as 4 little-endian bytes
+/// * return the first P that decodes and is not 𝒪
+pub fn nums_generator(label: &[u8]) -> ExtendedPoint {
+    (0_u32..)
+        .find_map(|c| {
+            let mut input = label.to_vec();
+            input.extend_from_slice(&c.to_le_bytes());
+            decode_point(&shake256(&input, 8 * POINT_BYTES))
+                .ok()
+                .filter(|p| *p != ExtendedPoint::id_point())
+        })
+        .unwrap()
+}
+
+/// # Multiscalar Multiplication
+/// Computes Σ sᵢ*Pᵢ over `scalars` and `points` with Straus' method: one shared chain of
+/// doublings and, per point, a table of its first 15 multiples indexed by
+/// 4-bit windows of the scalar. Runs in variable time, so it must only be used
+/// on public inputs such as signatures being verified.
+///
+/// Panics if `scalars` and `points` differ in length.
+pub fn multiscalar_mul(scalars: &[Scalar], points: &[ExtendedPoint]) -> ExtendedPoint {
+    assert_eq!(scalars.len(), points.len());
+    let tables: Vec<[ExtendedPoint; 15]> = points
+        .iter()
+        .map(|p| {
+            let mut table = [*p; 15];
+            for j in 1..15 {
+                table[j] = table[j - 1].add(p);
+            }
+            table
+        })
+        .collect();
+    let digits: Vec<[u8; 56]> = scalars.iter().map(|s| s.val.to_le_bytes()).collect();
+
+    let mut acc = ExtendedPoint::id_point();
+    for i in (0..2 * 56).rev() {
+        for _ in 0..4 {
+            acc = acc.double();
+        }
+        for (table, bytes) in tables.iter().zip(&digits) {
+            let nibble = (bytes[i / 2] >> (4 * (i % 2))) & 0x0F;
+            if nibble != 0 {
+                acc = acc.add(&table[nibble as usize - 1]);
+            }
+        }
+    }
+    acc
+}
+
+/// # Batch Bisection
+/// Checks `entries` as one batch with `holds` and, if the batch is rejected,
+/// checks its halves the same way until the entries that fail on their own are
+/// isolated. Returns those entries in order.
+pub(crate) fn find_invalid<'a, T>(entries: &'a [T], holds: &impl Fn(&[T]) -> bool) -> Vec<&'a T> {
+    let mut invalid = Vec::new();
+    bisect(entries, holds, &mut invalid);
+    invalid
+}
+
+fn bisect<'a, T>(entries: &'a [T], holds: &impl Fn(&[T]) -> bool, invalid: &mut Vec<&'a T>) {
+    if entries.is_empty() || holds(entries) {
+        return;
+    }
+    if entries.len() == 1 {
+        invalid.push(&entries[0]);
+        return;
+    }
+    let (left, right) = entries.split_at(entries.len() / 2);
+    bisect(left, holds, invalid);
+    bisect(right, holds, invalid);
+}
+
+/// Reduces a little-endian integer of up to 128 bytes modulo r. Longer inputs
+/// panic, so this stays internal to callers that pass at most 114 bytes.
+pub(crate) fn scalar_from_bytes_wide(bytes: &[u8]) -> Scalar {
+    let mut wide = [0u8; 128];
+    wide[..bytes.len()].copy_from_slice(bytes);
+    let modulus = NonZero::new(ORDER.resize::<{ U1024::LIMBS }>()).unwrap();
+    let reduced = U1024::from_le_slice(&wide).rem(&modulus);
+    Scalar::from(reduced.resize::<{ U448::LIMBS }>())
+}
+
+/// A uniformly random scalar, reduced from 114 random bytes so that the bias
+/// modulo r is negligible.
+pub(crate) fn random_scalar() -> Scalar {
+    let mut bytes = get_random_bytes(114);
+    let s = scalar_from_bytes_wide(&bytes);
+    bytes.zeroize();
+    s
+}
+
+/// Parses a 57-byte little-endian scalar encoding.
+/// ## Errors:
+/// * [`OperationError::InvalidScalarEncoding`] unless the value is fully reduced modulo r.
+pub fn scalar_from_canonical_bytes(bytes: &[u8]) -> Result<Scalar, OperationError> {
+    if bytes.len() != SCALAR_BYTES || bytes[SCALAR_BYTES - 1] != 0 {
+        return Err(OperationError::InvalidScalarEncoding);
+    }
+    let val = U448::from_le_slice(&bytes[..SCALAR_BYTES - 1]);
+    if val < ORDER {
+        Ok(Scalar::from(val))
+    } else {
+        Err(OperationError::InvalidScalarEncoding)
+    }
+}
+
+/// Encodes a scalar as 57 little-endian bytes.
+pub fn scalar_to_bytes_le(s: &Scalar) -> [u8; SCALAR_BYTES] {
+    let mut out = [0u8; SCALAR_BYTES];
+    out[..SCALAR_BYTES - 1].copy_from_slice(&s.val.to_le_bytes());
+    out
+}
+
+/// The additive inverse -s mod r of a reduced scalar.
+pub fn scalar_negate(s: &Scalar) -> Scalar {
+    Scalar::from(0_u64).sub_mod(s)
+}
+
+/// The inverse s⁻¹ mod r of a scalar, or `None` if s = 0.
+pub fn scalar_invert(s: &Scalar) -> Option<Scalar> {
+    let (inv, exists) = s.val.inv_odd_mod(&ORDER);
+    if bool::from(exists) && s.val != U448::ZERO {
+        Some(Scalar::from(inv))
+    } else {
+        None
+    }
+}
+
+// d = -39081 of the untwisted curve x² + y² = 1 + dx²y².
+fn edwards_d() -> FieldElement {
+    FieldElement::from_raw_slice([39081, 0, 0, 0, 0, 0, 0, 0]).negate()
+}
+
+pub(crate) fn is_equal(a: &FieldElement, b: &FieldElement) -> bool {
+    a.ct_eq(b).into()
+}
+
+fn is_negative(x: &FieldElement) -> u8 {
+    x.to_bytes()[0] & 1
+}
+
+// Compresses ψ(p), see [`dual_isogeny`].
+fn compress_dual(p: &ExtendedPoint) -> [u8; POINT_BYTES] {
+    let (x, y) = dual_isogeny(p);
+    let mut out = [0u8; POINT_BYTES];
+    out[..POINT_BYTES - 1].copy_from_slice(&y.to_bytes());
+    out[POINT_BYTES - 1] = is_negative(&x) << 7;
+    out
+}
+
+/// Applies ψ to a twisted point and returns the affine untwisted result:
+/// x' = 2xy / (y² + x²), y' = (y² - x²) / (2 - y² + x²)
+pub(crate) fn dual_isogeny(p: &ExtendedPoint) -> (FieldElement, FieldElement) {
+    let xx = p.X.square();
+    let yy = p.Y.square();
+    let zz = p.Z.square();
+
+    let x_num = p.X * p.Y + p.X * p.Y;
+    let x_den = yy + xx;
+    let y_num = yy - xx;
+    let y_den = zz + zz - y_num;
+
+    let inv = (x_den * y_den).invert();
+    (x_num * y_den * inv, y_num * x_den * inv)
+}
+
+/// Applies φ to an affine point of the untwisted curve:
+/// x' = 2xy / (y² - x²), y' = (y² + x²) / (2 - y² - x²)
+pub(crate) fn isogeny(x: &FieldElement, y: &FieldElement) -> ExtendedPoint {
+    let xx = x.square();
+    let yy = y.square();
+    let two = FieldElement::one() + FieldElement::one();
+
+    let x_num = *x * *y + *x * *y;
+    let x_den = yy - xx;
+    let y_num = yy + xx;
+    let y_den = two - y_num;
+
+    ExtendedPoint {
+        X: x_num * y_den,
+        Y: y_num * x_den,
+        Z: x_den * y_den,
+        T: x_num * y_num,
+    }
+}
+
+// RFC 8032 5.2.3. Recovers x from y and its sign bit.
+fn decompress(bytes: &[u8]) -> Result<(FieldElement, FieldElement), OperationError> {
+    if bytes.len() != POINT_BYTES || bytes[POINT_BYTES - 1] & 0x7F != 0 {
+        return Err(OperationError::InvalidPointEncoding);
+    }
+    let mut y_bytes = [0u8; POINT_BYTES - 1];
+    y_bytes.copy_from_slice(&bytes[..POINT_BYTES - 1]);
+    let y = FieldElement::from_bytes(&y_bytes);
+    if y.to_bytes() != y_bytes {
+        return Err(OperationError::InvalidPointEncoding);
+    }
+    let x_0 = bytes[POINT_BYTES - 1] >> 7;
+
+    // x² = u / v with u = y² - 1 and v = dy² - 1
+    let yy = y.square();
+    let u = yy - FieldElement::one();
+    let v = edwards_d() * yy - FieldElement::one();
+
+    // x = u³v(u⁵v³)^((p-3)/4)
+    let u3v = u.square() * u * v;
+    let u5v3 = u3v * u.square() * v.square();
+    let mut x = u3v * pow_p34(&u5v3);
+
+    if !is_equal(&(v * x.square()), &u) {
+        return Err(OperationError::InvalidPointEncoding);
+    }
+    if is_equal(&x, &FieldElement::zero()) && x_0 == 1 {
+        return Err(OperationError::InvalidPointEncoding);
+    }
+    if is_negative(&x) != x_0 {
+        x = x.negate();
+    }
+    Ok((x, y))
+}
+
+/// a^((p-3)/4) where (p-3)/4 = 2⁴⁴⁶ - 2²²² - 1 is 223 one bits,
+/// a zero bit and 222 one bits.
+pub(crate) fn pow_p34(a: &FieldElement) -> FieldElement {
+    let mut acc = *a;
+    for _ in 1..223 {
+        acc = acc.square() * *a;
+    }
+    acc = acc.square();
+    for _ in 0..222 {
+        acc = acc.square() * *a;
+    }
+    acc
+}
+
+/// Serializes an [`ExtendedPoint`] as the hex string of its 57-byte encoding.
+/// Use with `#[serde(with = "crate::ecc::edwards448::serde_point")]`.
+pub mod serde_point {
+    use super::{decode_point_checked, encode_point};
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use tiny_ed448_goldilocks::curve::extended_edwards::ExtendedPoint;
+
+    pub fn serialize<S: Serializer>(p: &ExtendedPoint, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(encode_point(p)))
+    }
+
+    /// Rejects encodings that [`decode_point_checked`] rejects.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ExtendedPoint, D::Error> {
+        let bytes = hex::decode(String::deserialize(deserializer)?).map_err(D::Error::custom)?;
+        decode_point_checked(&bytes).map_err(|e| D::Error::custom(format!("{:?}", e)))
+    }
+}
+
+/// [`serde_point`] for an optional point.
+pub mod serde_point_option {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use tiny_ed448_goldilocks::curve::extended_edwards::ExtendedPoint;
+
+    #[derive(Serialize, Deserialize)]
+    struct Wrapper(#[serde(with = "super::serde_point")] ExtendedPoint);
+
+    pub fn serialize<S: Serializer>(
+        p: &Option<ExtendedPoint>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        p.map(Wrapper).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<ExtendedPoint>, D::Error> {
+        Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|w| w.0))
+    }
+}
+
+/// [`serde_point`] for a list of points.
+pub mod serde_point_vec {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use tiny_ed448_goldilocks::curve::extended_edwards::ExtendedPoint;
+
+    #[derive(Serialize, Deserialize)]
+    struct Wrapper(#[serde(with = "super::serde_point")] ExtendedPoint);
+
+    pub fn serialize<S: Serializer>(
+        points: &[ExtendedPoint],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(points.iter().map(|p| Wrapper(*p)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<ExtendedPoint>, D::Error> {
+        Ok(Vec::<Wrapper>::deserialize(deserializer)?
+            .into_iter()
+            .map(|w| w.0)
+            .collect())
+    }
+}
+
+/// Serializes a [`Scalar`] as the hex string of its 57-byte encoding.
+/// Use with `#[serde(with = "crate::ecc::edwards448::serde_scalar")]`.
+pub mod serde_scalar {
+    use super::{scalar_from_canonical_bytes, scalar_to_bytes_le};
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use tiny_ed448_goldilocks::curve::field::scalar::Scalar;
+
+    pub fn serialize<S: Serializer>(s: &Scalar, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(scalar_to_bytes_le(s)))
+    }
+
+    /// Rejects encodings that [`scalar_from_canonical_bytes`] rejects.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Scalar, D::Error> {
+        let bytes = hex::decode(String::deserialize(deserializer)?).map_err(D::Error::custom)?;
+        scalar_from_canonical_bytes(&bytes).map_err(|e| D::Error::custom(format!("{:?}", e)))
+    }
+}
+
+/// [`serde_scalar`] for a list of scalars.
+pub mod serde_scalar_vec {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use tiny_ed448_goldilocks::curve::field::scalar::Scalar;
+
+    #[derive(Serialize, Deserialize)]
+    struct Wrapper(#[serde(with = "super::serde_scalar")] Scalar);
+
+    pub fn serialize<S: Serializer>(scalars: &[Scalar], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(scalars.iter().map(|s| Wrapper(*s)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Scalar>, D::Error> {
+        Ok(Vec::<Wrapper>::deserialize(deserializer)?
+            .into_iter()
+            .map(|w| w.0)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod edwards448_tests {
+    use super::{
+        decode_point, decode_point_checked, encode_base_multiple, encode_point, multiscalar_mul,
+        nums_generator, scalar_from_bytes_wide, scalar_invert,
+    };
+    use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+
+    /// RFC 8032 base point B
+    const BASE_POINT: &str = "14fa30f25b790898adc8d74e2c13bdfdc4397ce61cffd33ad7c2a0051e9c78874098a36c7373ea4b62c7c9563720768824bcb66e71463f6900";
+
+    #[test]
+    fn generator_encodes_to_base_point() {
+        let g = ExtendedPoint::generator();
+        assert_eq!(hex::encode(encode_point(&g)), BASE_POINT);
+        assert_eq!(
+            hex::encode(encode_base_multiple(&Scalar::from(1_u64))),
+            BASE_POINT
+        );
+    }
+
+    #[test]
+    fn point_encoding_roundtrip() {
+        let g = ExtendedPoint::generator();
+        for k in [1_u64, 2, 3, 4, 5, 0xdeadbeef] {
+            let p = g * Scalar::from(k);
+            let encoded = encode_point(&p);
+            assert_eq!(decode_point(&encoded).unwrap(), p);
+            assert_eq!(encode_base_multiple(&Scalar::from(k)), encoded);
+        }
+    }
+
+    #[test]
+    fn decode_rejects_invalid_encodings() {
+        let mut encoded = encode_point(&ExtendedPoint::generator());
+        assert!(decode_point(&encoded[..56]).is_err());
+        encoded[56] |= 1;
+        assert!(decode_point(&encoded).is_err());
+        // y = p is a non-canonical encoding of y = 0
+        let mut p = [0xff_u8; 57];
+        p[28] = 0xfe;
+        p[56] = 0;
+        assert!(decode_point(&p).is_err());
+    }
+
+    #[test]
+    fn checked_decoding_rejects_small_order_components() {
+        let p = ExtendedPoint::generator() * Scalar::from(0xdeadbeef_u64);
+        let encoded = encode_point(&p);
+        assert_eq!(decode_point_checked(&encoded).unwrap(), p);
+
+        // (0, 1) is the identity and (0, -1) has order 2
+        let mut identity = [0u8; 57];
+        identity[0] = 1;
+        let mut order_two = [0xff_u8; 57];
+        order_two[0] = 0xfe;
+        order_two[28] = 0xfe;
+        order_two[56] = 0;
+        for bytes in [identity, order_two] {
+            assert!(decode_point(&bytes).is_ok());
+            assert!(decode_point_checked(&bytes).is_err());
+        }
+    }
+
+    #[test]
+    fn multiscalar_mul_matches_scalar_mul() {
+        let g = ExtendedPoint::generator();
+        let points = [g, g * Scalar::from(3_u64), g * Scalar::from(0xdeadbeef_u64)];
+        let scalars = [
+            scalar_from_bytes_wide(&[0xff; 114]),
+            Scalar::from(0_u64),
+            scalar_from_bytes_wide(&[0x5a; 64]),
+        ];
+        let expected = points
+            .iter()
+            .zip(scalars)
+            .fold(ExtendedPoint::id_point(), |acc, (p, s)| acc + *p * s);
+        assert_eq!(multiscalar_mul(&scalars, &points), expected);
+        assert_eq!(multiscalar_mul(&[], &[]), ExtendedPoint::id_point());
+    }
+
+    #[test]
+    fn wide_reduction() {
+        let mut bytes = [0u8; 114];
+        bytes[0] = 7;
+        assert_eq!(scalar_from_bytes_wide(&bytes), Scalar::from(7_u64));
+    }
+
+    #[test]
+    fn nums_generators_are_distinct_subgroup_points() {
+        let h = nums_generator(b"label");
+        assert_eq!(h, nums_generator(b"label"));
+        assert_ne!(h, nums_generator(b"other label"));
+        assert_ne!(h, ExtendedPoint::generator());
+        assert_eq!(decode_point_checked(&encode_point(&h)).unwrap(), h);
+    }
+
+    #[test]
+    fn scalar_inversion() {
+        let s = scalar_from_bytes_wide(&[0xa5; 114]);
+        let inv = scalar_invert(&s).unwrap();
+        assert_eq!(s.mul_mod(&inv), Scalar::from(1_u64));
+        assert!(scalar_invert(&Scalar::from(0_u64)).is_none());
+    }
+}
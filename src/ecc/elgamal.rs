@@ -0,0 +1,244 @@
+//! Additively homomorphic exponential ElGamal encryption of small values.
+//!
+//! A value m is encrypted under the public key 𝑉 = x*𝑮 of a
+//! [`KeyPair`](crate::ecc::keypair::KeyPair) as (r*𝑮, m*𝑮 + r*𝑉). Adding two
+//! ciphertexts yields an encryption of the sum of their values, so votes or
+//! counters can be tallied without decrypting them individually. Decryption
+//! recovers m*𝑮 and then m with a baby-step giant-step search, which takes
+//! time and memory proportional to √m and therefore only suits small values,
+//! at most [`MAX_DISCRETE_LOG`](crate::ecc::elgamal::MAX_DISCRETE_LOG).
+//!
+//! The decryption key can be split among trustees with
+//! [`split_feldman`](crate::ecc::shamir::split_feldman). Each trustee then
+//! publishes a [`DecryptionShare`](crate::ecc::elgamal::DecryptionShare) with a
+//! Chaum–Pedersen proof that it was computed with the trustee's key share, and
+//! any threshold of valid shares decrypts the ciphertext.
+use super::{
+    edwards448::random_scalar,
+    keypair::KeyPair,
+    nizk::DleqProof,
+    shamir::{check_indices, lagrange_coefficients, ScalarShare},
+};
+use crate::OperationError;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    ops::{Add, Sub},
+};
+use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+
+/// Context of the proofs attached to decryption shares.
+const DECRYPTION_SHARE_CONTEXT: &[u8] = b"capycrypt elgamal decryption share";
+
+/// The largest `max_value` accepted by [`discrete_log`], 2³² - 1. The search
+/// then stores at most 2¹⁶ baby steps.
+pub const MAX_DISCRETE_LOG: u64 = u32::MAX as u64;
+
+/// An exponential ElGamal ciphertext (r*𝑮, m*𝑮 + r*𝑉).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Ciphertext {
+    /// Ephemeral point r*𝑮
+    #[serde(with = "super::edwards448::serde_point")]
+    pub c1: ExtendedPoint,
+    /// Masked value m*𝑮 + r*𝑉
+    #[serde(with = "super::edwards448::serde_point")]
+    pub c2: ExtendedPoint,
+}
+
+/// A trustee's share xᵢ*c₁ of the decryption of a ciphertext.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DecryptionShare {
+    /// Index i of the trustee's key share
+    pub index: u16,
+    /// Partial decryption xᵢ*c₁
+    #[serde(with = "super::edwards448::serde_point")]
+    pub point: ExtendedPoint,
+    /// Proof that log_𝑮(xᵢ*𝑮) = log_c₁(xᵢ*c₁)
+    pub proof: DleqProof,
+}
+
+impl Ciphertext {
+    /// # Encryption
+    /// Encrypts `value` under the public key 𝑉.
+    /// ## Algorithm:
+    /// * r ← Random(912) mod r
+    /// * (c₁, c₂) ← (r*𝑮, m*𝑮 + r*𝑉)
+    pub fn encrypt(pub_key: &ExtendedPoint, value: u64) -> Ciphertext {
+        let r = random_scalar();
+        Ciphertext {
+            c1: ExtendedPoint::generator() * r,
+            c2: ExtendedPoint::generator() * Scalar::from(value) + *pub_key * r,
+        }
+    }
+
+    /// # Decryption
+    /// Decrypts a value of at most `max_value`.
+    /// ## Algorithm:
+    /// * 𝑀 ← c₂ - x*c₁
+    /// * m ← discrete_log(𝑀, max_value)
+    /// ## Errors:
+    /// * [`OperationError::DiscreteLogNotFound`] if the value exceeds `max_value`
+    ///   or the ciphertext was not encrypted under the key pair.
+    /// * [`OperationError::DiscreteLogRangeTooLarge`] if `max_value` exceeds
+    ///   [`MAX_DISCRETE_LOG`].
+    pub fn decrypt(&self, key_pair: &KeyPair, max_value: u64) -> Result<u64, OperationError> {
+        let mask = self.c1 * *key_pair.priv_key.expose();
+        discrete_log(&(self.c2 + -mask), max_value)
+    }
+
+    /// # Re-randomization
+    /// Returns a fresh encryption of the same value that cannot be linked to
+    /// this ciphertext without the secret key.
+    /// ## Algorithm:
+    /// * r' ← Random(912) mod r
+    /// * (c₁ + r'*𝑮, c₂ + r'*𝑉)
+    pub fn rerandomize(&self, pub_key: &ExtendedPoint) -> Ciphertext {
+        *self + Ciphertext::encrypt(pub_key, 0)
+    }
+
+    /// # Decryption Share
+    /// Computes the partial decryption of the trustee holding `share` of the
+    /// secret key, with a proof of correctness.
+    /// ## Algorithm:
+    /// * 𝐷ᵢ ← xᵢ*c₁
+    /// * π ← DLEQ proof of log_𝑮(xᵢ*𝑮) = log_c₁(𝐷ᵢ)
+    pub fn decryption_share(&self, share: &ScalarShare) -> DecryptionShare {
+        let x = share.value.expose();
+        DecryptionShare {
+            index: share.index,
+            point: self.c1 * *x,
+            proof: DleqProof::prove(
+                x,
+                &ExtendedPoint::generator(),
+                &self.c1,
+                DECRYPTION_SHARE_CONTEXT,
+            ),
+        }
+    }
+
+    /// # Threshold Decryption
+    /// Decrypts a value of at most `max_value` from the decryption shares of
+    /// at least threshold trustees. Verify every share with
+    /// [`DecryptionShare::verify`] first.
+    /// ## Algorithm:
+    /// * x*c₁ ← Σ λᵢ*𝐷ᵢ, with the Lagrange coefficients λᵢ of the share indices
+    /// * m ← discrete_log(c₂ - x*c₁, max_value)
+    /// ## Errors:
+    /// * [`OperationError::InvalidParticipantIdentifier`] if share indices repeat or are 0.
+    /// * [`OperationError::DiscreteLogNotFound`] if the value exceeds
+    ///   `max_value` or there are fewer shares than the threshold.
+    /// * [`OperationError::DiscreteLogRangeTooLarge`] if `max_value` exceeds
+    ///   [`MAX_DISCRETE_LOG`].
+    pub fn combine_shares(
+        &self,
+        shares: &[DecryptionShare],
+        max_value: u64,
+    ) -> Result<u64, OperationError> {
+        check_indices(shares.iter().map(|s| s.index))?;
+        let indices: Vec<u16> = shares.iter().map(|s| s.index).collect();
+        let mask = shares
+            .iter()
+            .zip(lagrange_coefficients(&indices))
+            .fold(ExtendedPoint::id_point(), |acc, (share, lambda)| {
+                acc + share.point * lambda
+            });
+        discrete_log(&(self.c2 + -mask), max_value)
+    }
+}
+
+impl Add for Ciphertext {
+    type Output = Ciphertext;
+
+    /// Encrypts m₁ + m₂.
+    fn add(self, other: Ciphertext) -> Ciphertext {
+        Ciphertext {
+            c1: self.c1 + other.c1,
+            c2: self.c2 + other.c2,
+        }
+    }
+}
+
+impl Sub for Ciphertext {
+    type Output = Ciphertext;
+
+    /// Encrypts m₁ - m₂, which only decrypts if m₁ ≥ m₂.
+    fn sub(self, other: Ciphertext) -> Ciphertext {
+        Ciphertext {
+            c1: self.c1 + -other.c1,
+            c2: self.c2 + -other.c2,
+        }
+    }
+}
+
+impl DecryptionShare {
+    /// Checks the proof of a decryption share of `ciphertext` against the
+    /// trustee's public share xᵢ*𝑮, e.g. from
+    /// [`FeldmanCommitment::public_share`](crate::ecc::shamir::FeldmanCommitment::public_share).
+    /// ## Errors:
+    /// * [`OperationError::InvalidDecryptionShare`] if the proof does not verify.
+    pub fn verify(
+        &self,
+        ciphertext: &Ciphertext,
+        public_share: &ExtendedPoint,
+    ) -> Result<(), OperationError> {
+        self.proof
+            .verify(
+                &ExtendedPoint::generator(),
+                public_share,
+                &ciphertext.c1,
+                &self.point,
+                DECRYPTION_SHARE_CONTEXT,
+            )
+            .map_err(|_| OperationError::InvalidDecryptionShare(self.index))
+    }
+}
+
+/// # Small Discrete Logarithm
+/// Finds m ≤ `max_value` with `point` = m*𝑮 by baby-step giant-step search.
+/// ## Algorithm:
+/// * n ← ⌈√(max_value + 1)⌉
+/// * baby steps: store j*𝑮 for 0 ≤ j < n
+/// * giant steps: for 0 ≤ i < n: if 𝑀 - i*n*𝑮 = j*𝑮 is stored, m = i*n + j
+/// ## Errors:
+/// * [`OperationError::DiscreteLogNotFound`] if no such m exists.
+/// * [`OperationError::DiscreteLogRangeTooLarge`] if `max_value` exceeds
+///   [`MAX_DISCRETE_LOG`].
+pub fn discrete_log(point: &ExtendedPoint, max_value: u64) -> Result<u64, OperationError> {
+    if max_value > MAX_DISCRETE_LOG {
+        return Err(OperationError::DiscreteLogRangeTooLarge);
+    }
+    let mut n = (max_value as f64).sqrt() as u64;
+    while (n as u128) * (n as u128) <= max_value as u128 {
+        n += 1;
+    }
+    let g = ExtendedPoint::generator();
+
+    let mut baby_steps = HashMap::with_capacity(n as usize);
+    let mut current = ExtendedPoint::id_point();
+    for j in 0..n {
+        baby_steps.insert(affine_key(&current), j);
+        current = current + g;
+    }
+
+    let giant_step = -(g * Scalar::from(n));
+    let mut current = *point;
+    for i in 0..n {
+        if let Some(j) = baby_steps.get(&affine_key(&current)) {
+            let m = i * n + j;
+            if m <= max_value {
+                return Ok(m);
+            }
+        }
+        current = current + giant_step;
+    }
+    Err(OperationError::DiscreteLogNotFound)
+}
+
+/// Affine coordinates x || y, identical for all projective representations.
+fn affine_key(p: &ExtendedPoint) -> [u8; 112] {
+    let affine = p.to_affine();
+    let mut key = [0u8; 112];
+    key[..56].copy_from_slice(&affine.x.to_bytes());
+    key[56..].copy_from_slice(&affine.y.to_bytes());
+    key
+}
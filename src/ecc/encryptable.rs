@@ -1,3 +1,4 @@
+use super::keypair::KeyPair;
 use crate::{
     sha3::{
         aux_functions::byte_utils::{bytes_to_scalar, get_random_bytes, xor_bytes},
@@ -9,7 +10,7 @@ use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scala
 
 pub trait KeyEncryptable {
     fn key_encrypt(&mut self, pub_key: &ExtendedPoint, d: SecParam);
-    fn key_decrypt(&mut self, pw: &[u8]) -> Result<(), OperationError>;
+    fn key_decrypt(&mut self, key: &KeyPair) -> Result<(), OperationError>;
 }
 
 impl KeyEncryptable for Message {
@@ -59,23 +60,20 @@ impl KeyEncryptable for Message {
     /// * `Message.op_result` with result of comparision of `Message.t` == keyed hash of decryption.
     ///
     /// ## Algorithm:
-    /// * s ← KMACXOF256(pw, “”, 448, “K”); s ← 4s
+    /// * s ← secret scalar of the [`KeyPair`]
     /// * W ← sZ
     /// * (ke || ka) ← KMACXOF256(W x , “”, 448 * 2, “P”)
     /// * m ← KMACXOF256(ke, “”, |c|, “PKE”) ⊕ c
     /// * t’ ← KMACXOF256(ka, m, 448, “PKA”)
     ///
     /// ## Arguments:
-    /// * pw: &[u8]: password used to generate ```CurvePoint``` encryption key.
-    /// * d: u64: encryption security strength in bits. Can only be 224, 256, 384, or 512.
+    /// * key: &[`KeyPair`]: key pair whose public key the message was encrypted under.
     #[allow(non_snake_case)]
-    fn key_decrypt(&mut self, pw: &[u8]) -> Result<(), OperationError> {
+    fn key_decrypt(&mut self, key: &KeyPair) -> Result<(), OperationError> {
         let Z = self.asym_nonce.ok_or(OperationError::SymNonceNotSet)?;
         let d = self.d.ok_or(OperationError::SecurityParameterNotSet)?;
 
-        let s_bytes = kmac_xof(pw, &[], 448, "SK", d);
-        let s = bytes_to_scalar(&s_bytes).mul_mod(&Scalar::from(4_u64));
-        let Z = (Z * s).to_affine();
+        let Z = (Z * *key.priv_key.expose()).to_affine();
 
         let ke_ka = kmac_xof(&Z.x.to_bytes(), &[], 448 * 2, "PK", d);
         let (ke, ka) = ke_ka.split_at(ke_ka.len() / 2);
@@ -0,0 +1,962 @@
+//! FROST threshold signatures as specified in RFC 9591, with the
+//! FROST(Ed448, SHAKE256) ciphersuite.
+//!
+//! A group secret key is split into n shares so that any t of them can sign
+//! together, while fewer than t learn nothing about it. No participant ever
+//! reconstructs the group secret key, and the aggregated signature is a
+//! standard RFC 8032 Ed448 signature under the group public key, verifiable
+//! with [`VerifyingKey::verify`](crate::ecc::eddsa::VerifyingKey::verify) and
+//! an empty context.
+//!
+//! Shares come either from a trusted dealer,
+//! [`trusted_dealer_keygen`](crate::ecc::frost::trusted_dealer_keygen), or from
+//! the distributed key generation in [`dkg`](crate::ecc::frost::dkg). Signing
+//! then takes two rounds:
+//! 1. Every signer calls [`KeyPackage::commit`](crate::ecc::frost::KeyPackage::commit)
+//!    and sends its [`SigningCommitments`](crate::ecc::frost::SigningCommitments)
+//!    to a coordinator, which collects them with the message in a
+//!    [`SigningPackage`](crate::ecc::frost::SigningPackage).
+//! 2. Every signer calls [`KeyPackage::sign`](crate::ecc::frost::KeyPackage::sign)
+//!    on the signing package, and the coordinator combines the signature shares
+//!    with [`PublicKeyPackage::aggregate`](crate::ecc::frost::PublicKeyPackage::aggregate).
+use super::{
+    eddsa::{Ed448Signature, SigningKey, VerifyingKey},
+    edwards448::{encode_point, random_scalar, scalar_from_bytes_wide, scalar_to_bytes_le},
+    keypair::SecretScalar,
+    shamir::{
+        check_threshold, evaluate, lagrange_coefficients, random_polynomial, zeroize_polynomial,
+        FeldmanCommitment,
+    },
+};
+use crate::{
+    sha3::{aux_functions::byte_utils::get_random_bytes, shake_functions::shake256},
+    OperationError,
+};
+use serde::{Deserialize, Serialize};
+use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+use zeroize::Zeroize;
+
+const CONTEXT_STRING: &[u8] = b"FROST-ED448-SHAKE256-v1";
+
+/// A participant identifier. Identifiers are nonzero and unique within a group,
+/// usually 1 to n.
+pub type Identifier = u16;
+
+/// The share f(i) of the group secret key handed to participant i by a trusted dealer.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SecretShare {
+    /// Identifier i of the receiving participant
+    pub identifier: Identifier,
+    /// Secret share f(i)
+    pub value: SecretScalar,
+    /// Commitment to f, to check the share against
+    pub commitment: FeldmanCommitment,
+}
+
+/// Everything a participant needs to sign.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct KeyPackage {
+    /// Identifier of the participant
+    pub identifier: Identifier,
+    /// Secret share sᵢ of the group secret key
+    pub signing_share: SecretScalar,
+    /// Group public key
+    #[serde(with = "super::edwards448::serde_point")]
+    pub group_public_key: ExtendedPoint,
+    /// Number t of participants needed to sign
+    pub min_signers: u16,
+}
+
+/// The public information of a group: the group public key and, through the
+/// commitment, the public share sᵢ*𝑮 of every participant.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PublicKeyPackage {
+    /// Commitment to the polynomial the group secret key was shared with
+    pub commitment: FeldmanCommitment,
+}
+
+/// The secret nonces of one signer for one signing session.
+pub struct SigningNonces {
+    hiding: Scalar,
+    binding: Scalar,
+    commitments: SigningCommitments,
+}
+
+/// The public commitments (d*𝑮, e*𝑮) to the nonces of one signer.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct SigningCommitments {
+    /// Identifier of the signer
+    pub identifier: Identifier,
+    /// Commitment to the hiding nonce
+    #[serde(with = "super::edwards448::serde_point")]
+    pub hiding: ExtendedPoint,
+    /// Commitment to the binding nonce
+    #[serde(with = "super::edwards448::serde_point")]
+    pub binding: ExtendedPoint,
+}
+
+/// The message and the commitments of all signers of one signing session.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SigningPackage {
+    commitments: Vec<SigningCommitments>,
+    message: Vec<u8>,
+}
+
+/// The contribution zᵢ of one signer to the aggregated signature.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct SignatureShare {
+    /// Identifier of the signer
+    pub identifier: Identifier,
+    /// Signature share zᵢ
+    #[serde(with = "super::edwards448::serde_scalar")]
+    pub z: Scalar,
+}
+
+/// # Trusted Dealer Key Generation
+/// Generates a random group secret key and splits it into `max_signers` shares,
+/// any `min_signers` of which can sign. Participants are numbered 1 to
+/// `max_signers`. The dealer learns the group secret key and must be trusted
+/// to forget it.
+/// ## Errors:
+/// * [`OperationError::InvalidThresholdParameters`] unless
+///   2 ≤ `min_signers` ≤ `max_signers`.
+pub fn trusted_dealer_keygen(
+    max_signers: u16,
+    min_signers: u16,
+) -> Result<(Vec<SecretShare>, PublicKeyPackage), OperationError> {
+    split_scalar(&random_scalar(), max_signers, min_signers)
+}
+
+/// # Key Splitting
+/// Splits the secret scalar of an existing Ed448 key as in
+/// [`trusted_dealer_keygen`], so that threshold signatures verify under its
+/// [`VerifyingKey`].
+/// ## Errors:
+/// * [`OperationError::InvalidThresholdParameters`] unless
+///   2 ≤ `min_signers` ≤ `max_signers`.
+pub fn split_key(
+    key: &SigningKey,
+    max_signers: u16,
+    min_signers: u16,
+) -> Result<(Vec<SecretShare>, PublicKeyPackage), OperationError> {
+    split_scalar(key.scalar(), max_signers, min_signers)
+}
+
+impl SecretShare {
+    /// # Share Verification
+    /// Checks the share against the dealer's commitment, so a participant can
+    /// detect a dealer who handed out inconsistent shares.
+    /// ## Errors:
+    /// * [`OperationError::InvalidSecretShare`] if f(i)*𝑮 does not match the commitment.
+    pub fn verify(&self) -> Result<(), OperationError> {
+        if self.commitment.holds(self.identifier, self.value.expose()) {
+            Ok(())
+        } else {
+            Err(OperationError::InvalidSecretShare(self.identifier))
+        }
+    }
+}
+
+impl KeyPackage {
+    /// # Share Import
+    /// Verifies a share from a trusted dealer and turns it into a [`KeyPackage`].
+    /// ## Errors:
+    /// * [`OperationError::InvalidSecretShare`] if [`SecretShare::verify`] fails.
+    pub fn from_secret_share(share: &SecretShare) -> Result<KeyPackage, OperationError> {
+        share.verify()?;
+        Ok(KeyPackage {
+            identifier: share.identifier,
+            signing_share: share.value.clone(),
+            group_public_key: share.commitment.public_key(),
+            min_signers: share.commitment.0.len() as u16,
+        })
+    }
+
+    /// The public share sᵢ*𝑮 of this participant.
+    pub fn public_share(&self) -> ExtendedPoint {
+        ExtendedPoint::generator() * *self.signing_share.expose()
+    }
+
+    /// # Round One: Commitment
+    /// Draws fresh hiding and binding nonces for one signing session.
+    /// ## Algorithm:
+    /// * d ← H3(Random(256) || sᵢ), e ← H3(Random(256) || sᵢ)
+    /// * commitments: (d*𝑮, e*𝑮)
+    pub fn commit(&self) -> (SigningNonces, SigningCommitments) {
+        let mut hiding_randomness = get_random_bytes(32);
+        let mut binding_randomness = get_random_bytes(32);
+        let nonces = SigningNonces::new(
+            self.identifier,
+            nonce_generate(&hiding_randomness, self.signing_share.expose()),
+            nonce_generate(&binding_randomness, self.signing_share.expose()),
+        );
+        hiding_randomness.zeroize();
+        binding_randomness.zeroize();
+        let commitments = nonces.commitments;
+        (nonces, commitments)
+    }
+
+    /// # Round Two: Signature Share
+    /// Signs the message of `package`, consuming the nonces drawn for it.
+    /// ## Algorithm:
+    /// * ρᵢ ← H1(𝑌 || H4(m) || H5(commitments) || i) for every signer i
+    /// * 𝑅 ← Σ (𝐷ᵢ + ρᵢ*𝐸ᵢ)
+    /// * c ← H2(𝑅 || 𝑌 || m), λᵢ ← Lagrange coefficient of i among the signers
+    /// * zᵢ ← d + e·ρᵢ + λᵢ·sᵢ·c
+    /// ## Errors:
+    /// * [`OperationError::InsufficientSigners`] if `package` has fewer than
+    ///   `min_signers` commitments.
+    /// * [`OperationError::UnknownParticipant`] if `package` has no commitment
+    ///   from this participant.
+    /// * [`OperationError::NonceKeyMismatch`] if the commitment in `package`
+    ///   does not belong to `nonces`.
+    pub fn sign(
+        &self,
+        package: &SigningPackage,
+        nonces: SigningNonces,
+    ) -> Result<SignatureShare, OperationError> {
+        if package.commitments.len() < self.min_signers as usize {
+            return Err(OperationError::InsufficientSigners);
+        }
+        let index = package.index_of(self.identifier)?;
+        if package.commitments[index] != nonces.commitments {
+            return Err(OperationError::NonceKeyMismatch);
+        }
+
+        let binding_factors = package.binding_factors(&self.group_public_key);
+        let group_commitment = package.group_commitment(&binding_factors);
+        let c = challenge(&group_commitment, &self.group_public_key, &package.message);
+        let lambda = lagrange_coefficients(&package.identifiers())[index];
+
+        let z = nonces
+            .hiding
+            .add_mod(&nonces.binding.mul_mod(&binding_factors[index]))
+            .add_mod(&lambda.mul_mod(self.signing_share.expose()).mul_mod(&c));
+        Ok(SignatureShare {
+            identifier: self.identifier,
+            z,
+        })
+    }
+}
+
+impl PublicKeyPackage {
+    /// The group public key.
+    pub fn group_public_key(&self) -> ExtendedPoint {
+        self.commitment.public_key()
+    }
+
+    /// The group public key as an Ed448 [`VerifyingKey`].
+    pub fn verifying_key(&self) -> VerifyingKey {
+        VerifyingKey::from_bytes(&encode_point(&self.group_public_key())).unwrap()
+    }
+
+    /// The number t of participants needed to sign.
+    pub fn min_signers(&self) -> u16 {
+        self.commitment.0.len() as u16
+    }
+
+    /// # Signature Share Verification
+    /// Checks the signature share of one signer, to find a signer who spoiled
+    /// the aggregated signature.
+    /// ## Algorithm:
+    /// * accept iff zᵢ*𝑮 = 𝐷ᵢ + ρᵢ*𝐸ᵢ + (c·λᵢ)*𝑌ᵢ
+    /// ## Errors:
+    /// * [`OperationError::UnknownParticipant`] if `package` has no commitment
+    ///   from the signer.
+    /// * [`OperationError::InvalidSignatureShare`] if the check fails.
+    pub fn verify_signature_share(
+        &self,
+        package: &SigningPackage,
+        share: &SignatureShare,
+    ) -> Result<(), OperationError> {
+        let group_public_key = self.group_public_key();
+        let binding_factors = package.binding_factors(&group_public_key);
+        let group_commitment = package.group_commitment(&binding_factors);
+        self.check_share(package, share, &binding_factors, &group_commitment)
+    }
+
+    /// # Signature Aggregation
+    /// Combines one signature share from every signer of `package` into an
+    /// Ed448 signature (𝑅, z) with z ← Σ zᵢ, and verifies it.
+    /// ## Errors:
+    /// * [`OperationError::UnknownParticipant`] unless the shares come from
+    ///   exactly the signers of `package`.
+    /// * [`OperationError::InvalidSignatureShare`] with the first signer whose
+    ///   share is invalid, if the aggregated signature does not verify.
+    pub fn aggregate(
+        &self,
+        package: &SigningPackage,
+        shares: &[SignatureShare],
+    ) -> Result<Ed448Signature, OperationError> {
+        let mut ids: Vec<Identifier> = shares.iter().map(|s| s.identifier).collect();
+        ids.sort_unstable();
+        if ids != package.identifiers() {
+            return Err(OperationError::UnknownParticipant);
+        }
+
+        let group_public_key = self.group_public_key();
+        let binding_factors = package.binding_factors(&group_public_key);
+        let group_commitment = package.group_commitment(&binding_factors);
+        let z = shares
+            .iter()
+            .fold(Scalar::from(0_u64), |acc, share| acc.add_mod(&share.z));
+
+        let mut bytes = encode_point(&group_commitment).to_vec();
+        bytes.extend_from_slice(&scalar_to_bytes_le(&z));
+        let signature = Ed448Signature::from_bytes(&bytes)?;
+
+        if self
+            .verifying_key()
+            .verify(&package.message, &signature, &[])
+            .is_ok()
+        {
+            return Ok(signature);
+        }
+        for share in shares {
+            self.check_share(package, share, &binding_factors, &group_commitment)?;
+        }
+        Err(OperationError::SignatureVerificationFailure)
+    }
+
+    fn check_share(
+        &self,
+        package: &SigningPackage,
+        share: &SignatureShare,
+        binding_factors: &[Scalar],
+        group_commitment: &ExtendedPoint,
+    ) -> Result<(), OperationError> {
+        let index = package.index_of(share.identifier)?;
+        let commitments = &package.commitments[index];
+        let c = challenge(group_commitment, &self.group_public_key(), &package.message);
+        let lambda = lagrange_coefficients(&package.identifiers())[index];
+
+        let expected = commitments.hiding
+            + commitments.binding * binding_factors[index]
+            + self.commitment.public_share(share.identifier) * c.mul_mod(&lambda);
+        if ExtendedPoint::generator() * share.z == expected {
+            Ok(())
+        } else {
+            Err(OperationError::InvalidSignatureShare(share.identifier))
+        }
+    }
+}
+
+impl SigningNonces {
+    fn new(identifier: Identifier, hiding: Scalar, binding: Scalar) -> SigningNonces {
+        SigningNonces {
+            hiding,
+            binding,
+            commitments: SigningCommitments {
+                identifier,
+                hiding: ExtendedPoint::generator() * hiding,
+                binding: ExtendedPoint::generator() * binding,
+            },
+        }
+    }
+}
+
+impl Drop for SigningNonces {
+    fn drop(&mut self) {
+        self.hiding.val.zeroize();
+        self.binding.val.zeroize();
+    }
+}
+
+impl std::fmt::Debug for SigningNonces {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SigningNonces(..)")
+    }
+}
+
+impl SigningPackage {
+    /// Collects the commitments of all signers of `message`.
+    /// ## Errors:
+    /// * [`OperationError::InvalidParticipantIdentifier`] if an identifier is
+    ///   zero or appears twice.
+    pub fn new(
+        mut commitments: Vec<SigningCommitments>,
+        message: &[u8],
+    ) -> Result<SigningPackage, OperationError> {
+        commitments.sort_by_key(|c| c.identifier);
+        if commitments.first().is_some_and(|c| c.identifier == 0)
+            || commitments
+                .windows(2)
+                .any(|w| w[0].identifier == w[1].identifier)
+        {
+            return Err(OperationError::InvalidParticipantIdentifier);
+        }
+        Ok(SigningPackage {
+            commitments,
+            message: message.to_vec(),
+        })
+    }
+
+    /// The message to sign.
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+
+    /// The commitments of all signers, ordered by identifier.
+    pub fn commitments(&self) -> &[SigningCommitments] {
+        &self.commitments
+    }
+
+    fn identifiers(&self) -> Vec<Identifier> {
+        self.commitments.iter().map(|c| c.identifier).collect()
+    }
+
+    fn index_of(&self, identifier: Identifier) -> Result<usize, OperationError> {
+        self.commitments
+            .binary_search_by_key(&identifier, |c| c.identifier)
+            .map_err(|_| OperationError::UnknownParticipant)
+    }
+
+    // ρᵢ ← H1(𝑌 || H4(m) || H5(encoded commitments) || i), in commitment order.
+    fn binding_factors(&self, group_public_key: &ExtendedPoint) -> Vec<Scalar> {
+        let mut encoded_commitments = Vec::new();
+        for c in &self.commitments {
+            encoded_commitments.extend_from_slice(&encode_identifier(c.identifier));
+            encoded_commitments.extend_from_slice(&encode_point(&c.hiding));
+            encoded_commitments.extend_from_slice(&encode_point(&c.binding));
+        }
+        let mut prefix = encode_point(group_public_key).to_vec();
+        prefix.extend_from_slice(&h(b"msg", &self.message));
+        prefix.extend_from_slice(&h(b"com", &encoded_commitments));
+
+        self.commitments
+            .iter()
+            .map(|c| {
+                let mut input = prefix.clone();
+                input.extend_from_slice(&encode_identifier(c.identifier));
+                scalar_from_bytes_wide(&h(b"rho", &input))
+            })
+            .collect()
+    }
+
+    fn group_commitment(&self, binding_factors: &[Scalar]) -> ExtendedPoint {
+        self.commitments
+            .iter()
+            .zip(binding_factors)
+            .fold(ExtendedPoint::id_point(), |acc, (c, rho)| {
+                acc + c.hiding + c.binding * *rho
+            })
+    }
+}
+
+fn split_scalar(
+    secret: &Scalar,
+    max_signers: u16,
+    min_signers: u16,
+) -> Result<(Vec<SecretShare>, PublicKeyPackage), OperationError> {
+    check_signers(max_signers, min_signers)?;
+    let mut coefficients = random_polynomial(secret, min_signers);
+    let shares = share_polynomial(&coefficients, max_signers);
+    zeroize_polynomial(&mut coefficients);
+    Ok(shares)
+}
+
+// Shares f(x) = Σ aⱼxʲ among participants 1 to max_signers.
+fn share_polynomial(
+    coefficients: &[Scalar],
+    max_signers: u16,
+) -> (Vec<SecretShare>, PublicKeyPackage) {
+    let commitment = FeldmanCommitment::from_polynomial(coefficients);
+    let shares = (1..=max_signers)
+        .map(|i| SecretShare {
+            identifier: i,
+            value: SecretScalar::new(evaluate(coefficients, i)),
+            commitment: commitment.clone(),
+        })
+        .collect();
+    (shares, PublicKeyPackage { commitment })
+}
+
+// A single signer is no threshold, so FROST needs 2 ≤ t ≤ n.
+fn check_signers(max_signers: u16, min_signers: u16) -> Result<(), OperationError> {
+    if min_signers < 2 {
+        return Err(OperationError::InvalidThresholdParameters);
+    }
+    check_threshold(min_signers, max_signers as usize)
+}
+
+// H3(randomness || SerializeScalar(secret))
+fn nonce_generate(randomness: &[u8], secret: &Scalar) -> Scalar {
+    let mut input = randomness.to_vec();
+    input.extend_from_slice(&scalar_to_bytes_le(secret));
+    let mut digest = h(b"nonce", &input);
+    input.zeroize();
+    let nonce = scalar_from_bytes_wide(&digest);
+    digest.zeroize();
+    nonce
+}
+
+// H2(𝑅 || 𝑌 || m), the RFC 8032 Ed448 challenge with an empty context.
+fn challenge(
+    group_commitment: &ExtendedPoint,
+    group_public_key: &ExtendedPoint,
+    msg: &[u8],
+) -> Scalar {
+    let mut input = b"SigEd448\0\0".to_vec();
+    input.extend_from_slice(&encode_point(group_commitment));
+    input.extend_from_slice(&encode_point(group_public_key));
+    input.extend_from_slice(msg);
+    scalar_from_bytes_wide(&shake256(&input, 912))
+}
+
+// SHAKE256(contextString || tag || m, 114)
+fn h(tag: &[u8], m: &[u8]) -> Vec<u8> {
+    let mut input = CONTEXT_STRING.to_vec();
+    input.extend_from_slice(tag);
+    input.extend_from_slice(m);
+    shake256(&input, 912)
+}
+
+fn encode_identifier(identifier: Identifier) -> [u8; 57] {
+    scalar_to_bytes_le(&Scalar::from(identifier as u64))
+}
+
+/// Distributed key generation from the FROST paper (Komlo and Goldberg, 2020),
+/// in which no party ever learns the group secret key.
+///
+/// Every participant runs [`part1`](dkg::part1) and broadcasts its
+/// [`Round1Package`](dkg::Round1Package). With the round-one packages of all
+/// others, it runs [`part2`](dkg::part2) and sends each other participant its
+/// [`Round2Package`](dkg::Round2Package) over a confidential, authenticated
+/// channel. With the round-two packages addressed to it, it runs
+/// [`part3`](dkg::part3) to obtain its [`KeyPackage`] and the group's
+/// [`PublicKeyPackage`].
+pub mod dkg {
+    use super::{check_signers, encode_identifier, h, Identifier, KeyPackage, PublicKeyPackage};
+    use crate::{
+        ecc::{
+            edwards448::{encode_point, random_scalar, scalar_from_bytes_wide},
+            keypair::SecretScalar,
+            shamir::{evaluate, random_polynomial, FeldmanCommitment},
+        },
+        OperationError,
+    };
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+    use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+    use zeroize::Zeroize;
+
+    /// The secret state a participant keeps between [`part1`] and [`part2`].
+    pub struct Round1SecretPackage {
+        identifier: Identifier,
+        coefficients: Vec<Scalar>,
+        commitment: FeldmanCommitment,
+        max_signers: u16,
+    }
+
+    /// The message a participant broadcasts in round one.
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    pub struct Round1Package {
+        /// Commitment to the participant's polynomial
+        pub commitment: FeldmanCommitment,
+        /// Commitment 𝑅 of the proof of knowledge of a₀
+        #[serde(with = "crate::ecc::edwards448::serde_point")]
+        pub proof_r: ExtendedPoint,
+        /// Response μ of the proof of knowledge of a₀
+        #[serde(with = "crate::ecc::edwards448::serde_scalar")]
+        pub proof_mu: Scalar,
+    }
+
+    /// The secret state a participant keeps between [`part2`] and [`part3`].
+    pub struct Round2SecretPackage {
+        identifier: Identifier,
+        commitment: FeldmanCommitment,
+        own_share: Scalar,
+        max_signers: u16,
+    }
+
+    /// The share fᵢ(j) participant i sends to participant j in round two.
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    pub struct Round2Package {
+        /// Secret share fᵢ(j)
+        pub signing_share: SecretScalar,
+    }
+
+    /// # DKG Round One
+    /// Draws a random polynomial fᵢ of degree `min_signers` - 1, commits to it
+    /// and proves knowledge of its constant term.
+    /// ## Algorithm:
+    /// * a₀, …, aₜ₋₁ ← random scalars; commitment: (a₀*𝑮, …, aₜ₋₁*𝑮)
+    /// * k ← random scalar, 𝑅 ← k*𝑮
+    /// * c ← HDKG(i || a₀*𝑮 || 𝑅), μ ← k + a₀·c
+    /// ## Errors:
+    /// * [`OperationError::InvalidThresholdParameters`] unless
+    ///   2 ≤ `min_signers` ≤ `max_signers`.
+    /// * [`OperationError::InvalidParticipantIdentifier`] if `identifier` is zero.
+    pub fn part1(
+        identifier: Identifier,
+        max_signers: u16,
+        min_signers: u16,
+    ) -> Result<(Round1SecretPackage, Round1Package), OperationError> {
+        check_signers(max_signers, min_signers)?;
+        if identifier == 0 {
+            return Err(OperationError::InvalidParticipantIdentifier);
+        }
+        let coefficients = random_polynomial(&random_scalar(), min_signers);
+        Ok(round1(
+            identifier,
+            max_signers,
+            coefficients,
+            random_scalar(),
+        ))
+    }
+
+    /// # DKG Round Two
+    /// Verifies the round-one packages of all other participants and computes
+    /// the share fᵢ(j) for every other participant j.
+    /// ## Errors:
+    /// * [`OperationError::IncorrectNumberOfPackages`] unless there is exactly
+    ///   one package from every other participant.
+    /// * [`OperationError::InvalidProofOfKnowledge`] with the first participant
+    ///   whose commitment or proof is invalid.
+    pub fn part2(
+        secret: Round1SecretPackage,
+        round1_packages: &BTreeMap<Identifier, Round1Package>,
+    ) -> Result<(Round2SecretPackage, BTreeMap<Identifier, Round2Package>), OperationError> {
+        check_senders(
+            secret.identifier,
+            secret.max_signers,
+            round1_packages.keys(),
+        )?;
+        for (&id, package) in round1_packages {
+            if package.commitment.0.len() != secret.coefficients.len() || !verify_proof(id, package)
+            {
+                return Err(OperationError::InvalidProofOfKnowledge(id));
+            }
+        }
+
+        let round2_packages = round1_packages
+            .keys()
+            .map(|&id| {
+                let share = evaluate(&secret.coefficients, id);
+                (
+                    id,
+                    Round2Package {
+                        signing_share: SecretScalar::new(share),
+                    },
+                )
+            })
+            .collect();
+        let round2_secret = Round2SecretPackage {
+            identifier: secret.identifier,
+            commitment: secret.commitment.clone(),
+            own_share: evaluate(&secret.coefficients, secret.identifier),
+            max_signers: secret.max_signers,
+        };
+        Ok((round2_secret, round2_packages))
+    }
+
+    /// # DKG Round Three
+    /// Verifies the shares received from all other participants against their
+    /// round-one commitments and sums them into this participant's share of
+    /// the group secret key.
+    /// ## Algorithm:
+    /// * sᵢ ← Σ fⱼ(i) over all participants j, including i
+    /// * group commitment: Σ of all polynomial commitments
+    /// ## Errors:
+    /// * [`OperationError::IncorrectNumberOfPackages`] unless both maps hold
+    ///   exactly one package from every other participant.
+    /// * [`OperationError::InvalidSecretShare`] with the first participant whose
+    ///   share does not match its commitment.
+    pub fn part3(
+        secret: &Round2SecretPackage,
+        round1_packages: &BTreeMap<Identifier, Round1Package>,
+        round2_packages: &BTreeMap<Identifier, Round2Package>,
+    ) -> Result<(KeyPackage, PublicKeyPackage), OperationError> {
+        check_senders(
+            secret.identifier,
+            secret.max_signers,
+            round1_packages.keys(),
+        )?;
+        if !round1_packages.keys().eq(round2_packages.keys()) {
+            return Err(OperationError::IncorrectNumberOfPackages);
+        }
+
+        let mut signing_share = secret.own_share;
+        let mut group_commitment = secret.commitment.0.clone();
+        for (id, package) in round2_packages {
+            let commitment = &round1_packages[id].commitment;
+            let share = package.signing_share.expose();
+            if commitment.0.len() != group_commitment.len()
+                || !commitment.holds(secret.identifier, share)
+            {
+                signing_share.val.zeroize();
+                return Err(OperationError::InvalidSecretShare(*id));
+            }
+            signing_share = signing_share.add_mod(share);
+            for (acc, c) in group_commitment.iter_mut().zip(&commitment.0) {
+                *acc = *acc + *c;
+            }
+        }
+
+        let commitment = FeldmanCommitment(group_commitment);
+        let key_package = KeyPackage {
+            identifier: secret.identifier,
+            signing_share: SecretScalar::new(signing_share),
+            group_public_key: commitment.public_key(),
+            min_signers: commitment.0.len() as u16,
+        };
+        signing_share.val.zeroize();
+        Ok((key_package, PublicKeyPackage { commitment }))
+    }
+
+    pub(super) fn round1(
+        identifier: Identifier,
+        max_signers: u16,
+        coefficients: Vec<Scalar>,
+        mut k: Scalar,
+    ) -> (Round1SecretPackage, Round1Package) {
+        let commitment = FeldmanCommitment::from_polynomial(&coefficients);
+        let proof_r = ExtendedPoint::generator() * k;
+        let c = proof_challenge(identifier, &commitment.public_key(), &proof_r);
+        let proof_mu = k.add_mod(&coefficients[0].mul_mod(&c));
+        k.val.zeroize();
+
+        let package = Round1Package {
+            commitment: commitment.clone(),
+            proof_r,
+            proof_mu,
+        };
+        let secret = Round1SecretPackage {
+            identifier,
+            coefficients,
+            commitment,
+            max_signers,
+        };
+        (secret, package)
+    }
+
+    // accept iff 𝑅 = μ*𝑮 - c*(a₀*𝑮)
+    fn verify_proof(identifier: Identifier, package: &Round1Package) -> bool {
+        let public = package.commitment.public_key();
+        let c = proof_challenge(identifier, &public, &package.proof_r);
+        ExtendedPoint::generator() * package.proof_mu == package.proof_r + public * c
+    }
+
+    // HDKG(i || a₀*𝑮 || 𝑅)
+    fn proof_challenge(
+        identifier: Identifier,
+        public: &ExtendedPoint,
+        r: &ExtendedPoint,
+    ) -> Scalar {
+        let mut input = encode_identifier(identifier).to_vec();
+        input.extend_from_slice(&encode_point(public));
+        input.extend_from_slice(&encode_point(r));
+        scalar_from_bytes_wide(&h(b"dkg", &input))
+    }
+
+    fn check_senders<'a>(
+        identifier: Identifier,
+        max_signers: u16,
+        senders: impl Iterator<Item = &'a Identifier>,
+    ) -> Result<(), OperationError> {
+        let mut count = 0;
+        for &id in senders {
+            if id == 0 || id == identifier {
+                return Err(OperationError::InvalidParticipantIdentifier);
+            }
+            count += 1;
+        }
+        if count + 1 == max_signers as usize {
+            Ok(())
+        } else {
+            Err(OperationError::IncorrectNumberOfPackages)
+        }
+    }
+
+    impl Drop for Round1SecretPackage {
+        fn drop(&mut self) {
+            for a in self.coefficients.iter_mut() {
+                a.val.zeroize();
+            }
+        }
+    }
+
+    impl Drop for Round2SecretPackage {
+        fn drop(&mut self) {
+            self.own_share.val.zeroize();
+        }
+    }
+
+    impl std::fmt::Debug for Round1SecretPackage {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("Round1SecretPackage(..)")
+        }
+    }
+
+    impl std::fmt::Debug for Round2SecretPackage {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("Round2SecretPackage(..)")
+        }
+    }
+}
+
+#[cfg(test)]
+mod frost_tests {
+    use super::{dkg, nonce_generate, share_polynomial, KeyPackage, SigningNonces, SigningPackage};
+    use crate::ecc::edwards448::{
+        decode_point, encode_point, scalar_from_canonical_bytes, scalar_to_bytes_le,
+    };
+    use std::collections::BTreeMap;
+    use tiny_ed448_goldilocks::curve::field::scalar::Scalar;
+
+    fn scalar(hex: &str) -> Scalar {
+        scalar_from_canonical_bytes(&hex::decode(hex).unwrap()).unwrap()
+    }
+
+    fn scalar_hex(s: &Scalar) -> String {
+        hex::encode(scalar_to_bytes_le(s))
+    }
+
+    // RFC 9591 Appendix E.2, FROST(Ed448, SHAKE256)
+    #[test]
+    fn rfc_9591_vector() {
+        let group_secret = scalar("6298e1eef3c379392caaed061ed8a31033c9e9e3420726f23b404158a401cd9df24632adfe6b418dc942d8a091817dd8bd70e1c72ba52f3c00");
+        let coefficient = scalar("dbd7a514f7a731976620f0436bd135fe8dddc3fadd6e0d13dbd58a1981e587d377d48e0b7ce4e0092967c5e85884d0275a7a740b6abdcd0500");
+        let (shares, public) = share_polynomial(&[group_secret, coefficient], 3);
+        assert_eq!(
+            hex::encode(encode_point(&public.group_public_key())),
+            "3832f82fda00ff5365b0376df705675b63d2a93c24c6e81d40801ba265632be10f443f95968fadb70d10786827f30dc001c8d0f9b7c1d1b000"
+        );
+        let expected_shares = [
+            "4a2b2f5858a932ad3d3b18bd16e76ced3070d72fd79ae4402df201f525e754716a1bc1b87a502297f2a99d89ea054e0018eb55d39562fd0100",
+            "2503d56c4f516444a45b080182b8a2ebbe4d9b2ab509f25308c88c0ea7ccdc44e2ef4fc4f63403a11b116372438a1e287265cadeff1fcb0700",
+            "00db7a8146f995db0a7cf844ed89d8e94c2b5f259378ff66e39d172828b264185ac4decf7219e4aa4478285b9c0eef4fccdf3eea69dd980d00",
+        ];
+        for (share, expected) in shares.iter().zip(expected_shares) {
+            assert_eq!(scalar_hex(share.value.expose()), expected);
+        }
+
+        // (identifier, hiding randomness, binding randomness, hiding nonce, binding nonce, share)
+        let signers = [
+            (
+                1,
+                "3d9e765ab0f06bc3131acc2f7642223255fd5295f3c04bef5c644c0bae4f85be",
+                "86160f8224ea273128b0ea5af828a9c0b0985cd9b52a3b8b668ae9d3205a6672",
+                "d9610abd59ac2e9b33a3919acf28f5c895918389c69a26a3fcfe6a70edab4685e9ea3d3641d8bfe1634616fafd457b2ef6503c51f191991300",
+                "1caf737f1e73b5ab7f155c239ea70fb5783cd8da454298984a325cb4eb968b0e625936cbb9623e57919b3cc01be40f7f45b6c37fd947772b00",
+                "0dad0c550bade2576c6cbe1d7b57a55b00e57a6e3683c8f84fbfa48751745bb0b2b89624e7d1b443b1ff62ffde9ee0c15e5df678c4ed1c0400",
+            ),
+            (
+                3,
+                "8cba373b6c3a3601d5cfa3d37cbc93f310d253a2fcf886695bb59ac703d8a100",
+                "1a84c88d88fbb99a4417157487b45367eb91d7dd2ba55566d6784f39f750a2f7",
+                "3a8f5b86cf6e80d3a2d99ac5628a1229eaa9dd205b3b3bdd3bc8d2270c749d08836379c8b50d964f492ba8785f3deb571e0b4e7db887be1400",
+                "4b529518068f9b261d56cffced7f753b9b22db6a79c8c495584ec67edc561da644b70f9af42e633e14bc145b3ea61c5d2bf30090c3700b2f00",
+                "0baf31c64cf452fe3a520f14c5e2b898d6869341fdd8ea26044086a11a4372cd31ba2ee9cf7e316706067a53d05e184ebb95cae16aac7f3700",
+            ),
+        ];
+        let mut nonces = Vec::new();
+        let mut key_packages = Vec::new();
+        for (id, hiding_rand, binding_rand, hiding, binding, _) in signers {
+            let key_package = KeyPackage::from_secret_share(&shares[id as usize - 1]).unwrap();
+            let secret = key_package.signing_share.expose();
+            let d = nonce_generate(&hex::decode(hiding_rand).unwrap(), secret);
+            let e = nonce_generate(&hex::decode(binding_rand).unwrap(), secret);
+            assert_eq!(scalar_hex(&d), hiding);
+            assert_eq!(scalar_hex(&e), binding);
+            nonces.push(SigningNonces::new(id, d, e));
+            key_packages.push(key_package);
+        }
+        assert_eq!(
+            hex::encode(encode_point(&nonces[0].commitments.binding)),
+            "605d2b3b5af4159ca3fa23e4463a3573d4144b43c7b01322d621817c80fcd84f874ea79655648ca65f128a4cfbec236c0c89f3898b27529180"
+        );
+
+        let commitments = nonces.iter().map(|n| n.commitments).collect();
+        let package = SigningPackage::new(commitments, b"test").unwrap();
+        let binding_factors = package.binding_factors(&public.group_public_key());
+        assert_eq!(
+            scalar_hex(&binding_factors[1]),
+            "14e0cfb824bd2003099f3ebb2fe00e95355eab38098a5619acc62a8faa2092ce25cdc061a7b51b6ed41e291ed311a2909d2ce8c3acadfc1200"
+        );
+
+        let shares: Vec<_> = key_packages
+            .iter()
+            .zip(nonces)
+            .map(|(key_package, nonces)| key_package.sign(&package, nonces).unwrap())
+            .collect();
+        for (share, signer) in shares.iter().zip(signers) {
+            assert_eq!(scalar_hex(&share.z), signer.5);
+        }
+        assert_eq!(
+            hex::encode(public.aggregate(&package, &shares).unwrap().to_bytes()),
+            "60e3d4d641dd19054c1eced47f02f4a5e377126fb75f68cbd1122d948f93e26069f6740506da0366eb4225746faf894ecd196a1f56d1e1d280185c3e1b58a13556a7becd31403a5ef4d66b0eb0335cb31f54ff2a296cb7cd7de472c50db750e6aab705dd52affdf80f1af3c05a2f9a9c3b00"
+        );
+    }
+
+    // DKG vectors of the ZF FROST implementation for FROST(Ed448, SHAKE256)
+    #[test]
+    fn dkg_vector() {
+        // (signing key a₀, coefficient a₁, proof of knowledge, signing share)
+        let participants = [
+            (
+                "01cbf4ac6b8ee0d03b799a62b7d7f32f05e2e117e9362f11004071fdb17ee2740206f45c910b4754eb0729e36e657136acfe16ff5abaf13800",
+                "74d5bf96312d4aaebf4c6a7e4760a50233f3fb0ce76f34df95781cd8a07b1607073cf70d8272f76956c67f6a9d643ba257e280f19754692300",
+                "345f573924403f60672d42d2f428959935f2dca278cec91c955d166ab06ca0e7919e311d376f0c5e4e28a5df96dda71d36576fc7d7748b128063d520bd4b3a6ddcdf05bb84c87a83766218067a3ebb85664084a5a8c58d99b073526234be5ec518333f5e8bcc80ed11d563569123f2fd1200",
+                "1fdca64c6411f60ea7e3f4a271d3e625dffcdf1b945b51d98c8dd3500edb23bc8d3d0f885712d8ec8d349be9c3a0f804f60cfd4d2b37db0c00",
+            ),
+            (
+                "9e17dfce20dfd4f0d0bd893a75cba3ff3ab6574d716cd68b4b393d9cd3ff747dcbb6d629ee48d8cc06b29397f3706e18a497f641ee633b2d00",
+                "37ca04419ca24b74f5da8001d8d6fdcdc86fe7c49b54e6913aae526165d4e6f5b6b075933e452e7f84fc9fed2820f83abde9c1fdf4e8a43500",
+                "7bd1f9052217b2a02f4798906480ac288927c54dbd846e5bcbf4d08389a205269bf620a6ad1a04a3a0bcfd238c57f5f3960dd4795cebe66a80ed3c4617d163ec19648bd55d2f7acb4c4080c0062b878086ddb73f83aee1ad73426cab8a7d08f4540ccf6e31e08606861dd5daca506fc10d00",
+                "f6ce1c841fd7064fc55d62515c8d6679dbabd725966622f5fa17519b16eb10ca19d51c0fcef9c27e81f7c26885373e0e769f009b4f4d203b00",
+            ),
+            (
+                "82d5659bd4259a777be0b300b2bd1507535931b9143f676d90f505e37e4cdfbb33e9367a61d6cd39a8b7b6ef9f33d3ac25e4ebbfbd02693800",
+                "1f98090b80b8f340bee147bc3d4549a49082eae6c821056f8787d88d01c0ef10ceaaa0e5b52fc5a818000827fb11122c6bc6c05d97d8361500",
+                "eb47491f2461792114d357d02102c1a806451cfa88f1297f7a671a87a04de0ffde478ec1c2b91e743379254fe84eb2e0d170c69aec88bc1980bf8009ffc93d6ee0c3713681aa303cf85595bd975953318ab07be9e56dc6ba22465793ee337e383562fafc7525c05b36732b93f5f4fee22300",
+                "da7c3a1048da9e6b8e480a72d48479ab4724f9804e96a44c7f7e04691ffbfdd7a56c2a9644e1ad1075baeae746ce8317f63104e87363652900",
+            ),
+        ];
+
+        let mut secrets = Vec::new();
+        let mut round1_packages = BTreeMap::new();
+        for (i, (a0, a1, proof, _)) in participants.iter().enumerate() {
+            let id = i as u16 + 1;
+            let (secret, mut package) =
+                dkg::round1(id, 3, vec![scalar(a0), scalar(a1)], Scalar::from(1_u64));
+            let proof = hex::decode(proof).unwrap();
+            package.proof_r = decode_point(&proof[..57]).unwrap();
+            package.proof_mu = scalar_from_canonical_bytes(&proof[57..]).unwrap();
+            secrets.push(secret);
+            round1_packages.insert(id, package);
+        }
+
+        let mut round2_secrets = Vec::new();
+        let mut round2_packages: BTreeMap<u16, BTreeMap<u16, dkg::Round2Package>> = BTreeMap::new();
+        for (i, secret) in secrets.into_iter().enumerate() {
+            let id = i as u16 + 1;
+            let mut others = round1_packages.clone();
+            others.remove(&id);
+            let (round2_secret, packages) = dkg::part2(secret, &others).unwrap();
+            for (to, package) in packages {
+                round2_packages.entry(to).or_default().insert(id, package);
+            }
+            round2_secrets.push(round2_secret);
+        }
+
+        for (i, secret) in round2_secrets.iter().enumerate() {
+            let id = i as u16 + 1;
+            let mut others = round1_packages.clone();
+            others.remove(&id);
+            let (key_package, public) = dkg::part3(secret, &others, &round2_packages[&id]).unwrap();
+            assert_eq!(
+                scalar_hex(key_package.signing_share.expose()),
+                participants[i].3
+            );
+            assert_eq!(
+                hex::encode(encode_point(&public.group_public_key())),
+                "2c73a3b674e283032effc2494341a608145179b229d664a870336a09b433363079a5ad68044eed31c3cc187267fe8216e559e4e4a8b9f49a80"
+            );
+        }
+        assert_eq!(
+            hex::encode(encode_point(&round1_packages[&1].commitment.0[0])),
+            "2747193abad63b685e15421f8c15c7c98107bf00d447561906a23e595dcba7367b7cf5ec4b7c751ec4f1721be4a1162d906af4c88a74c09b80"
+        );
+    }
+}
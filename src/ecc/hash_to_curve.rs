@@ -0,0 +1,205 @@
+//! Hashing of byte strings to points of edwards448 as specified in RFC 9380.
+//!
+//! Implements the suites `edwards448_XOF:SHAKE256_ELL2_RO_` with
+//! [`hash_to_curve`](crate::ecc::hash_to_curve::hash_to_curve) and
+//! `edwards448_XOF:SHAKE256_ELL2_NU_` with
+//! [`encode_to_curve`](crate::ecc::hash_to_curve::encode_to_curve). The message is
+//! expanded with [`expand_message_xof`](crate::ecc::hash_to_curve::expand_message_xof)
+//! using this crate's SHAKE256, reduced to elements of GF(p), mapped to curve448
+//! with Elligator 2, carried over to edwards448 with the 4-isogeny of RFC 7748 and
+//! multiplied by the cofactor. Nobody knows the discrete logarithm of the
+//! resulting point to 𝑮 or to any other hashed point.
+//!
+//! Every protocol must hash with its own domain separation tag `dst`, so that
+//! points hashed by one protocol are unrelated to those of another.
+//!
+//! Points are returned in the crate's internal representation, so
+//! [`encode_point`](crate::ecc::edwards448::encode_point) of the result is the
+//! RFC 9380 output in RFC 8032 encoding.
+use super::edwards448::{isogeny, pow_p34};
+use crate::{sha3::shake_functions::shake256, OperationError};
+use crypto_bigint::{
+    subtle::{Choice, ConditionallyNegatable, ConditionallySelectable, ConstantTimeEq},
+    Encoding, NonZero, U1024, U448,
+};
+use tiny_ed448_goldilocks::curve::{
+    extended_edwards::ExtendedPoint, field::field_element::FieldElement,
+};
+
+/// The field modulus p = 2⁴⁴⁸ - 2²²⁴ - 1.
+const MODULUS: U448 = U448::from_be_hex(
+    "fffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+);
+/// L = ceil((ceil(log2(p)) + k) / 8) bytes of uniform output per field element, k = 224.
+const FIELD_ELEMENT_BYTES: usize = 84;
+/// Length of the hash of an oversized domain separation tag, ceil(2k / 8).
+const OVERSIZE_DST_BYTES: usize = 56;
+
+/// # Message Expansion
+/// Implements expand_message_xof of RFC 9380 5.3.2 with SHAKE256.
+/// ## Algorithm:
+/// * if len(dst) > 255: dst ← SHAKE256("H2C-OVERSIZE-DST-" || dst, 56)
+/// * dst' ← dst || len(dst) as one byte
+/// * return SHAKE256(msg || len_in_bytes as two big-endian bytes || dst', len_in_bytes)
+/// ## Errors:
+/// * [`OperationError::ExpandLengthTooLarge`] if `len_in_bytes` > 65535.
+pub fn expand_message_xof(
+    msg: &[u8],
+    dst: &[u8],
+    len_in_bytes: usize,
+) -> Result<Vec<u8>, OperationError> {
+    let len = u16::try_from(len_in_bytes).map_err(|_| OperationError::ExpandLengthTooLarge)?;
+    let dst = if dst.len() > 255 {
+        shake256(
+            &[b"H2C-OVERSIZE-DST-".as_slice(), dst].concat(),
+            8 * OVERSIZE_DST_BYTES,
+        )
+    } else {
+        dst.to_vec()
+    };
+
+    let mut msg_prime = msg.to_vec();
+    msg_prime.extend_from_slice(&len.to_be_bytes());
+    msg_prime.extend_from_slice(&dst);
+    msg_prime.push(dst.len() as u8);
+    Ok(shake256(&msg_prime, 8 * len_in_bytes))
+}
+
+/// # Hash to Field
+/// Implements hash_to_field of RFC 9380 5.2 for GF(p), p = 2⁴⁴⁸ - 2²²⁴ - 1.
+/// ## Algorithm:
+/// * uniform_bytes ← expand_message_xof(msg, dst, 84 * count)
+/// * uᵢ ← uniform_bytes[84i..84(i + 1)] as a big-endian integer mod p
+/// ## Errors:
+/// * [`OperationError::ExpandLengthTooLarge`] if 84 * `count` > 65535, i.e.
+///   for `count` > 780.
+pub fn hash_to_field(
+    msg: &[u8],
+    dst: &[u8],
+    count: usize,
+) -> Result<Vec<FieldElement>, OperationError> {
+    let modulus = NonZero::new(MODULUS.resize::<{ U1024::LIMBS }>()).unwrap();
+    let len_in_bytes = count
+        .checked_mul(FIELD_ELEMENT_BYTES)
+        .ok_or(OperationError::ExpandLengthTooLarge)?;
+    Ok(expand_message_xof(msg, dst, len_in_bytes)?
+        .chunks(FIELD_ELEMENT_BYTES)
+        .map(|chunk| {
+            let mut wide = [0u8; 128];
+            wide[128 - FIELD_ELEMENT_BYTES..].copy_from_slice(chunk);
+            let reduced = U1024::from_be_slice(&wide)
+                .rem(&modulus)
+                .resize::<{ U448::LIMBS }>();
+            FieldElement::from_bytes(&reduced.to_le_bytes())
+        })
+        .collect())
+}
+
+/// # Hash to Curve
+/// Implements hash_to_curve of RFC 9380 3 for the suite
+/// `edwards448_XOF:SHAKE256_ELL2_RO_`. The output is indistinguishable from a
+/// uniformly random point of the prime-order subgroup, so it can stand in for a
+/// random oracle, e.g. as a password-derived generator.
+/// ## Algorithm:
+/// * (u₀, u₁) ← hash_to_field(msg, dst, 2)
+/// * Q₀ ← map_to_curve(u₀), Q₁ ← map_to_curve(u₁)
+/// * return 4*(Q₀ + Q₁)
+pub fn hash_to_curve(msg: &[u8], dst: &[u8]) -> ExtendedPoint {
+    // two field elements take 168 bytes, well below the limit
+    let u = hash_to_field(msg, dst, 2).unwrap();
+    (map_to_curve(&u[0]) + map_to_curve(&u[1]))
+        .double()
+        .double()
+}
+
+/// # Encode to Curve
+/// Implements encode_to_curve of RFC 9380 3 for the suite
+/// `edwards448_XOF:SHAKE256_ELL2_NU_`. The output is not uniformly distributed
+/// over the group, which is sufficient for protocols that only need a point of
+/// unknown discrete logarithm, such as VRFs. Prefer [`hash_to_curve`] otherwise.
+/// ## Algorithm:
+/// * u ← hash_to_field(msg, dst, 1)
+/// * Q ← map_to_curve(u)
+/// * return 4*Q
+pub fn encode_to_curve(msg: &[u8], dst: &[u8]) -> ExtendedPoint {
+    let u = hash_to_field(msg, dst, 1).unwrap();
+    map_to_curve(&u[0]).double().double()
+}
+
+/// Maps a field element to a point of edwards448 with Elligator 2 on curve448
+/// followed by the rational map of RFC 9380 6.8.2, returning its image under φ.
+fn map_to_curve(u: &FieldElement) -> ExtendedPoint {
+    let (s, t) = elligator2_curve448(u);
+    let (x, y) = curve448_to_edwards448(&s, &t);
+    isogeny(&x, &y)
+}
+
+/// Elligator 2 for curve448 t² = s³ + As² + s, A = 156326, with Z = -1,
+/// following the straight-line steps of RFC 9380 6.7.1.
+fn elligator2_curve448(u: &FieldElement) -> (FieldElement, FieldElement) {
+    let a = FieldElement::from_raw_slice([156326, 0, 0, 0, 0, 0, 0, 0]);
+    let one = FieldElement::one();
+
+    // Z * u² with the exceptional case Z * u² = -1 mapped to 0
+    let mut t1 = u.square().negate();
+    let exceptional = t1.ct_eq(&FieldElement::minus_one());
+    t1.conditional_assign(&FieldElement::zero(), exceptional);
+
+    // x1 = -A / (1 + Z * u²), gx1 = x1³ + Ax1² + x1
+    let x1 = a.negate() * (t1 + one).invert();
+    let gx1 = ((x1 + a) * x1 + one) * x1;
+    // x2 = -x1 - A, gx2 = Z * u² * gx1
+    let x2 = x1.negate() - a;
+    let gx2 = t1 * gx1;
+
+    let gx1_is_square = is_square(&gx1);
+    let s = FieldElement::conditional_select(&x2, &x1, gx1_is_square);
+    let y2 = FieldElement::conditional_select(&gx2, &gx1, gx1_is_square);
+    let mut t = sqrt(&y2);
+    // sgn0(t) = 1 exactly when x1 was chosen
+    let negate = sgn0(&t) ^ gx1_is_square;
+    t.conditional_negate(negate);
+    (s, t)
+}
+
+/// The rational map from curve448 to edwards448 of RFC 9380 6.8.2, sending
+/// the exceptional points with a vanishing denominator to the identity.
+fn curve448_to_edwards448(s: &FieldElement, t: &FieldElement) -> (FieldElement, FieldElement) {
+    let ss = s.square();
+    let tt2 = t.square() + t.square();
+    let ss_minus_one = ss - FieldElement::one();
+
+    // x = 4t(s² - 1) / (s⁴ - 2s² + 1 + 4t²)
+    let x_num = (ss_minus_one * *t) + (ss_minus_one * *t);
+    let x_num = x_num + x_num;
+    let x_den = ss_minus_one.square() + tt2 + tt2;
+
+    // y = -(s⁵ - 2s³ + s - 4st²) / (s⁵ - 2s³ + s - 2s²t² - 2t²)
+    let s5 = ss_minus_one.square() * *s;
+    let y_num = tt2 * (*s + *s) - s5;
+    let y_den = s5 - (ss + FieldElement::one()) * tt2;
+
+    let den = x_den * y_den;
+    let inv = den.invert();
+    let mut x = x_num * y_den * inv;
+    let mut y = y_num * x_den * inv;
+    let exceptional = den.ct_eq(&FieldElement::zero());
+    x.conditional_assign(&FieldElement::zero(), exceptional);
+    y.conditional_assign(&FieldElement::one(), exceptional);
+    (x, y)
+}
+
+/// Euler's criterion: a^((p-1)/2) = a^(2(p-3)/4 + 1) is 0 or 1 for squares.
+fn is_square(a: &FieldElement) -> Choice {
+    let legendre = pow_p34(a).square() * *a;
+    !legendre.ct_eq(&FieldElement::minus_one())
+}
+
+/// A square root a^((p+1)/4) = a^((p-3)/4 + 1), valid since p ≡ 3 mod 4.
+fn sqrt(a: &FieldElement) -> FieldElement {
+    pow_p34(a) * *a
+}
+
+fn sgn0(a: &FieldElement) -> Choice {
+    Choice::from(a.to_bytes()[0] & 1)
+}
@@ -0,0 +1,244 @@
+//! Hierarchical deterministic key derivation in the style of BIP32 and SLIP-10.
+//!
+//! A master secret scalar and a 64-byte chain code are derived from a seed
+//! with KMAC. Each child scalar is the parent scalar plus a tweak t, so that
+//! for non-hardened children the public key V + t*𝑮 can be derived from the
+//! parent public key alone:
+//! * hardened (index ≥ 2³¹): t ← kmac_xof(c, s || index, ..., “HDH”)
+//! * non-hardened: t ← kmac_xof(c, V || index, ..., “HDN”)
+//!
+//! As in BIP32, anyone holding an [`ExtendedPublicKey`](crate::ecc::hd::ExtendedPublicKey)
+//! and any non-hardened child secret key can recover the parent secret key.
+//! Only share extended public keys of accounts below a hardened step, and keep
+//! all child secrets derived through non-hardened steps as secret as the parent.
+use super::{
+    edwards448::{encode_point, scalar_from_bytes_wide, scalar_to_bytes_le},
+    keypair::{KeyPair, SecretScalar},
+};
+use crate::{sha3::shake_functions::kmac_xof, OperationError, SecParam};
+use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+
+/// Offset of hardened child indices.
+pub const HARDENED: u32 = 1 << 31;
+/// Length in bytes of a chain code.
+pub const CHAIN_CODE_BYTES: usize = 64;
+/// Minimum length in bytes of a master seed.
+pub const MIN_SEED_BYTES: usize = 16;
+
+// Output of one derivation step: a 114-byte tweak reduced mod r, then the chain code.
+const TWEAK_BYTES: usize = 114;
+
+/// A list of child indices, see [`DerivationPath::parse`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivationPath(Vec<u32>);
+
+/// A secret scalar together with its chain code.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtendedSecretKey {
+    secret: SecretScalar,
+    /// Chain code mixed into every child derivation
+    pub chain_code: [u8; CHAIN_CODE_BYTES],
+    /// Number of derivation steps from the master key
+    pub depth: u8,
+    /// Index this key was derived with, 0 for the master key
+    pub child_number: u32,
+}
+
+/// A public key together with its chain code. Can only derive non-hardened children.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExtendedPublicKey {
+    /// Public key 𝑉 = s*𝑮
+    pub pub_key: ExtendedPoint,
+    /// Chain code mixed into every child derivation
+    pub chain_code: [u8; CHAIN_CODE_BYTES],
+    /// Number of derivation steps from the master key
+    pub depth: u8,
+    /// Index this key was derived with, 0 for the master key
+    pub child_number: u32,
+}
+
+impl DerivationPath {
+    /// # Path Parsing
+    /// Parses paths such as `m/44'/1'/0'` or `M/0/1`. A trailing `'` or `h`
+    /// marks a hardened index, which adds [`HARDENED`] to it.
+    /// ## Errors:
+    /// * [`OperationError::InvalidDerivationPath`] if the path does not start with
+    ///   `m` or `M`, or an index is not a decimal number below 2³¹.
+    pub fn parse(path: &str) -> Result<DerivationPath, OperationError> {
+        let mut segments = path.split('/');
+        if !matches!(segments.next(), Some("m") | Some("M")) {
+            return Err(OperationError::InvalidDerivationPath);
+        }
+        segments
+            .map(|segment| {
+                let (digits, offset) = match segment.strip_suffix(['\'', 'h']) {
+                    Some(digits) => (digits, HARDENED),
+                    None => (segment, 0),
+                };
+                if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(OperationError::InvalidDerivationPath);
+                }
+                match digits.parse::<u32>() {
+                    Ok(index) if index < HARDENED => Ok(index + offset),
+                    _ => Err(OperationError::InvalidDerivationPath),
+                }
+            })
+            .collect::<Result<Vec<u32>, OperationError>>()
+            .map(DerivationPath)
+    }
+
+    /// The child indices, hardened ones with [`HARDENED`] added.
+    pub fn indices(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+impl ExtendedSecretKey {
+    /// # Master Key Generation
+    /// Derives the master key from a seed.
+    /// ## Algorithm:
+    /// * (t || c) ← kmac_xof(seed, “”, 8 * (114 + 64), “HDM”)
+    /// * s ← t mod r
+    /// ## Errors:
+    /// * [`OperationError::InvalidSecretLength`] if `seed` is shorter than 16 bytes.
+    /// * [`OperationError::DerivedKeyInvalid`] in the negligible case s = 0.
+    pub fn from_seed(seed: &[u8]) -> Result<ExtendedSecretKey, OperationError> {
+        if seed.len() < MIN_SEED_BYTES {
+            return Err(OperationError::InvalidSecretLength);
+        }
+        let (t, chain_code) = expand(seed, &[], "HDM");
+        if t == Scalar::from(0_u64) {
+            return Err(OperationError::DerivedKeyInvalid);
+        }
+        Ok(ExtendedSecretKey {
+            secret: SecretScalar::new(t),
+            chain_code,
+            depth: 0,
+            child_number: 0,
+        })
+    }
+
+    /// # Child Key Derivation
+    /// Derives the child at `index`; indices from [`HARDENED`] on are hardened.
+    /// ## Algorithm:
+    /// * hardened: (t || c') ← kmac_xof(c, s || index, 8 * (114 + 64), “HDH”)
+    /// * otherwise: (t || c') ← kmac_xof(c, 𝑉 || index, 8 * (114 + 64), “HDN”)
+    /// * s' ← s + (t mod r)
+    /// ## Errors:
+    /// * [`OperationError::DerivedKeyInvalid`] in the negligible case s' = 0 or
+    ///   if the depth would exceed 255.
+    pub fn derive_child(&self, index: u32) -> Result<ExtendedSecretKey, OperationError> {
+        let s = self.secret.expose();
+        let (t, chain_code) = if index >= HARDENED {
+            let mut data = scalar_to_bytes_le(s).to_vec();
+            data.extend_from_slice(&index.to_be_bytes());
+            let tweak = expand(&self.chain_code, &data, "HDH");
+            data.fill(0);
+            tweak
+        } else {
+            tweak_public(&self.chain_code, &(ExtendedPoint::generator() * *s), index)
+        };
+
+        let child = s.add_mod(&t);
+        if child == Scalar::from(0_u64) {
+            return Err(OperationError::DerivedKeyInvalid);
+        }
+        Ok(ExtendedSecretKey {
+            secret: SecretScalar::new(child),
+            chain_code,
+            depth: self
+                .depth
+                .checked_add(1)
+                .ok_or(OperationError::DerivedKeyInvalid)?,
+            child_number: index,
+        })
+    }
+
+    /// Derives the key at `path` below this key, see [`DerivationPath::parse`].
+    pub fn derive_path(&self, path: &str) -> Result<ExtendedSecretKey, OperationError> {
+        DerivationPath::parse(path)?
+            .indices()
+            .iter()
+            .try_fold(self.clone(), |key, &index| key.derive_child(index))
+    }
+
+    /// The extended public key with the same chain code.
+    pub fn public_key(&self) -> ExtendedPublicKey {
+        ExtendedPublicKey {
+            pub_key: ExtendedPoint::generator() * *self.secret.expose(),
+            chain_code: self.chain_code,
+            depth: self.depth,
+            child_number: self.child_number,
+        }
+    }
+
+    /// The [`KeyPair`] holding this key's secret scalar.
+    pub fn to_keypair(&self, owner: String) -> KeyPair {
+        KeyPair::from_scalar(*self.secret.expose(), owner)
+    }
+}
+
+impl ExtendedPublicKey {
+    /// # Public Child Key Derivation
+    /// Derives the public key of the non-hardened child at `index`.
+    /// ## Algorithm:
+    /// * (t || c') ← kmac_xof(c, 𝑉 || index, 8 * (114 + 64), “HDN”)
+    /// * 𝑉' ← 𝑉 + (t mod r)*𝑮
+    /// ## Errors:
+    /// * [`OperationError::HardenedPublicDerivation`] if `index` is hardened.
+    /// * [`OperationError::DerivedKeyInvalid`] in the negligible case 𝑉' = 𝒪 or
+    ///   if the depth would exceed 255.
+    pub fn derive_child(&self, index: u32) -> Result<ExtendedPublicKey, OperationError> {
+        if index >= HARDENED {
+            return Err(OperationError::HardenedPublicDerivation);
+        }
+        let (t, chain_code) = tweak_public(&self.chain_code, &self.pub_key, index);
+        let pub_key = self.pub_key + ExtendedPoint::generator() * t;
+        if pub_key == ExtendedPoint::id_point() {
+            return Err(OperationError::DerivedKeyInvalid);
+        }
+        Ok(ExtendedPublicKey {
+            pub_key,
+            chain_code,
+            depth: self
+                .depth
+                .checked_add(1)
+                .ok_or(OperationError::DerivedKeyInvalid)?,
+            child_number: index,
+        })
+    }
+
+    /// Derives the public key at `path` below this key, which must not contain
+    /// hardened steps, see [`DerivationPath::parse`].
+    pub fn derive_path(&self, path: &str) -> Result<ExtendedPublicKey, OperationError> {
+        DerivationPath::parse(path)?
+            .indices()
+            .iter()
+            .try_fold(*self, |key, &index| key.derive_child(index))
+    }
+}
+
+fn tweak_public(
+    chain_code: &[u8; CHAIN_CODE_BYTES],
+    pub_key: &ExtendedPoint,
+    index: u32,
+) -> (Scalar, [u8; CHAIN_CODE_BYTES]) {
+    let mut data = encode_point(pub_key).to_vec();
+    data.extend_from_slice(&index.to_be_bytes());
+    expand(chain_code, &data, "HDN")
+}
+
+fn expand(key: &[u8], data: &[u8], s: &str) -> (Scalar, [u8; CHAIN_CODE_BYTES]) {
+    let mut out = kmac_xof(
+        key,
+        data,
+        8 * (TWEAK_BYTES + CHAIN_CODE_BYTES),
+        s,
+        SecParam::D512,
+    );
+    let t = scalar_from_bytes_wide(&out[..TWEAK_BYTES]);
+    let mut chain_code = [0u8; CHAIN_CODE_BYTES];
+    chain_code.copy_from_slice(&out[TWEAK_BYTES..]);
+    out.fill(0);
+    (t, chain_code)
+}
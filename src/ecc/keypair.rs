@@ -1,12 +1,19 @@
 #![warn(clippy::just_underscores_and_digits)]
-use crate::{sha3, SecParam};
+use super::{
+    eddsa::{SigningKey, SECRET_KEY_BYTES},
+    keystore::{EncryptedKeyPair, KdfParams},
+    x448::{edwards_diffie_hellman, X448PublicKey, X448_BYTES},
+};
+use crate::{sha3, OperationError, SecParam};
+use crypto_bigint::subtle::ConstantTimeEq;
 use serde::{Deserialize, Serialize};
 use sha3::{
-    aux_functions::byte_utils::{bytes_to_scalar, get_date_and_time_as_string},
+    aux_functions::byte_utils::{bytes_to_scalar, get_date_and_time_as_string, get_random_bytes},
     shake_functions::kmac_xof,
 };
 use std::{fs::File, io::Read};
 use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+use zeroize::Zeroize;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 /// An object containing the fields necessary to represent an asymmetric keypair.
@@ -14,42 +21,112 @@ pub struct KeyPair {
     /// String indicating the owner of the key, can be arbitrary
     pub owner: String,
     /// Public encryption key
+    #[serde(with = "super::edwards448::serde_point")]
     pub pub_key: ExtendedPoint,
-    /// value representing secret scalar, None if KeyType is PUBLIC
-    pub priv_key: Vec<u8>,
+    /// Secret scalar s with 𝑉 = s*𝑮
+    pub priv_key: SecretScalar,
     /// Date key was generated
     pub date_created: String,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+/// A secret scalar that is zeroized when dropped.
+pub struct SecretScalar(#[serde(with = "super::edwards448::serde_scalar")] Scalar);
+
+impl SecretScalar {
+    /// Wraps a secret scalar.
+    pub fn new(s: Scalar) -> SecretScalar {
+        SecretScalar(s)
+    }
+
+    /// The wrapped scalar. Copies made from it are not zeroized.
+    pub fn expose(&self) -> &Scalar {
+        &self.0
+    }
+}
+
+impl Drop for SecretScalar {
+    fn drop(&mut self) {
+        self.0.val.zeroize();
+    }
+}
+
+impl PartialEq for SecretScalar {
+    fn eq(&self, other: &SecretScalar) -> bool {
+        self.0.val.ct_eq(&other.0.val).into()
+    }
+}
+
+impl std::fmt::Debug for SecretScalar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SecretScalar(..)")
+    }
+}
+
 impl KeyPair {
     /// # Asymmetric [`KeyPair`] Generation
     /// Generates a (Schnorr/ECDHIES) key pair from passphrase pw.
+    /// Only the derived scalar is kept; the passphrase is not stored.
     ///
     /// ## Algorithm:
-    /// * s ← kmac_xof(pw, “”, 448, “K”); s ← 4s
+    /// * s ← kmac_xof(pw, “”, 448, “SK”); s ← 4s
     /// * 𝑉 ← s*𝑮
     /// * key pair: (s, 𝑉)
     /// ## Arguments:
     /// * pw: &Vec<u8> : password as bytes, can be blank but shouldnt be
     /// * owner: String : A label to indicate the owner of the key
-    /// * curve: [`EdCurves`] : The selected Edwards curve
+    /// * d: [`SecParam`] : security strength of the key derivation
     /// ## Returns:
     /// * return  -> [`KeyPair`]: Key object containing owner, private key, public key x and y coordinates, and timestamp.
+    ///
     /// verification key 𝑉 is hashed together with the message 𝑚
     /// and the nonce 𝑈: hash (𝑚, 𝑈, 𝑉) .
-    #[allow(non_snake_case)]
     pub fn new(pw: &[u8], owner: String, d: SecParam) -> KeyPair {
         let data = kmac_xof(pw, &[], 448, "SK", d);
         let s: Scalar = bytes_to_scalar(&data).mul_mod(&Scalar::from(4_u64));
-        let V = ExtendedPoint::generator() * s;
+        Self::from_scalar(s, owner)
+    }
+
+    /// # Seeded [`KeyPair`] Generation
+    /// Generates a key pair from a 57-byte random seed, expanded as an RFC 8032
+    /// Ed448 secret key. The public key 𝑉 therefore encodes to the Ed448 public
+    /// key of the same seed, see [`SigningKey`].
+    /// ## Errors:
+    /// * [`OperationError::InvalidSecretLength`] unless `seed` is 57 bytes long.
+    pub fn from_seed(seed: &[u8], owner: String) -> Result<KeyPair, OperationError> {
+        let signing_key = SigningKey::from_seed(seed)?;
+        Ok(Self::from_scalar(*signing_key.scalar(), owner))
+    }
+
+    /// Generates a key pair from a fresh random seed, see [`KeyPair::from_seed`].
+    pub fn generate(owner: String) -> KeyPair {
+        Self::from_seed(&get_random_bytes(SECRET_KEY_BYTES as u64), owner).unwrap()
+    }
+
+    pub(crate) fn from_scalar(s: Scalar, owner: String) -> KeyPair {
         KeyPair {
             owner,
-            pub_key: V,
-            priv_key: pw.to_vec(),
+            pub_key: ExtendedPoint::generator() * s,
+            priv_key: SecretScalar::new(s),
             date_created: get_date_and_time_as_string(),
         }
     }
 
+    /// The X448 public key belonging to this key pair.
+    pub fn x448_public_key(&self) -> X448PublicKey {
+        X448PublicKey::from_edwards(&self.pub_key)
+    }
+
+    /// # X448 Key Agreement
+    /// Computes the X448 shared secret between this key pair and `their_public`,
+    /// see [`edwards_diffie_hellman`].
+    pub fn x448_diffie_hellman(
+        &self,
+        their_public: &X448PublicKey,
+    ) -> Result<[u8; X448_BYTES], OperationError> {
+        edwards_diffie_hellman(self.priv_key.expose(), their_public)
+    }
+
     /// # KeyPair Saving
     ///
     /// Saves the key pair to a JSON file.
@@ -75,4 +152,38 @@ impl KeyPair {
         let keypair: KeyPair = serde_json::from_str(&contents)?;
         Ok(keypair)
     }
+
+    /// # Encrypted KeyPair Saving
+    ///
+    /// Saves the key pair to a JSON file with the secret scalar encrypted under
+    /// passphrase pw, using the default [`KdfParams`]. See [`EncryptedKeyPair`].
+    ///
+    /// ## Errors:
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save_encrypted(
+        &self,
+        filename: &str,
+        pw: &[u8],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        EncryptedKeyPair::new(self, pw, KdfParams::default())?.write_to_file(filename)?;
+        Ok(())
+    }
+
+    /// # Encrypted KeyPair Loading
+    ///
+    /// Reads a file written by [`KeyPair::save_encrypted`] and decrypts it with
+    /// passphrase pw.
+    ///
+    /// ## Errors:
+    ///
+    /// Returns an error if:
+    /// - The file cannot be read or parsed into an [`EncryptedKeyPair`].
+    /// - [`EncryptedKeyPair::decrypt`] fails, e.g. because the passphrase is wrong.
+    pub fn load_encrypted(
+        filename: &str,
+        pw: &[u8],
+    ) -> Result<KeyPair, Box<dyn std::error::Error>> {
+        Ok(EncryptedKeyPair::read_from_file(filename)?.decrypt(pw)?)
+    }
 }
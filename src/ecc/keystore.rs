@@ -0,0 +1,230 @@
+//! Passphrase-encrypted key files.
+//!
+//! An [`EncryptedKeyPair`](crate::ecc::keystore::EncryptedKeyPair) keeps the owner, creation date, public key and
+//! key-derivation parameters in the clear and seals only the secret scalar,
+//! using the KMAC-based authenticated encryption of
+//! [`SpongeEncryptable`](crate::sha3::encryptable::SpongeEncryptable). The sealing key is derived from the passphrase and
+//! bound to the cleartext fields, so a file whose header was edited no longer
+//! decrypts.
+use super::{
+    edwards448::{encode_point, scalar_from_canonical_bytes, scalar_to_bytes_le},
+    keypair::{KeyPair, SecretScalar},
+};
+use crate::{
+    sha3::{
+        aux_functions::byte_utils::get_random_bytes, encryptable::SpongeEncryptable,
+        shake_functions::kmac_xof,
+    },
+    Message, OperationError, SecParam,
+};
+use serde::{Deserialize, Serialize};
+use std::{fs::File, io::Read};
+use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+use zeroize::Zeroize;
+
+/// Version of the key file format written by this crate.
+pub const KEYSTORE_VERSION: u32 = 1;
+/// Number of KMAC iterations used to stretch a passphrase by default.
+pub const DEFAULT_KDF_ITERATIONS: u32 = 100_000;
+/// Largest iteration count accepted, so that a crafted key file cannot make
+/// decryption run for hours.
+pub const MAX_KDF_ITERATIONS: u32 = 100 * DEFAULT_KDF_ITERATIONS;
+/// Length in bytes of a freshly generated salt.
+pub const SALT_BYTES: usize = 32;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+/// Parameters of the passphrase key derivation, stored in the clear.
+pub struct KdfParams {
+    /// Random salt
+    pub salt: Vec<u8>,
+    /// Number of KMAC iterations
+    pub iterations: u32,
+    /// Security strength of the KMAC instances
+    pub d: SecParam,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+/// A [`KeyPair`] whose secret scalar is sealed under a passphrase.
+pub struct EncryptedKeyPair {
+    /// Key file format version, see [`KEYSTORE_VERSION`]
+    pub version: u32,
+    /// String indicating the owner of the key, can be arbitrary
+    pub owner: String,
+    /// Public encryption key
+    #[serde(with = "super::edwards448::serde_point")]
+    pub pub_key: ExtendedPoint,
+    /// Date key was generated
+    pub date_created: String,
+    /// Passphrase key derivation parameters
+    pub kdf: KdfParams,
+    /// The secret scalar encrypted with [`SpongeEncryptable::sha3_encrypt`]
+    pub sealed_key: Message,
+}
+
+impl KdfParams {
+    /// Parameters with a fresh random salt.
+    pub fn new(iterations: u32, d: SecParam) -> KdfParams {
+        KdfParams {
+            salt: get_random_bytes(SALT_BYTES as u64),
+            iterations,
+            d,
+        }
+    }
+
+    /// # Passphrase Stretching
+    /// Derives a 512-bit key from passphrase pw.
+    /// ## Algorithm:
+    /// * k ← kmac_xof(pw, salt, 512, “KDF”)
+    /// * repeat iterations times: k ← kmac_xof(pw, k, 512, “KDF”)
+    /// ## Errors:
+    /// * [`OperationError::KdfIterationsTooLarge`] if `iterations` exceeds
+    ///   [`MAX_KDF_ITERATIONS`].
+    pub fn derive(&self, pw: &[u8]) -> Result<Vec<u8>, OperationError> {
+        if self.iterations > MAX_KDF_ITERATIONS {
+            return Err(OperationError::KdfIterationsTooLarge);
+        }
+        let mut k = kmac_xof(pw, &self.salt, 512, "KDF", self.d);
+        for _ in 0..self.iterations {
+            let next = kmac_xof(pw, &k, 512, "KDF", self.d);
+            k.zeroize();
+            k = next;
+        }
+        Ok(k)
+    }
+}
+
+impl Default for KdfParams {
+    fn default() -> KdfParams {
+        KdfParams::new(DEFAULT_KDF_ITERATIONS, SecParam::D512)
+    }
+}
+
+impl EncryptedKeyPair {
+    /// # Key Sealing
+    /// Encrypts the secret scalar of `key_pair` under passphrase pw.
+    /// ## Algorithm:
+    /// * k ← derive(pw), see [`KdfParams::derive`]
+    /// * ke ← kmac_xof(k, header, 512, “KS”), where header encodes the version,
+    ///   public key, owner and creation date
+    /// * sealed_key ← sha3_encrypt(s, ke)
+    /// ## Errors:
+    /// * [`OperationError::KdfIterationsTooLarge`] if `kdf` has more than
+    ///   [`MAX_KDF_ITERATIONS`] iterations.
+    pub fn new(
+        key_pair: &KeyPair,
+        pw: &[u8],
+        kdf: KdfParams,
+    ) -> Result<EncryptedKeyPair, OperationError> {
+        let mut sealed = EncryptedKeyPair {
+            version: KEYSTORE_VERSION,
+            owner: key_pair.owner.clone(),
+            pub_key: key_pair.pub_key,
+            date_created: key_pair.date_created.clone(),
+            kdf,
+            sealed_key: Message::new(vec![]),
+        };
+        sealed.seal(key_pair.priv_key.expose(), pw)?;
+        Ok(sealed)
+    }
+
+    /// # Key Unsealing
+    /// Decrypts the key pair with passphrase pw.
+    /// ## Errors:
+    /// * [`OperationError::UnsupportedKeystoreVersion`] if the file was written by
+    ///   an unknown format version.
+    /// * [`OperationError::KdfIterationsTooLarge`] if the file asks for more than
+    ///   [`MAX_KDF_ITERATIONS`] iterations.
+    /// * [`OperationError::SHA3DecryptionFailure`] if the passphrase is wrong or
+    ///   the file was modified.
+    /// * [`OperationError::KeystorePublicKeyMismatch`] if the decrypted scalar does
+    ///   not belong to the stored public key.
+    pub fn decrypt(&self, pw: &[u8]) -> Result<KeyPair, OperationError> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(OperationError::UnsupportedKeystoreVersion);
+        }
+        let mut ke = self.sealing_key(pw)?;
+        let mut sealed_key = self.sealed_key.clone();
+        let res = sealed_key.sha3_decrypt(&ke);
+        ke.zeroize();
+        res?;
+
+        let s = scalar_from_canonical_bytes(&sealed_key.msg);
+        sealed_key.msg.zeroize();
+        let s = SecretScalar::new(s?);
+
+        if ExtendedPoint::generator() * *s.expose() != self.pub_key {
+            return Err(OperationError::KeystorePublicKeyMismatch);
+        }
+        Ok(KeyPair {
+            owner: self.owner.clone(),
+            pub_key: self.pub_key,
+            priv_key: s,
+            date_created: self.date_created.clone(),
+        })
+    }
+
+    /// # Passphrase Change
+    /// Re-encrypts the secret scalar under passphrase `new_pw` with a fresh salt,
+    /// keeping the iteration count and security strength.
+    /// ## Errors:
+    /// * Any error of [`EncryptedKeyPair::decrypt`] under `old_pw`, in which case
+    ///   the key file is left unchanged.
+    pub fn change_passphrase(
+        &mut self,
+        old_pw: &[u8],
+        new_pw: &[u8],
+    ) -> Result<(), OperationError> {
+        let key_pair = self.decrypt(old_pw)?;
+        self.kdf = KdfParams::new(self.kdf.iterations, self.kdf.d);
+        self.seal(key_pair.priv_key.expose(), new_pw)
+    }
+
+    /// # Encrypted KeyPair Saving
+    ///
+    /// Saves the encrypted key pair to a JSON file.
+    pub fn write_to_file(&self, filename: &str) -> std::io::Result<()> {
+        let json_key_pair = serde_json::to_string_pretty(self).unwrap();
+        std::fs::write(filename, json_key_pair)
+    }
+
+    /// # Encrypted KeyPair Loading
+    ///
+    /// Reads a JSON file written by [`EncryptedKeyPair::write_to_file`].
+    ///
+    /// ## Errors:
+    ///
+    /// Returns an error if:
+    /// - The file cannot be opened or read.
+    /// - The JSON content cannot be parsed into an `EncryptedKeyPair`.
+    pub fn read_from_file(filename: &str) -> Result<EncryptedKeyPair, Box<dyn std::error::Error>> {
+        let mut file = File::open(filename)?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+
+        let sealed: EncryptedKeyPair = serde_json::from_str(&contents)?;
+        Ok(sealed)
+    }
+
+    fn seal(&mut self, s: &Scalar, pw: &[u8]) -> Result<(), OperationError> {
+        let mut ke = self.sealing_key(pw)?;
+        let mut sealed_key = Message::new(scalar_to_bytes_le(s).to_vec());
+        sealed_key.sha3_encrypt(&ke, self.kdf.d);
+        ke.zeroize();
+        self.sealed_key = sealed_key;
+        Ok(())
+    }
+
+    // Binds the cleartext fields into the key that seals the secret scalar.
+    fn sealing_key(&self, pw: &[u8]) -> Result<Vec<u8>, OperationError> {
+        let mut header = self.version.to_le_bytes().to_vec();
+        header.extend_from_slice(&encode_point(&self.pub_key));
+        header.extend_from_slice(&(self.owner.len() as u64).to_le_bytes());
+        header.extend_from_slice(self.owner.as_bytes());
+        header.extend_from_slice(self.date_created.as_bytes());
+
+        let mut k = self.kdf.derive(pw)?;
+        let ke = kmac_xof(&k, &header, 512, "KS", self.kdf.d);
+        k.zeroize();
+        Ok(ke)
+    }
+}
@@ -0,0 +1,306 @@
+//! MuSig2 multi-signatures producing a single legacy Schnorr [`Signature`](crate::ecc::signable::Signature).
+//!
+//! A group of signers aggregates its public keys into one key 𝑋 and, in two
+//! rounds, jointly signs a message. The result verifies with
+//! [`Signable::verify`](crate::ecc::signable::Signable::verify) under 𝑋 exactly
+//! like a signature made by a single [`KeyPair`](crate::ecc::keypair::KeyPair):
+//! 1. Key aggregation: every key 𝑉ᵢ is weighted by aᵢ ← H(L, 𝑉ᵢ), where L is the
+//!    whole key list, which prevents rogue-key attacks.
+//! 2. Round one: every signer draws two secret nonces and publishes their
+//!    points (𝑅ᵢ₁, 𝑅ᵢ₂), see
+//!    [`SecretNonce::generate`](crate::ecc::musig::SecretNonce::generate).
+//!    The published points are summed with
+//!    [`PublicNonce::aggregate`](crate::ecc::musig::PublicNonce::aggregate).
+//! 3. Round two: every signer computes a partial signature with
+//!    [`SigningSession::partial_sign`](crate::ecc::musig::SigningSession::partial_sign),
+//!    and the partial signatures are summed with
+//!    [`SigningSession::aggregate`](crate::ecc::musig::SigningSession::aggregate).
+//!
+//! A [`SecretNonce`](crate::ecc::musig::SecretNonce) is consumed by signing and
+//! cannot be copied or serialized. Signing twice with the same secret nonce
+//! reveals the secret key.
+//!
+//! Unlike MuSig2, whose challenge is H(𝑋, 𝑅, m), the challenge here is the
+//! h ← kmac_xof(𝑅ₓ, m, 448, “T”) of [`Signable::sign`](crate::ecc::signable::Signable::sign),
+//! which omits 𝑋, so that aggregated signatures verify with
+//! [`Signable::verify`](crate::ecc::signable::Signable::verify). The nonce
+//! coefficient b still hashes 𝑋, binding every session to the key list.
+use super::{
+    edwards448::{encode_point, scalar_from_bytes_wide, scalar_to_bytes_le},
+    keypair::KeyPair,
+    signable::{challenge, reduce, Signature},
+};
+use crate::{
+    sha3::{aux_functions::byte_utils::get_random_bytes, shake_functions::kmac_xof},
+    OperationError, SecParam,
+};
+use serde::{Deserialize, Serialize};
+use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+use zeroize::Zeroize;
+
+// Hashes to scalars are 114 bytes long and reduced mod r.
+const WIDE_BITS: usize = 912;
+
+/// The aggregated public key of a list of signers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyAggContext {
+    keys: Vec<ExtendedPoint>,
+    coefficients: Vec<Scalar>,
+    agg_key: ExtendedPoint,
+    d: SecParam,
+}
+
+/// The secret nonces (k₁, k₂) of one signer for one signing session.
+pub struct SecretNonce {
+    k1: Scalar,
+    k2: Scalar,
+    pub_key: ExtendedPoint,
+}
+
+/// The public nonce points (𝑅₁, 𝑅₂) = (k₁*𝑮, k₂*𝑮) of one signer, or the sum
+/// of all signers' public nonces.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct PublicNonce {
+    /// First nonce point
+    #[serde(with = "super::edwards448::serde_point")]
+    pub r1: ExtendedPoint,
+    /// Second nonce point
+    #[serde(with = "super::edwards448::serde_point")]
+    pub r2: ExtendedPoint,
+}
+
+/// The contribution 𝑍ᵢ of one signer to the aggregated signature.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct PartialSignature {
+    /// Partial response 𝑍ᵢ
+    #[serde(with = "super::edwards448::serde_scalar")]
+    pub z: Scalar,
+}
+
+/// The state shared by all signers of one message once the nonces are known.
+#[derive(Debug, Clone)]
+pub struct SigningSession<'a> {
+    ctx: &'a KeyAggContext,
+    b: Scalar,
+    r: ExtendedPoint,
+    h: Vec<u8>,
+    c: Scalar,
+}
+
+impl KeyAggContext {
+    /// # Key Aggregation
+    /// Aggregates the public keys of all signers. The keys are sorted by their
+    /// encoding first, so the aggregated key does not depend on their order.
+    /// ## Algorithm:
+    /// * L ← encodings of 𝑉₁, …, 𝑉ₙ in ascending order
+    /// * aᵢ ← kmac_xof(L, 𝑉ᵢ, 912, “KAGG”) mod r
+    /// * 𝑋 ← Σ aᵢ*𝑉ᵢ
+    /// ## Arguments:
+    /// * keys: &[ExtendedPoint] : public keys of all signers, each at most once
+    /// * d: [`SecParam`] : security strength of the signature, as for [`Signable::sign`](crate::ecc::signable::Signable::sign)
+    /// ## Errors:
+    /// * [`OperationError::InvalidKeyList`] if `keys` is empty, contains a key
+    ///   more than once, or 𝑋 is the identity point.
+    pub fn new(keys: &[ExtendedPoint], d: SecParam) -> Result<KeyAggContext, OperationError> {
+        let mut keys = keys.to_vec();
+        keys.sort_by_cached_key(encode_point);
+        // A repeated key would be aggregated twice but could only sign once
+        if keys.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(OperationError::InvalidKeyList);
+        }
+        let list: Vec<u8> = keys.iter().flat_map(encode_point).collect();
+
+        let coefficients: Vec<Scalar> = keys
+            .iter()
+            .map(|key| {
+                scalar_from_bytes_wide(&kmac_xof(&list, &encode_point(key), WIDE_BITS, "KAGG", d))
+            })
+            .collect();
+        let agg_key = keys
+            .iter()
+            .zip(&coefficients)
+            .fold(ExtendedPoint::id_point(), |acc, (key, a)| acc + *key * *a);
+
+        if keys.is_empty() || agg_key == ExtendedPoint::id_point() {
+            return Err(OperationError::InvalidKeyList);
+        }
+        Ok(KeyAggContext {
+            keys,
+            coefficients,
+            agg_key,
+            d,
+        })
+    }
+
+    /// The aggregated public key 𝑋 the final signature verifies under.
+    pub fn aggregated_key(&self) -> ExtendedPoint {
+        self.agg_key
+    }
+
+    /// # Signing Session
+    /// Starts the second round for message m once the public nonces of all
+    /// signers have been summed into `agg_nonce`.
+    /// ## Algorithm:
+    /// * b ← kmac_xof(𝑋, 𝑅₁ || 𝑅₂ || m, 912, “NCOEF”) mod r
+    /// * 𝑅 ← 𝑅₁ + b*𝑅₂
+    /// * h ← kmac_xof(𝑅ₓ, m, 448, “T”), as in [`Signable::sign`](crate::ecc::signable::Signable::sign).
+    ///   Unlike MuSig2, h does not hash 𝑋, see the [module documentation](crate::ecc::musig).
+    pub fn session(&self, agg_nonce: &PublicNonce, msg: &[u8]) -> SigningSession<'_> {
+        let mut data = encode_point(&agg_nonce.r1).to_vec();
+        data.extend_from_slice(&encode_point(&agg_nonce.r2));
+        data.extend_from_slice(msg);
+        let b = scalar_from_bytes_wide(&kmac_xof(
+            &encode_point(&self.agg_key),
+            &data,
+            WIDE_BITS,
+            "NCOEF",
+            self.d,
+        ));
+
+        let r = agg_nonce.r1 + agg_nonce.r2 * b;
+        let h = challenge(&r, msg, self.d);
+        let c = reduce(&h);
+        SigningSession {
+            ctx: self,
+            b,
+            r,
+            h,
+            c,
+        }
+    }
+
+    fn coefficient(&self, pub_key: &ExtendedPoint) -> Result<Scalar, OperationError> {
+        self.keys
+            .iter()
+            .position(|key| key == pub_key)
+            .map(|i| self.coefficients[i])
+            .ok_or(OperationError::SignerNotInKeyList)
+    }
+}
+
+impl SecretNonce {
+    /// # Nonce Generation
+    /// Draws the secret nonces of `key` for one signing session and returns
+    /// them with the public nonce to send to the other signers. Draw fresh
+    /// nonces for every session.
+    /// ## Algorithm:
+    /// * t ← Random(512)
+    /// * kⱼ ← kmac_xof(t, s || 𝑉, 912, “MNj”) mod r for j = 1, 2
+    /// * (𝑅₁, 𝑅₂) ← (k₁*𝑮, k₂*𝑮)
+    pub fn generate(key: &KeyPair) -> (SecretNonce, PublicNonce) {
+        let mut t = get_random_bytes(64);
+        let mut data = scalar_to_bytes_le(key.priv_key.expose()).to_vec();
+        data.extend_from_slice(&encode_point(&key.pub_key));
+
+        let mut k1_bytes = kmac_xof(&t, &data, WIDE_BITS, "MN1", SecParam::D512);
+        let mut k2_bytes = kmac_xof(&t, &data, WIDE_BITS, "MN2", SecParam::D512);
+        let secret = SecretNonce {
+            k1: scalar_from_bytes_wide(&k1_bytes),
+            k2: scalar_from_bytes_wide(&k2_bytes),
+            pub_key: key.pub_key,
+        };
+        t.zeroize();
+        data.zeroize();
+        k1_bytes.zeroize();
+        k2_bytes.zeroize();
+
+        let public = PublicNonce {
+            r1: ExtendedPoint::generator() * secret.k1,
+            r2: ExtendedPoint::generator() * secret.k2,
+        };
+        (secret, public)
+    }
+}
+
+impl Drop for SecretNonce {
+    fn drop(&mut self) {
+        self.k1.val.zeroize();
+        self.k2.val.zeroize();
+    }
+}
+
+impl std::fmt::Debug for SecretNonce {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SecretNonce(..)")
+    }
+}
+
+impl PublicNonce {
+    /// # Nonce Aggregation
+    /// Sums the public nonces of all signers: (Σ 𝑅ᵢ₁, Σ 𝑅ᵢ₂).
+    pub fn aggregate(nonces: &[PublicNonce]) -> PublicNonce {
+        nonces.iter().fold(
+            PublicNonce {
+                r1: ExtendedPoint::id_point(),
+                r2: ExtendedPoint::id_point(),
+            },
+            |acc, nonce| PublicNonce {
+                r1: acc.r1 + nonce.r1,
+                r2: acc.r2 + nonce.r2,
+            },
+        )
+    }
+}
+
+impl SigningSession<'_> {
+    /// # Partial Signing
+    /// Computes the partial signature of `key`, consuming its secret nonce.
+    /// ## Algorithm:
+    /// * 𝑍ᵢ ← (k₁ + b·k₂ – h·aᵢ·s) mod r
+    /// ## Errors:
+    /// * [`OperationError::NonceKeyMismatch`] if `nonce` was generated for another key.
+    /// * [`OperationError::SignerNotInKeyList`] if `key` was not aggregated.
+    pub fn partial_sign(
+        &self,
+        key: &KeyPair,
+        nonce: SecretNonce,
+    ) -> Result<PartialSignature, OperationError> {
+        if nonce.pub_key != key.pub_key {
+            return Err(OperationError::NonceKeyMismatch);
+        }
+        let a = self.ctx.coefficient(&key.pub_key)?;
+        let k = nonce.k1.add_mod(&self.b.mul_mod(&nonce.k2));
+        let z = k - self.c.mul_mod(&a).mul_mod(key.priv_key.expose());
+        Ok(PartialSignature { z })
+    }
+
+    /// # Partial Signature Verification
+    /// Checks the partial signature of the signer with public key 𝑉ᵢ and public
+    /// nonce (𝑅ᵢ₁, 𝑅ᵢ₂), to find a signer who spoiled the aggregated signature.
+    /// ## Algorithm:
+    /// * accept iff 𝑍ᵢ*𝑮 + (h·aᵢ)*𝑉ᵢ = 𝑅ᵢ₁ + b*𝑅ᵢ₂
+    /// ## Errors:
+    /// * [`OperationError::SignerNotInKeyList`] if `pub_key` was not aggregated.
+    /// * [`OperationError::SignatureVerificationFailure`] if the check fails.
+    pub fn partial_verify(
+        &self,
+        partial_sig: &PartialSignature,
+        nonce: &PublicNonce,
+        pub_key: &ExtendedPoint,
+    ) -> Result<(), OperationError> {
+        let a = self.ctx.coefficient(pub_key)?;
+        let lhs = ExtendedPoint::generator() * partial_sig.z + *pub_key * self.c.mul_mod(&a);
+        if lhs == nonce.r1 + nonce.r2 * self.b {
+            Ok(())
+        } else {
+            Err(OperationError::SignatureVerificationFailure)
+        }
+    }
+
+    /// # Signature Aggregation
+    /// Sums the partial signatures of all signers into a [`Signature`] (h, 𝑍)
+    /// under the aggregated key. Attach it to the signed [`Message`](crate::Message)
+    /// together with the [`SecParam`] of the [`KeyAggContext`] to verify it.
+    /// ## Algorithm:
+    /// * 𝑍 ← Σ 𝑍ᵢ mod r
+    pub fn aggregate(&self, partial_sigs: &[PartialSignature]) -> Signature {
+        let z = partial_sigs
+            .iter()
+            .fold(Scalar::from(0_u64), |acc, sig| acc.add_mod(&sig.z));
+        Signature {
+            h: self.h.clone(),
+            z,
+            u: Some(self.r),
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! Non-interactive zero-knowledge proofs about discrete logarithms.
+//!
+//! * [`DlogProof`](crate::ecc::nizk::DlogProof) is a Schnorr proof of knowledge
+//!   of x with 𝑉 = x*𝑮, such as the secret of a
+//!   [`KeyPair`](crate::ecc::keypair::KeyPair), without signing anything.
+//! * [`DleqProof`](crate::ecc::nizk::DleqProof) is a Chaum–Pedersen proof that
+//!   two points share the same discrete logarithm to two bases, 𝐴 = x*𝐺₁ and
+//!   𝐵 = x*𝐺₂, without revealing x.
+//!
+//! Both are made non-interactive with the Fiat–Shamir transform: the challenge
+//! is a KMAC256 hash keyed with a caller-chosen context over every point of the
+//! statement and of the prover's commitment. A proof only verifies under the
+//! context it was made for, so bind the context to the session, the verifier
+//! or the purpose of the proof to keep proofs from being replayed elsewhere.
+use super::edwards448::{
+    encode_point, multiscalar_mul, scalar_from_bytes_wide, scalar_from_canonical_bytes,
+    scalar_negate, scalar_to_bytes_le, SCALAR_BYTES,
+};
+use crate::{
+    sha3::{aux_functions::byte_utils::get_random_bytes, shake_functions::kmac_xof},
+    OperationError, SecParam,
+};
+use serde::{Deserialize, Serialize};
+use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+use zeroize::Zeroize;
+
+// Hashes to scalars are 114 bytes long and reduced mod r.
+const WIDE_BITS: usize = 912;
+
+/// Length in bytes of an encoded proof c || s.
+pub const PROOF_BYTES: usize = 2 * SCALAR_BYTES;
+
+/// A Schnorr proof of knowledge of the discrete logarithm of a point to 𝑮.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct DlogProof {
+    /// Fiat–Shamir challenge c
+    #[serde(with = "super::edwards448::serde_scalar")]
+    pub c: Scalar,
+    /// Response s = k + c*x
+    #[serde(with = "super::edwards448::serde_scalar")]
+    pub s: Scalar,
+}
+
+/// A Chaum–Pedersen proof that two points have the same discrete logarithm to
+/// two bases.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct DleqProof {
+    /// Fiat–Shamir challenge c
+    #[serde(with = "super::edwards448::serde_scalar")]
+    pub c: Scalar,
+    /// Response s = k + c*x
+    #[serde(with = "super::edwards448::serde_scalar")]
+    pub s: Scalar,
+}
+
+impl DlogProof {
+    /// # Proof of Knowledge of a Discrete Logarithm
+    /// Proves knowledge of x for the public point 𝑉 = x*𝑮. To prove
+    /// possession of a key pair, pass `key_pair.priv_key.expose()`.
+    /// ## Algorithm:
+    /// * k ← kmac_xof(Random(512), x || 𝑉, 912, “NZK”) mod r
+    /// * 𝑅 ← k*𝑮
+    /// * c ← kmac_xof(context, 𝑉 || 𝑅, 912, “DLOG”) mod r
+    /// * s ← (k + c*x) mod r
+    /// ## Arguments:
+    /// * x: &Scalar : the secret discrete logarithm
+    /// * context: &[u8] : binds the proof to its purpose, may be empty
+    pub fn prove(x: &Scalar, context: &[u8]) -> DlogProof {
+        let public = ExtendedPoint::generator() * *x;
+        let k = nonce(x, &[public]);
+        let commitment = ExtendedPoint::generator() * k;
+        let c = challenge(context, "DLOG", &[public, commitment]);
+        DlogProof {
+            c,
+            s: k.add_mod(&c.mul_mod(x)),
+        }
+    }
+
+    /// # Proof Verification
+    /// Checks that the prover knows the discrete logarithm of `public` to 𝑮.
+    /// ## Algorithm:
+    /// * 𝑅 ← s*𝑮 - c*𝑉
+    /// * accept iff c = kmac_xof(context, 𝑉 || 𝑅, 912, “DLOG”) mod r
+    /// ## Errors:
+    /// * [`OperationError::ProofVerificationFailure`] if the proof does not verify.
+    pub fn verify(&self, public: &ExtendedPoint, context: &[u8]) -> Result<(), OperationError> {
+        let commitment = multiscalar_mul(
+            &[self.s, scalar_negate(&self.c)],
+            &[ExtendedPoint::generator(), *public],
+        );
+        if challenge(context, "DLOG", &[*public, commitment]) == self.c {
+            Ok(())
+        } else {
+            Err(OperationError::ProofVerificationFailure)
+        }
+    }
+
+    /// Decodes the 114-byte encoding c || s of a proof.
+    /// ## Errors:
+    /// * [`OperationError::InvalidProofLength`] if `bytes` is not 114 bytes long.
+    /// * [`OperationError::InvalidScalarEncoding`] unless c and s are reduced modulo r.
+    pub fn from_bytes(bytes: &[u8]) -> Result<DlogProof, OperationError> {
+        let (c, s) = decode_scalars(bytes)?;
+        Ok(DlogProof { c, s })
+    }
+
+    /// Encodes the proof as c || s in 114 bytes.
+    pub fn to_bytes(&self) -> [u8; PROOF_BYTES] {
+        encode_scalars(&self.c, &self.s)
+    }
+}
+
+impl DleqProof {
+    /// # Proof of Discrete Logarithm Equality
+    /// Proves that 𝐴 = x*𝐺₁ and 𝐵 = x*𝐺₂ for the same secret x.
+    /// ## Algorithm:
+    /// * k ← kmac_xof(Random(512), x || 𝐺₁ || 𝐺₂, 912, “NZK”) mod r
+    /// * 𝑅₁ ← k*𝐺₁, 𝑅₂ ← k*𝐺₂
+    /// * c ← kmac_xof(context, 𝐺₁ || 𝐴 || 𝐺₂ || 𝐵 || 𝑅₁ || 𝑅₂, 912, “DLEQ”) mod r
+    /// * s ← (k + c*x) mod r
+    /// ## Arguments:
+    /// * x: &Scalar : the secret discrete logarithm
+    /// * base1: &ExtendedPoint : the base 𝐺₁
+    /// * base2: &ExtendedPoint : the base 𝐺₂
+    /// * context: &[u8] : binds the proof to its purpose, may be empty
+    pub fn prove(
+        x: &Scalar,
+        base1: &ExtendedPoint,
+        base2: &ExtendedPoint,
+        context: &[u8],
+    ) -> DleqProof {
+        let (public1, public2) = (*base1 * *x, *base2 * *x);
+        let k = nonce(x, &[*base1, *base2]);
+        let c = challenge(
+            context,
+            "DLEQ",
+            &[*base1, public1, *base2, public2, *base1 * k, *base2 * k],
+        );
+        DleqProof {
+            c,
+            s: k.add_mod(&c.mul_mod(x)),
+        }
+    }
+
+    /// # Proof Verification
+    /// Checks that `public1` and `public2` have the same discrete logarithm
+    /// to `base1` and `base2` respectively.
+    /// ## Algorithm:
+    /// * 𝑅₁ ← s*𝐺₁ - c*𝐴, 𝑅₂ ← s*𝐺₂ - c*𝐵
+    /// * accept iff c = kmac_xof(context, 𝐺₁ || 𝐴 || 𝐺₂ || 𝐵 || 𝑅₁ || 𝑅₂, 912, “DLEQ”) mod r
+    /// ## Errors:
+    /// * [`OperationError::ProofVerificationFailure`] if the proof does not verify.
+    pub fn verify(
+        &self,
+        base1: &ExtendedPoint,
+        public1: &ExtendedPoint,
+        base2: &ExtendedPoint,
+        public2: &ExtendedPoint,
+        context: &[u8],
+    ) -> Result<(), OperationError> {
+        let neg_c = scalar_negate(&self.c);
+        let r1 = multiscalar_mul(&[self.s, neg_c], &[*base1, *public1]);
+        let r2 = multiscalar_mul(&[self.s, neg_c], &[*base2, *public2]);
+        let points = [*base1, *public1, *base2, *public2, r1, r2];
+        if challenge(context, "DLEQ", &points) == self.c {
+            Ok(())
+        } else {
+            Err(OperationError::ProofVerificationFailure)
+        }
+    }
+
+    /// Decodes the 114-byte encoding c || s of a proof.
+    /// ## Errors:
+    /// * [`OperationError::InvalidProofLength`] if `bytes` is not 114 bytes long.
+    /// * [`OperationError::InvalidScalarEncoding`] unless c and s are reduced modulo r.
+    pub fn from_bytes(bytes: &[u8]) -> Result<DleqProof, OperationError> {
+        let (c, s) = decode_scalars(bytes)?;
+        Ok(DleqProof { c, s })
+    }
+
+    /// Encodes the proof as c || s in 114 bytes.
+    pub fn to_bytes(&self) -> [u8; PROOF_BYTES] {
+        encode_scalars(&self.c, &self.s)
+    }
+}
+
+/// k ← kmac_xof(Random(512), x || P₁ || … || Pₙ, 912, “NZK”) mod r. Mixing in
+/// fresh randomness keeps k secret even if the same statement is proven twice.
+fn nonce(x: &Scalar, points: &[ExtendedPoint]) -> Scalar {
+    let mut t = get_random_bytes(64);
+    let mut data = scalar_to_bytes_le(x).to_vec();
+    for p in points {
+        data.extend_from_slice(&encode_point(p));
+    }
+    let mut k_bytes = kmac_xof(&t, &data, WIDE_BITS, "NZK", SecParam::D512);
+    let k = scalar_from_bytes_wide(&k_bytes);
+    t.zeroize();
+    data.zeroize();
+    k_bytes.zeroize();
+    k
+}
+
+/// c ← kmac_xof(context, P₁ || … || Pₙ, 912, s) mod r
+fn challenge(context: &[u8], s: &str, points: &[ExtendedPoint]) -> Scalar {
+    let transcript: Vec<u8> = points.iter().flat_map(encode_point).collect();
+    scalar_from_bytes_wide(&kmac_xof(
+        context,
+        &transcript,
+        WIDE_BITS,
+        s,
+        SecParam::D512,
+    ))
+}
+
+fn decode_scalars(bytes: &[u8]) -> Result<(Scalar, Scalar), OperationError> {
+    if bytes.len() != PROOF_BYTES {
+        return Err(OperationError::InvalidProofLength);
+    }
+    let (c, s) = bytes.split_at(SCALAR_BYTES);
+    Ok((
+        scalar_from_canonical_bytes(c)?,
+        scalar_from_canonical_bytes(s)?,
+    ))
+}
+
+fn encode_scalars(c: &Scalar, s: &Scalar) -> [u8; PROOF_BYTES] {
+    let mut out = [0u8; PROOF_BYTES];
+    out[..SCALAR_BYTES].copy_from_slice(&scalar_to_bytes_le(c));
+    out[SCALAR_BYTES..].copy_from_slice(&scalar_to_bytes_le(s));
+    out
+}
@@ -0,0 +1,256 @@
+//! Oblivious pseudorandom functions following RFC 9497.
+//!
+//! A server holding a key k and a client holding an input x jointly compute
+//! F(k, x) = H(x, k*HashToGroup(x)): the client learns F(k, x) but not k,
+//! and the server learns nothing about x. In a password breach check, the
+//! server publishes F(k, p) for every breached password p and clients test
+//! their own password without revealing it.
+//!
+//! The protocol runs in three steps:
+//! 1. The client blinds its input with
+//!    [`OprfClient::blind`](crate::ecc::oprf::OprfClient::blind).
+//! 2. The server evaluates the blinded element with
+//!    [`OprfServer::blind_evaluate`](crate::ecc::oprf::OprfServer::blind_evaluate).
+//! 3. The client unblinds and hashes the result with
+//!    [`OprfClient::finalize`](crate::ecc::oprf::OprfClient::finalize).
+//!
+//! In the verifiable [`Mode::Voprf`](crate::ecc::oprf::Mode::Voprf), the server
+//! attaches a Chaum–Pedersen [`DleqProof`](crate::ecc::nizk::DleqProof) that it
+//! used the key behind its public key, so it cannot single out clients by
+//! evaluating with different keys.
+//!
+//! RFC 9497 does not define an edwards448 suite. This module uses the
+//! identifier `edwards448-SHAKE256` with
+//! [`hash_to_curve`](crate::ecc::hash_to_curve::hash_to_curve) as HashToGroup
+//! and SHAKE256 with 64 bytes of output as the hash, in place of the
+//! decaf448 group of the OPRF(decaf448, SHAKE-256) suite.
+use super::{
+    edwards448::{encode_point, random_scalar, scalar_invert, POINT_BYTES},
+    hash_to_curve::hash_to_curve,
+    keypair::{KeyPair, SecretScalar},
+    nizk::DleqProof,
+};
+use crate::{sha3::shake_functions::shake256, OperationError};
+use serde::{Deserialize, Serialize};
+use tiny_ed448_goldilocks::curve::extended_edwards::ExtendedPoint;
+use zeroize::Zeroize;
+
+/// Identifies the group and hash function in the context string.
+const IDENTIFIER: &[u8] = b"edwards448-SHAKE256";
+/// Length in bytes of a PRF output.
+pub const OUTPUT_BYTES: usize = 64;
+
+/// The protocol variant, which is bound into every hash.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    /// Base mode, the client cannot check which key the server used
+    Oprf,
+    /// Verifiable mode, every evaluation carries a proof under the server's public key
+    Voprf,
+}
+
+/// The server of the protocol, holding the PRF key.
+pub struct OprfServer {
+    mode: Mode,
+    key_pair: KeyPair,
+}
+
+/// The client of the protocol.
+#[derive(Debug, Clone)]
+pub struct OprfClient {
+    mode: Mode,
+    server_key: Option<ExtendedPoint>,
+}
+
+/// The client's secret state between blinding and finalizing. It holds the
+/// input in the clear and is zeroized when dropped.
+pub struct BlindState {
+    input: Vec<u8>,
+    blind: SecretScalar,
+    blinded_element: ExtendedPoint,
+}
+
+/// The blinded input r*HashToGroup(x) sent from the client to the server.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct BlindedElement(#[serde(with = "super::edwards448::serde_point")] pub ExtendedPoint);
+
+/// The server's evaluation k*r*HashToGroup(x) of a blinded element, with
+/// a proof in verifiable mode.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Evaluation {
+    /// The evaluated element
+    #[serde(with = "super::edwards448::serde_point")]
+    pub element: ExtendedPoint,
+    /// Proof that log_𝑮(pkS) = log_blinded(element), present in [`Mode::Voprf`]
+    pub proof: Option<DleqProof>,
+}
+
+impl Mode {
+    /// contextString = "OPRFV1-" || mode || "-" || identifier, RFC 9497 3.1
+    fn context_string(&self) -> Vec<u8> {
+        let mode = match self {
+            Mode::Oprf => 0x00,
+            Mode::Voprf => 0x01,
+        };
+        [b"OPRFV1-".as_slice(), &[mode], b"-", IDENTIFIER].concat()
+    }
+
+    // Inputs are length-prefixed with two bytes in Finalize and must not hash
+    // to the identity, RFC 9497 3.3.1.
+    fn hash_to_group(&self, input: &[u8]) -> Result<ExtendedPoint, OperationError> {
+        if input.len() > u16::MAX as usize {
+            return Err(OperationError::InvalidOprfInput);
+        }
+        let dst = [b"HashToGroup-".as_slice(), &self.context_string()].concat();
+        let element = hash_to_curve(input, &dst);
+        if element == ExtendedPoint::id_point() {
+            return Err(OperationError::InvalidOprfInput);
+        }
+        Ok(element)
+    }
+}
+
+impl OprfServer {
+    /// Creates a server that evaluates the PRF under the secret key of `key_pair`.
+    pub fn new(mode: Mode, key_pair: KeyPair) -> OprfServer {
+        OprfServer { mode, key_pair }
+    }
+
+    /// The public key pkS = k*𝑮 clients verify evaluations against.
+    pub fn public_key(&self) -> ExtendedPoint {
+        self.key_pair.pub_key
+    }
+
+    /// # Blind Evaluation
+    /// Evaluates the PRF on a blinded element, RFC 9497 3.3.1 and 3.3.2.
+    /// ## Algorithm:
+    /// * Z ← k*blindedElement
+    /// * in verifiable mode: π ← DLEQ proof of log_𝑮(pkS) = log_blindedElement(Z),
+    ///   with contextString as proof context
+    /// ## Errors:
+    /// * [`OperationError::InvalidPointEncoding`] if the blinded element is the identity.
+    pub fn blind_evaluate(&self, blinded: &BlindedElement) -> Result<Evaluation, OperationError> {
+        if blinded.0 == ExtendedPoint::id_point() {
+            return Err(OperationError::InvalidPointEncoding);
+        }
+        let k = self.key_pair.priv_key.expose();
+        let element = blinded.0 * *k;
+        let proof = match self.mode {
+            Mode::Oprf => None,
+            Mode::Voprf => Some(DleqProof::prove(
+                k,
+                &ExtendedPoint::generator(),
+                &blinded.0,
+                &self.mode.context_string(),
+            )),
+        };
+        Ok(Evaluation { element, proof })
+    }
+
+    /// # Direct Evaluation
+    /// Computes F(k, x) for an input the server knows, e.g. to publish the
+    /// outputs of breached passwords, RFC 9497 3.3.1.
+    /// ## Errors:
+    /// * [`OperationError::InvalidOprfInput`] if the input is longer than 65535
+    ///   bytes or hashes to the identity.
+    pub fn evaluate(&self, input: &[u8]) -> Result<[u8; OUTPUT_BYTES], OperationError> {
+        let element = self.mode.hash_to_group(input)?;
+        Ok(finalize_hash(
+            input,
+            &(element * *self.key_pair.priv_key.expose()),
+        ))
+    }
+}
+
+impl OprfClient {
+    /// A client of the base mode, which cannot verify evaluations.
+    pub fn oprf() -> OprfClient {
+        OprfClient {
+            mode: Mode::Oprf,
+            server_key: None,
+        }
+    }
+
+    /// A client of the verifiable mode, which accepts only evaluations
+    /// proven under `server_key`.
+    pub fn voprf(server_key: &ExtendedPoint) -> OprfClient {
+        OprfClient {
+            mode: Mode::Voprf,
+            server_key: Some(*server_key),
+        }
+    }
+
+    /// # Blinding
+    /// Blinds `input` for evaluation by the server, RFC 9497 3.3.1.
+    /// ## Algorithm:
+    /// * r ← Random(912) mod r
+    /// * blindedElement ← r*HashToGroup(x)
+    /// ## Returns:
+    /// * return -> Result<([`BlindState`], [`BlindedElement`]), OperationError>:
+    ///   the state to keep for [`OprfClient::finalize`] and the element to send.
+    /// ## Errors:
+    /// * [`OperationError::InvalidOprfInput`] if the input is longer than 65535
+    ///   bytes or hashes to the identity.
+    pub fn blind(&self, input: &[u8]) -> Result<(BlindState, BlindedElement), OperationError> {
+        let element = self.mode.hash_to_group(input)?;
+        let blind = SecretScalar::new(random_scalar());
+        let blinded_element = element * *blind.expose();
+        let state = BlindState {
+            input: input.to_vec(),
+            blind,
+            blinded_element,
+        };
+        Ok((state, BlindedElement(blinded_element)))
+    }
+
+    /// # Finalization
+    /// Unblinds the server's evaluation and derives F(k, x), RFC 9497 3.3.1
+    /// and 3.3.2.
+    /// ## Algorithm:
+    /// * in verifiable mode: verify π for (𝑮, pkS, blindedElement, Z)
+    /// * N ← r⁻¹*Z
+    /// * output ← SHAKE256(len(x) || x || len(N) || N || "Finalize", 64), with
+    ///   lengths as two big-endian bytes
+    /// ## Errors:
+    /// * [`OperationError::ProofVerificationFailure`] in verifiable mode if the
+    ///   proof is missing or does not verify.
+    pub fn finalize(
+        &self,
+        state: BlindState,
+        evaluation: &Evaluation,
+    ) -> Result<[u8; OUTPUT_BYTES], OperationError> {
+        if let Some(server_key) = &self.server_key {
+            evaluation
+                .proof
+                .ok_or(OperationError::ProofVerificationFailure)?
+                .verify(
+                    &ExtendedPoint::generator(),
+                    server_key,
+                    &state.blinded_element,
+                    &evaluation.element,
+                    &self.mode.context_string(),
+                )?;
+        }
+        // the blind is nonzero except with negligible probability
+        let unblind = scalar_invert(state.blind.expose()).unwrap();
+        Ok(finalize_hash(&state.input, &(evaluation.element * unblind)))
+    }
+}
+
+impl Drop for BlindState {
+    fn drop(&mut self) {
+        self.input.zeroize();
+    }
+}
+
+/// SHAKE256(len(x) || x || len(N) || N || "Finalize", 64)
+fn finalize_hash(input: &[u8], unblinded: &ExtendedPoint) -> [u8; OUTPUT_BYTES] {
+    let mut data = (input.len() as u16).to_be_bytes().to_vec();
+    data.extend_from_slice(input);
+    data.extend_from_slice(&(POINT_BYTES as u16).to_be_bytes());
+    data.extend_from_slice(&encode_point(unblinded));
+    data.extend_from_slice(b"Finalize");
+    let out = shake256(&data, 8 * OUTPUT_BYTES).try_into().unwrap();
+    data.zeroize();
+    out
+}
@@ -0,0 +1,262 @@
+//! The CPace balanced password-authenticated key exchange over Ed448.
+//!
+//! Two parties that share only a low-entropy password, such as a short pairing
+//! code, agree on a strong session key. An eavesdropper learns nothing about
+//! the password, and an active attacker can test a single password guess per
+//! session, so short codes stay safe as long as failed attempts are limited.
+//!
+//! Both parties derive a secret generator 𝑮ₚ from the password with
+//! [`hash_to_curve`](crate::ecc::hash_to_curve::hash_to_curve) and run a
+//! Diffie–Hellman exchange over it in a single round:
+//! 1. Each party calls [`CPace::start`](crate::ecc::pake::CPace::start) and sends
+//!    the returned [`CPaceMessage`](crate::ecc::pake::CPaceMessage). The
+//!    messages may cross on the wire.
+//! 2. Each party passes the peer's message to
+//!    [`CPace::finish`](crate::ecc::pake::CPace::finish) and obtains a
+//!    [`SessionKey`](crate::ecc::pake::SessionKey).
+//!
+//! The state machines only consume and produce messages, so they run over any
+//! transport. Mismatched passwords produce unrelated session keys rather than
+//! an error. Exchange
+//! [`SessionKey::confirmation`](crate::ecc::pake::SessionKey::confirmation) tags
+//! to detect this before using the key.
+//!
+//! The generator string and the transcript are hashed with TupleHash256, so the
+//! construction follows the structure of draft-irtf-cfrg-cpace but does not
+//! interoperate with its test vectors.
+use super::{
+    edwards448::{decode_point_checked, encode_point, random_scalar, POINT_BYTES},
+    hash_to_curve::hash_to_curve,
+    keypair::SecretScalar,
+};
+use crate::{
+    aes::aes_functions::AesKeySize,
+    sha3::shake_functions::{kmac_xof, tuple_hash},
+    OperationError, SecParam,
+};
+use crypto_bigint::subtle::ConstantTimeEq;
+use serde::{Deserialize, Serialize};
+use tiny_ed448_goldilocks::curve::extended_edwards::ExtendedPoint;
+use zeroize::Zeroize;
+
+/// Domain separation tag of the password-derived generator.
+const GENERATOR_DST: &[u8] = b"CPace-edwards448-SHAKE256";
+/// Length in bytes of session keys and confirmation tags.
+pub const SESSION_KEY_BYTES: usize = 64;
+
+/// The position of a party in the transcript. The two parties of a session
+/// must take different roles, e.g. the device that shows the pairing code is
+/// the initiator and the device it is typed into the responder.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Role {
+    /// Its message comes first in the transcript
+    Initiator,
+    /// Its message comes second in the transcript
+    Responder,
+}
+
+/// The single message a party sends, 𝑌 = y*𝑮ₚ with optional public
+/// associated data such as a device name.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CPaceMessage {
+    /// The party's share 𝑌 = y*𝑮ₚ
+    #[serde(with = "super::edwards448::serde_point")]
+    pub point: ExtendedPoint,
+    /// Public data bound into the session key
+    pub associated_data: Vec<u8>,
+}
+
+/// A party waiting for its peer's message. It holds the ephemeral secret y,
+/// which is zeroized when the state is finished or dropped.
+pub struct CPace {
+    role: Role,
+    sid: Vec<u8>,
+    secret: SecretScalar,
+    message: CPaceMessage,
+}
+
+/// The key established by a CPace session, from which keys for the crate's
+/// symmetric ciphers are derived. It is zeroized when dropped.
+pub struct SessionKey {
+    role: Role,
+    isk: [u8; SESSION_KEY_BYTES],
+}
+
+impl CPaceMessage {
+    /// Encodes the message as 𝑌 || associated data.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = encode_point(&self.point).to_vec();
+        out.extend_from_slice(&self.associated_data);
+        out
+    }
+
+    /// Decodes a message encoded with [`CPaceMessage::to_bytes`].
+    /// ## Errors:
+    /// * [`OperationError::InvalidPointEncoding`] unless the message starts
+    ///   with the encoding of a point of the prime-order subgroup other than 𝒪.
+    pub fn from_bytes(bytes: &[u8]) -> Result<CPaceMessage, OperationError> {
+        if bytes.len() < POINT_BYTES {
+            return Err(OperationError::InvalidPointEncoding);
+        }
+        let (point, associated_data) = bytes.split_at(POINT_BYTES);
+        Ok(CPaceMessage {
+            point: decode_point_checked(point)?,
+            associated_data: associated_data.to_vec(),
+        })
+    }
+}
+
+impl CPace {
+    /// # Session Start
+    /// Starts a session of `role` and returns the message to send to the peer.
+    /// ## Algorithm:
+    /// * 𝑮ₚ ← hash_to_curve(TupleHash256((password, channel_id, sid), 512, “CPace generator”),
+    ///   “CPace-edwards448-SHAKE256”)
+    /// * y ← Random(912) mod r
+    /// * 𝑌 ← y*𝑮ₚ
+    /// ## Arguments:
+    /// * role: [`Role`] : the party's position in the transcript
+    /// * password: &[u8] : the shared password or pairing code
+    /// * sid: &[u8] : a session identifier both parties agree on, e.g. a random
+    ///   nonce chosen by the initiator. It should be unique for every session.
+    /// * channel_id: &[u8] : identifies the two parties or the channel, may be empty
+    /// * associated_data: &[u8] : public data sent along with 𝑌, may be empty
+    pub fn start(
+        role: Role,
+        password: &[u8],
+        sid: &[u8],
+        channel_id: &[u8],
+        associated_data: &[u8],
+    ) -> (CPace, CPaceMessage) {
+        let mut generator_string = tuple_hash(
+            &[password, channel_id, sid],
+            8 * SESSION_KEY_BYTES,
+            "CPace generator",
+            SecParam::D512,
+        );
+        let generator = hash_to_curve(&generator_string, GENERATOR_DST);
+        generator_string.zeroize();
+
+        let secret = SecretScalar::new(random_scalar());
+        let message = CPaceMessage {
+            point: generator * *secret.expose(),
+            associated_data: associated_data.to_vec(),
+        };
+        let state = CPace {
+            role,
+            sid: sid.to_vec(),
+            secret,
+            message: message.clone(),
+        };
+        (state, message)
+    }
+
+    /// # Session Finish
+    /// Derives the session key from the peer's message.
+    /// ## Algorithm:
+    /// * abort if 𝑌ₚₑₑᵣ = 𝒪, so that 𝐾 ≠ 𝒪 as well
+    /// * 𝐾 ← y*𝑌ₚₑₑᵣ
+    /// * ISK ← TupleHash256((sid, 𝐾, 𝑌ᵢ, ADᵢ, 𝑌ᵣ, ADᵣ), 512, “CPace ISK”), with the
+    ///   initiator's message first
+    /// ## Errors:
+    /// * [`OperationError::InvalidPointEncoding`] if the peer's point is 𝒪.
+    pub fn finish(self, peer: &CPaceMessage) -> Result<SessionKey, OperationError> {
+        if peer.point == ExtendedPoint::id_point() {
+            return Err(OperationError::InvalidPointEncoding);
+        }
+        let mut shared = encode_point(&(peer.point * *self.secret.expose()));
+        let (initiator, responder) = match self.role {
+            Role::Initiator => (&self.message, peer),
+            Role::Responder => (peer, &self.message),
+        };
+        let mut isk = tuple_hash(
+            &[
+                &self.sid,
+                &shared,
+                &encode_point(&initiator.point),
+                &initiator.associated_data,
+                &encode_point(&responder.point),
+                &responder.associated_data,
+            ],
+            8 * SESSION_KEY_BYTES,
+            "CPace ISK",
+            SecParam::D512,
+        );
+        let key = SessionKey {
+            role: self.role,
+            isk: isk.as_slice().try_into().unwrap(),
+        };
+        shared.zeroize();
+        isk.zeroize();
+        Ok(key)
+    }
+}
+
+impl SessionKey {
+    /// A 64-byte key for
+    /// [`SpongeEncryptable`](crate::sha3::encryptable::SpongeEncryptable)
+    /// encryption, kmac_xof(ISK, “”, 512, “CPace SKE”).
+    pub fn sponge_key(&self) -> Vec<u8> {
+        kmac_xof(&self.isk, &[], 512, "CPace SKE", SecParam::D512)
+    }
+
+    /// A key of `key_size` for the
+    /// [`AesEncryptable`](crate::aes::encryptable::AesEncryptable) modes,
+    /// kmac_xof(ISK, “”, |key|, “CPace AES”).
+    pub fn aes_key(&self, key_size: AesKeySize) -> Vec<u8> {
+        kmac_xof(
+            &self.isk,
+            &[],
+            8 * key_size.enc_key_len(),
+            "CPace AES",
+            SecParam::D512,
+        )
+    }
+
+    /// # Key Confirmation
+    /// The tag this party sends to prove it derived the same session key,
+    /// kmac_xof(ISK, “”, 512, “CPace confirm initiator”) for the initiator and
+    /// “CPace confirm responder” for the responder.
+    pub fn confirmation(&self) -> [u8; SESSION_KEY_BYTES] {
+        confirmation_tag(&self.isk, self.role)
+    }
+
+    /// Checks the confirmation tag received from the peer in constant time.
+    /// ## Errors:
+    /// * [`OperationError::PakeConfirmationFailure`] if the tag does not match,
+    ///   which means the passwords differ or a message was tampered with.
+    pub fn verify_confirmation(&self, tag: &[u8]) -> Result<(), OperationError> {
+        let peer = match self.role {
+            Role::Initiator => Role::Responder,
+            Role::Responder => Role::Initiator,
+        };
+        let expected = confirmation_tag(&self.isk, peer);
+        if tag.len() == SESSION_KEY_BYTES && bool::from(expected.ct_eq(tag)) {
+            Ok(())
+        } else {
+            Err(OperationError::PakeConfirmationFailure)
+        }
+    }
+}
+
+impl Drop for CPace {
+    fn drop(&mut self) {
+        self.sid.zeroize();
+    }
+}
+
+impl Drop for SessionKey {
+    fn drop(&mut self) {
+        self.isk.zeroize();
+    }
+}
+
+fn confirmation_tag(isk: &[u8], role: Role) -> [u8; SESSION_KEY_BYTES] {
+    let s = match role {
+        Role::Initiator => "CPace confirm initiator",
+        Role::Responder => "CPace confirm responder",
+    };
+    kmac_xof(isk, &[], 8 * SESSION_KEY_BYTES, s, SecParam::D512)
+        .try_into()
+        .unwrap()
+}
@@ -0,0 +1,327 @@
+//! Pedersen commitments to values and range proofs over them.
+//!
+//! A commitment 𝐶 = v*𝑮 + r*𝑯 to a value v with a random blinding factor r
+//! hides v completely and binds the committer to it, since nobody knows the
+//! discrete logarithm of the second generator 𝑯 =
+//! [`pedersen_generator`](crate::ecc::shamir::pedersen_generator) to 𝑮.
+//! Commitments are additively homomorphic: the sum of two commitments commits
+//! to the sum of their values under the sum of their blinding factors, so
+//! balances can be updated and checked without opening them.
+//!
+//! A [`RangeProof`](crate::ecc::pedersen::RangeProof) shows that a commitment
+//! holds a value in [0, 2ⁿ) for n ≤ 64 without revealing it. It commits to
+//! every bit of the value and proves with a Chaum–Pedersen OR proof that each
+//! bit commitment holds 0 or 1. Proofs grow linearly with n, unlike
+//! Bulletproofs, in exchange for a much simpler construction.
+use super::{
+    edwards448::{
+        encode_point, multiscalar_mul, random_scalar, scalar_from_bytes_wide, scalar_negate,
+    },
+    keypair::SecretScalar,
+    shamir::pedersen_generator,
+};
+use crate::{sha3::shake_functions::kmac_xof, OperationError, SecParam};
+use crypto_bigint::U448;
+use serde::{Deserialize, Serialize};
+use std::ops::{Add, Sub};
+use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+
+// Hashes to scalars are 114 bytes long and reduced mod r.
+const WIDE_BITS: usize = 912;
+/// Largest supported bit length of a range proof.
+pub const MAX_RANGE_BITS: usize = 64;
+
+/// A Pedersen commitment 𝐶 = v*𝑮 + r*𝑯.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Commitment(#[serde(with = "super::edwards448::serde_point")] pub ExtendedPoint);
+
+/// The value v and blinding factor r that open a [`Commitment`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Opening {
+    /// The committed value v
+    #[serde(with = "super::edwards448::serde_scalar")]
+    pub value: Scalar,
+    /// The blinding factor r
+    pub blinding: SecretScalar,
+}
+
+/// A proof that a commitment holds a value in [0, 2ⁿ).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RangeProof {
+    /// Commitments 𝐶ᵢ = bᵢ*𝑮 + rᵢ*𝑯 to the bits of the value, least significant first
+    #[serde(with = "super::edwards448::serde_point_vec")]
+    pub bit_commitments: Vec<ExtendedPoint>,
+    /// One OR proof per bit commitment
+    pub bit_proofs: Vec<BitProof>,
+}
+
+/// A Chaum–Pedersen OR proof that 𝐶ᵢ or 𝐶ᵢ - 𝑮 is a multiple of 𝑯, with
+/// one challenge and response per branch.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct BitProof {
+    #[serde(with = "super::edwards448::serde_scalar")]
+    c0: Scalar,
+    #[serde(with = "super::edwards448::serde_scalar")]
+    c1: Scalar,
+    #[serde(with = "super::edwards448::serde_scalar")]
+    s0: Scalar,
+    #[serde(with = "super::edwards448::serde_scalar")]
+    s1: Scalar,
+}
+
+impl Commitment {
+    /// # Commitment
+    /// Commits to `value` with a fresh random blinding factor.
+    /// ## Algorithm:
+    /// * r ← Random(912) mod r
+    /// * 𝐶 ← v*𝑮 + r*𝑯
+    /// ## Returns:
+    /// * return -> ([`Commitment`], [`Opening`]): the commitment to publish and
+    ///   the opening to keep.
+    pub fn commit(value: &Scalar) -> (Commitment, Opening) {
+        let opening = Opening {
+            value: *value,
+            blinding: SecretScalar::new(random_scalar()),
+        };
+        (Commitment::from_opening(&opening), opening)
+    }
+
+    /// Recomputes the commitment 𝐶 = v*𝑮 + r*𝑯 of an opening.
+    pub fn from_opening(opening: &Opening) -> Commitment {
+        Commitment(
+            ExtendedPoint::generator() * opening.value
+                + pedersen_generator() * *opening.blinding.expose(),
+        )
+    }
+
+    /// Checks that `opening` opens this commitment.
+    /// ## Errors:
+    /// * [`OperationError::InvalidOpening`] if it does not.
+    pub fn verify_opening(&self, opening: &Opening) -> Result<(), OperationError> {
+        if Commitment::from_opening(opening) == *self {
+            Ok(())
+        } else {
+            Err(OperationError::InvalidOpening)
+        }
+    }
+}
+
+impl Add for Commitment {
+    type Output = Commitment;
+
+    /// Commits to v₁ + v₂ under the blinding factor r₁ + r₂.
+    fn add(self, other: Commitment) -> Commitment {
+        Commitment(self.0 + other.0)
+    }
+}
+
+impl Sub for Commitment {
+    type Output = Commitment;
+
+    /// Commits to v₁ - v₂ under the blinding factor r₁ - r₂.
+    fn sub(self, other: Commitment) -> Commitment {
+        Commitment(self.0 + -other.0)
+    }
+}
+
+impl Add for &Opening {
+    type Output = Opening;
+
+    /// Opens the sum of the two commitments.
+    fn add(self, other: &Opening) -> Opening {
+        Opening {
+            value: self.value.add_mod(&other.value),
+            blinding: SecretScalar::new(self.blinding.expose().add_mod(other.blinding.expose())),
+        }
+    }
+}
+
+impl Sub for &Opening {
+    type Output = Opening;
+
+    /// Opens the difference of the two commitments.
+    fn sub(self, other: &Opening) -> Opening {
+        Opening {
+            value: self.value.sub_mod(&other.value),
+            blinding: SecretScalar::new(self.blinding.expose().sub_mod(other.blinding.expose())),
+        }
+    }
+}
+
+impl RangeProof {
+    /// # Range Proof
+    /// Proves that the commitment of `opening` holds a value in [0, 2ⁿ)
+    /// with n = `bits`.
+    /// ## Algorithm:
+    /// * write v = Σ bᵢ*2ⁱ and pick random rᵢ for i ≥ 1 and
+    ///   r₀ ← r - Σᵢ₌₁ rᵢ*2ⁱ, so that Σ 2ⁱ*𝐶ᵢ = 𝐶
+    /// * 𝐶ᵢ ← bᵢ*𝑮 + rᵢ*𝑯
+    /// * for every bit, with 𝑃₀ = 𝐶ᵢ and 𝑃₁ = 𝐶ᵢ - 𝑮, so that 𝑃ᵦ = rᵢ*𝑯 for b = bᵢ:
+    ///   * k ← Random, 𝑅ᵦ ← k*𝑯
+    ///   * simulate the other branch: c', s' ← Random, 𝑅₁₋ᵦ ← s'*𝑯 - c'*𝑃₁₋ᵦ
+    ///   * c ← kmac_xof(context, 𝐶 || 𝐶₀ || … || 𝐶ₙ₋₁ || i || 𝑅₀ || 𝑅₁, 912, “RANGE”) mod r
+    ///   * cᵦ ← c - c', sᵦ ← k + cᵦ*rᵢ
+    /// ## Arguments:
+    /// * opening: &[`Opening`] : opening of the commitment
+    /// * bits: usize : bit length n of the range, 1 ≤ n ≤ 64
+    /// * context: &[u8] : binds the proof to its purpose, may be empty
+    /// ## Errors:
+    /// * [`OperationError::UnsupportedRangeSize`] unless 1 ≤ n ≤ 64.
+    /// * [`OperationError::ValueOutOfRange`] if v ≥ 2ⁿ.
+    pub fn prove(
+        opening: &Opening,
+        bits: usize,
+        context: &[u8],
+    ) -> Result<RangeProof, OperationError> {
+        if !(1..=MAX_RANGE_BITS).contains(&bits) {
+            return Err(OperationError::UnsupportedRangeSize);
+        }
+        if opening.value.val.bits_vartime() > bits {
+            return Err(OperationError::ValueOutOfRange);
+        }
+        let h = pedersen_generator();
+        let commitment = Commitment::from_opening(opening);
+
+        let mut blindings: Vec<SecretScalar> = (0..bits)
+            .map(|_| SecretScalar::new(random_scalar()))
+            .collect();
+        let rest = (1..bits).fold(Scalar::from(0_u64), |acc, i| {
+            acc.add_mod(&blindings[i].expose().mul_mod(&power_of_two(i)))
+        });
+        blindings[0] = SecretScalar::new(opening.blinding.expose().sub_mod(&rest));
+
+        let bit_values: Vec<bool> = (0..bits)
+            .map(|i| opening.value.val.bit_vartime(i))
+            .collect();
+        let bit_commitments: Vec<ExtendedPoint> = bit_values
+            .iter()
+            .zip(&blindings)
+            .map(|(&b, r)| {
+                let blinded = h * *r.expose();
+                if b {
+                    blinded + ExtendedPoint::generator()
+                } else {
+                    blinded
+                }
+            })
+            .collect();
+
+        let transcript = transcript(&commitment, &bit_commitments);
+        let bit_proofs = (0..bits)
+            .map(|i| {
+                let points = [
+                    bit_commitments[i],
+                    bit_commitments[i] + ExtendedPoint::generator().negate(),
+                ];
+                let real = bit_values[i] as usize;
+                let fake = 1 - real;
+
+                let k = random_scalar();
+                let (c_fake, s_fake) = (random_scalar(), random_scalar());
+                let mut nonces = [ExtendedPoint::id_point(); 2];
+                nonces[real] = h * k;
+                nonces[fake] = h * s_fake + (points[fake] * c_fake).negate();
+
+                let c = bit_challenge(context, &transcript, i, &nonces);
+                let c_real = c.sub_mod(&c_fake);
+                let s_real = k.add_mod(&c_real.mul_mod(blindings[i].expose()));
+                if real == 0 {
+                    BitProof {
+                        c0: c_real,
+                        c1: c_fake,
+                        s0: s_real,
+                        s1: s_fake,
+                    }
+                } else {
+                    BitProof {
+                        c0: c_fake,
+                        c1: c_real,
+                        s0: s_fake,
+                        s1: s_real,
+                    }
+                }
+            })
+            .collect();
+
+        Ok(RangeProof {
+            bit_commitments,
+            bit_proofs,
+        })
+    }
+
+    /// # Range Proof Verification
+    /// Checks that `commitment` holds a value in [0, 2ⁿ), where n is the
+    /// number of bit commitments in the proof.
+    /// ## Algorithm:
+    /// * check Σ 2ⁱ*𝐶ᵢ = 𝐶
+    /// * for every bit: 𝑅₀ ← s₀*𝑯 - c₀*𝐶ᵢ, 𝑅₁ ← s₁*𝑯 - c₁*(𝐶ᵢ - 𝑮), and check
+    ///   c₀ + c₁ = kmac_xof(context, 𝐶 || 𝐶₀ || … || 𝐶ₙ₋₁ || i || 𝑅₀ || 𝑅₁, 912, “RANGE”) mod r
+    /// ## Errors:
+    /// * [`OperationError::UnsupportedRangeSize`] unless the proof covers 1 to 64 bits.
+    /// * [`OperationError::ProofVerificationFailure`] if the proof does not verify.
+    pub fn verify(&self, commitment: &Commitment, context: &[u8]) -> Result<(), OperationError> {
+        let bits = self.bit_commitments.len();
+        if !(1..=MAX_RANGE_BITS).contains(&bits) || self.bit_proofs.len() != bits {
+            return Err(OperationError::UnsupportedRangeSize);
+        }
+        let powers: Vec<Scalar> = (0..bits).map(power_of_two).collect();
+        if multiscalar_mul(&powers, &self.bit_commitments) != commitment.0 {
+            return Err(OperationError::ProofVerificationFailure);
+        }
+
+        let h = pedersen_generator();
+        let g = ExtendedPoint::generator();
+        let transcript = transcript(commitment, &self.bit_commitments);
+        for (i, (c_i, proof)) in self
+            .bit_commitments
+            .iter()
+            .zip(&self.bit_proofs)
+            .enumerate()
+        {
+            let nonces = [
+                multiscalar_mul(&[proof.s0, scalar_negate(&proof.c0)], &[h, *c_i]),
+                multiscalar_mul(
+                    &[proof.s1, scalar_negate(&proof.c1), proof.c1],
+                    &[h, *c_i, g],
+                ),
+            ];
+            if bit_challenge(context, &transcript, i, &nonces) != proof.c0.add_mod(&proof.c1) {
+                return Err(OperationError::ProofVerificationFailure);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 𝐶 || 𝐶₀ || … || 𝐶ₙ₋₁, which every bit challenge is bound to.
+fn transcript(commitment: &Commitment, bit_commitments: &[ExtendedPoint]) -> Vec<u8> {
+    std::iter::once(&commitment.0)
+        .chain(bit_commitments)
+        .flat_map(encode_point)
+        .collect()
+}
+
+/// c ← kmac_xof(context, transcript || i || 𝑅₀ || 𝑅₁, 912, “RANGE”) mod r,
+/// with i as one byte.
+fn bit_challenge(
+    context: &[u8],
+    transcript: &[u8],
+    index: usize,
+    nonces: &[ExtendedPoint; 2],
+) -> Scalar {
+    let mut data = transcript.to_vec();
+    data.push(index as u8);
+    data.extend_from_slice(&encode_point(&nonces[0]));
+    data.extend_from_slice(&encode_point(&nonces[1]));
+    scalar_from_bytes_wide(&kmac_xof(
+        context,
+        &data,
+        WIDE_BITS,
+        "RANGE",
+        SecParam::D512,
+    ))
+}
+
+fn power_of_two(i: usize) -> Scalar {
+    Scalar::from(U448::ONE.shl_vartime(i))
+}
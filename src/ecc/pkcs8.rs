@@ -0,0 +1,452 @@
+//! Standard key encodings for Ed448 and X448 as specified in RFC 8410.
+//!
+//! Public keys are encoded as DER SubjectPublicKeyInfo, secret keys as DER
+//! PKCS#8 OneAsymmetricKey holding the raw 57-byte Ed448 or 56-byte X448
+//! secret key, and both can be wrapped in PEM. The output matches what
+//! OpenSSL writes for the same keys. Decoding accepts version 1 and version 2
+//! PKCS#8 documents and validates every key it returns: public keys must be
+//! canonical encodings of points of the prime-order subgroup, and a public key
+//! embedded in a PKCS#8 document must belong to its secret key.
+use super::{
+    eddsa::{SigningKey, VerifyingKey, SECRET_KEY_BYTES},
+    edwards448::{decode_point_checked, encode_point, POINT_BYTES},
+    keypair::KeyPair,
+    x448::{X448PublicKey, X448SecretKey, X448_BYTES},
+};
+use crate::OperationError;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use tiny_ed448_goldilocks::curve::extended_edwards::ExtendedPoint;
+use zeroize::Zeroize;
+
+/// PEM label of a SubjectPublicKeyInfo document.
+pub const PUBLIC_KEY_LABEL: &str = "PUBLIC KEY";
+/// PEM label of a PKCS#8 document.
+pub const PRIVATE_KEY_LABEL: &str = "PRIVATE KEY";
+
+const TAG_INTEGER: u8 = 0x02;
+const TAG_BIT_STRING: u8 = 0x03;
+const TAG_OCTET_STRING: u8 = 0x04;
+const TAG_OID: u8 = 0x06;
+const TAG_SEQUENCE: u8 = 0x30;
+const TAG_ATTRIBUTES: u8 = 0xA0;
+const TAG_PUBLIC_KEY: u8 = 0x81;
+
+/// The key algorithms of RFC 8410 supported by this crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyAlgorithm {
+    /// id-Ed448, 1.3.101.113
+    Ed448,
+    /// id-X448, 1.3.101.111
+    X448,
+}
+
+impl KeyAlgorithm {
+    fn oid(&self) -> [u8; 3] {
+        match self {
+            KeyAlgorithm::Ed448 => [0x2B, 0x65, 0x71],
+            KeyAlgorithm::X448 => [0x2B, 0x65, 0x6F],
+        }
+    }
+
+    fn public_key_len(&self) -> usize {
+        match self {
+            KeyAlgorithm::Ed448 => POINT_BYTES,
+            KeyAlgorithm::X448 => X448_BYTES,
+        }
+    }
+
+    fn secret_key_len(&self) -> usize {
+        match self {
+            KeyAlgorithm::Ed448 => SECRET_KEY_BYTES,
+            KeyAlgorithm::X448 => X448_BYTES,
+        }
+    }
+
+    // AlgorithmIdentifier ::= SEQUENCE { algorithm OBJECT IDENTIFIER }
+    fn identifier(&self) -> Vec<u8> {
+        tlv(TAG_SEQUENCE, &tlv(TAG_OID, &self.oid()))
+    }
+}
+
+/// # SubjectPublicKeyInfo Encoding
+/// Encodes a raw public key as a DER SubjectPublicKeyInfo.
+/// ## Algorithm:
+/// * SEQUENCE { SEQUENCE { OID }, BIT STRING { 0x00 || key } }
+pub fn encode_spki(alg: KeyAlgorithm, public_key: &[u8]) -> Vec<u8> {
+    let mut bits = vec![0u8];
+    bits.extend_from_slice(public_key);
+    let mut body = alg.identifier();
+    body.extend(tlv(TAG_BIT_STRING, &bits));
+    tlv(TAG_SEQUENCE, &body)
+}
+
+/// # SubjectPublicKeyInfo Decoding
+/// Returns the raw public key of a DER SubjectPublicKeyInfo. The key itself
+/// is not validated, see the `from_public_key_der` functions.
+/// ## Errors:
+/// * [`OperationError::InvalidDerEncoding`] if `der` is not a well-formed document.
+/// * [`OperationError::UnsupportedKeyAlgorithm`] if the document holds a key of
+///   another algorithm.
+pub fn decode_spki(alg: KeyAlgorithm, der: &[u8]) -> Result<Vec<u8>, OperationError> {
+    let mut outer = DerReader::new(der);
+    let mut spki = DerReader::new(outer.read(TAG_SEQUENCE)?);
+    outer.finish()?;
+
+    read_identifier(&mut spki, alg)?;
+    let bits = spki.read(TAG_BIT_STRING)?;
+    spki.finish()?;
+    match bits.split_first() {
+        Some((0, key)) if key.len() == alg.public_key_len() => Ok(key.to_vec()),
+        _ => Err(OperationError::InvalidDerEncoding),
+    }
+}
+
+/// # PKCS#8 Encoding
+/// Encodes a raw secret key as a version 1 DER PKCS#8 document.
+/// ## Algorithm:
+/// * SEQUENCE { INTEGER 0, SEQUENCE { OID }, OCTET STRING { OCTET STRING { key } } }
+pub fn encode_pkcs8(alg: KeyAlgorithm, secret_key: &[u8]) -> Vec<u8> {
+    let mut inner = tlv(TAG_OCTET_STRING, secret_key);
+    let mut body = tlv(TAG_INTEGER, &[0]);
+    body.extend(alg.identifier());
+    body.extend(tlv(TAG_OCTET_STRING, &inner));
+    let der = tlv(TAG_SEQUENCE, &body);
+    inner.zeroize();
+    body.zeroize();
+    der
+}
+
+/// # PKCS#8 Decoding
+/// Returns the raw secret key of a version 1 or 2 DER PKCS#8 document, and the
+/// raw public key if a version 2 document carries one. Attributes are skipped.
+/// ## Errors:
+/// * [`OperationError::InvalidDerEncoding`] if `der` is not a well-formed document.
+/// * [`OperationError::UnsupportedKeyAlgorithm`] if the document holds a key of
+///   another algorithm.
+pub fn decode_pkcs8(
+    alg: KeyAlgorithm,
+    der: &[u8],
+) -> Result<(Vec<u8>, Option<Vec<u8>>), OperationError> {
+    let mut outer = DerReader::new(der);
+    let mut doc = DerReader::new(outer.read(TAG_SEQUENCE)?);
+    outer.finish()?;
+
+    let version = match doc.read(TAG_INTEGER)? {
+        [0] => 0,
+        [1] => 1,
+        _ => return Err(OperationError::InvalidDerEncoding),
+    };
+    read_identifier(&mut doc, alg)?;
+
+    let mut private_key = DerReader::new(doc.read(TAG_OCTET_STRING)?);
+    let secret_key = private_key.read(TAG_OCTET_STRING)?;
+    private_key.finish()?;
+    if secret_key.len() != alg.secret_key_len() {
+        return Err(OperationError::InvalidDerEncoding);
+    }
+
+    if doc.peek() == Some(TAG_ATTRIBUTES) {
+        doc.read(TAG_ATTRIBUTES)?;
+    }
+    let public_key = match doc.peek() {
+        Some(TAG_PUBLIC_KEY) if version == 1 => match doc.read(TAG_PUBLIC_KEY)?.split_first() {
+            Some((0, key)) if key.len() == alg.public_key_len() => Some(key.to_vec()),
+            _ => return Err(OperationError::InvalidDerEncoding),
+        },
+        _ => None,
+    };
+    doc.finish()?;
+    Ok((secret_key.to_vec(), public_key))
+}
+
+/// # PEM Encoding
+/// Wraps a DER document in base64 lines of 64 characters between
+/// `-----BEGIN label-----` and `-----END label-----`.
+pub fn pem_encode(label: &str, der: &[u8]) -> String {
+    let b64 = STANDARD.encode(der);
+    let mut pem = format!("-----BEGIN {}-----\n", label);
+    for line in b64.as_bytes().chunks(64) {
+        pem.push_str(std::str::from_utf8(line).unwrap());
+        pem.push('\n');
+    }
+    pem.push_str(&format!("-----END {}-----\n", label));
+    pem
+}
+
+/// # PEM Decoding
+/// Returns the DER document of a PEM block with the given label.
+/// ## Errors:
+/// * [`OperationError::InvalidPemEncoding`] if the block is missing, has another
+///   label or does not hold valid base64.
+pub fn pem_decode(label: &str, pem: &str) -> Result<Vec<u8>, OperationError> {
+    let begin = format!("-----BEGIN {}-----", label);
+    let end = format!("-----END {}-----", label);
+    let body = pem
+        .trim()
+        .strip_prefix(&begin)
+        .and_then(|rest| rest.strip_suffix(&end))
+        .ok_or(OperationError::InvalidPemEncoding)?;
+    let b64: String = body.split_whitespace().collect();
+    STANDARD
+        .decode(b64)
+        .map_err(|_| OperationError::InvalidPemEncoding)
+}
+
+impl VerifyingKey {
+    /// Encodes this public key as a DER SubjectPublicKeyInfo.
+    pub fn to_public_key_der(&self) -> Vec<u8> {
+        encode_spki(KeyAlgorithm::Ed448, &self.to_bytes())
+    }
+
+    /// Encodes this public key as a PEM SubjectPublicKeyInfo.
+    pub fn to_public_key_pem(&self) -> String {
+        pem_encode(PUBLIC_KEY_LABEL, &self.to_public_key_der())
+    }
+
+    /// # Ed448 Public Key Import
+    /// Decodes a DER SubjectPublicKeyInfo holding an Ed448 public key.
+    /// ## Errors:
+    /// * Any error of [`decode_spki`].
+    /// * [`OperationError::InvalidPointEncoding`] if [`decode_point_checked`] rejects the key.
+    pub fn from_public_key_der(der: &[u8]) -> Result<VerifyingKey, OperationError> {
+        let bytes = decode_spki(KeyAlgorithm::Ed448, der)?;
+        decode_point_checked(&bytes)?;
+        VerifyingKey::from_bytes(&bytes)
+    }
+
+    /// Decodes a PEM SubjectPublicKeyInfo, see [`VerifyingKey::from_public_key_der`].
+    pub fn from_public_key_pem(pem: &str) -> Result<VerifyingKey, OperationError> {
+        Self::from_public_key_der(&pem_decode(PUBLIC_KEY_LABEL, pem)?)
+    }
+}
+
+impl SigningKey {
+    /// Encodes this secret key as a DER PKCS#8 document.
+    pub fn to_pkcs8_der(&self) -> Vec<u8> {
+        let mut seed = self.to_bytes();
+        let der = encode_pkcs8(KeyAlgorithm::Ed448, &seed);
+        seed.zeroize();
+        der
+    }
+
+    /// Encodes this secret key as a PEM PKCS#8 document.
+    pub fn to_pkcs8_pem(&self) -> String {
+        let mut der = self.to_pkcs8_der();
+        let pem = pem_encode(PRIVATE_KEY_LABEL, &der);
+        der.zeroize();
+        pem
+    }
+
+    /// # Ed448 Secret Key Import
+    /// Decodes a DER PKCS#8 document holding an Ed448 secret key.
+    /// ## Errors:
+    /// * Any error of [`decode_pkcs8`].
+    /// * [`OperationError::InvalidScalarEncoding`] if the secret scalar expands to zero.
+    /// * [`OperationError::InvalidPointEncoding`] if the document carries a public
+    ///   key that does not belong to the secret key.
+    pub fn from_pkcs8_der(der: &[u8]) -> Result<SigningKey, OperationError> {
+        let (mut seed, public_key) = decode_pkcs8(KeyAlgorithm::Ed448, der)?;
+        let key = SigningKey::from_seed(&seed);
+        seed.zeroize();
+        let key = key?;
+
+        if key.verifying_key().to_point() == ExtendedPoint::id_point() {
+            return Err(OperationError::InvalidScalarEncoding);
+        }
+        match public_key {
+            Some(public_key) if public_key[..] != key.verifying_key().to_bytes()[..] => {
+                Err(OperationError::InvalidPointEncoding)
+            }
+            _ => Ok(key),
+        }
+    }
+
+    /// Decodes a PEM PKCS#8 document, see [`SigningKey::from_pkcs8_der`].
+    pub fn from_pkcs8_pem(pem: &str) -> Result<SigningKey, OperationError> {
+        let mut der = pem_decode(PRIVATE_KEY_LABEL, pem)?;
+        let key = Self::from_pkcs8_der(&der);
+        der.zeroize();
+        key
+    }
+}
+
+impl X448PublicKey {
+    /// Encodes this public key as a DER SubjectPublicKeyInfo.
+    pub fn to_public_key_der(&self) -> Vec<u8> {
+        encode_spki(KeyAlgorithm::X448, &self.to_bytes())
+    }
+
+    /// Encodes this public key as a PEM SubjectPublicKeyInfo.
+    pub fn to_public_key_pem(&self) -> String {
+        pem_encode(PUBLIC_KEY_LABEL, &self.to_public_key_der())
+    }
+
+    /// # X448 Public Key Import
+    /// Decodes a DER SubjectPublicKeyInfo holding an X448 public key.
+    /// ## Errors:
+    /// * Any error of [`decode_spki`].
+    /// * [`OperationError::InvalidPointEncoding`] unless the key is the canonical
+    ///   u-coordinate of a point of the prime-order subgroup of curve448.
+    pub fn from_public_key_der(der: &[u8]) -> Result<X448PublicKey, OperationError> {
+        let key = X448PublicKey::from_bytes(&decode_spki(KeyAlgorithm::X448, der)?)?;
+        key.to_edwards(0)?;
+        Ok(key)
+    }
+
+    /// Decodes a PEM SubjectPublicKeyInfo, see [`X448PublicKey::from_public_key_der`].
+    pub fn from_public_key_pem(pem: &str) -> Result<X448PublicKey, OperationError> {
+        Self::from_public_key_der(&pem_decode(PUBLIC_KEY_LABEL, pem)?)
+    }
+}
+
+impl X448SecretKey {
+    /// Encodes this secret key as a DER PKCS#8 document.
+    pub fn to_pkcs8_der(&self) -> Vec<u8> {
+        let mut k = self.to_bytes();
+        let der = encode_pkcs8(KeyAlgorithm::X448, &k);
+        k.zeroize();
+        der
+    }
+
+    /// Encodes this secret key as a PEM PKCS#8 document.
+    pub fn to_pkcs8_pem(&self) -> String {
+        let mut der = self.to_pkcs8_der();
+        let pem = pem_encode(PRIVATE_KEY_LABEL, &der);
+        der.zeroize();
+        pem
+    }
+
+    /// # X448 Secret Key Import
+    /// Decodes a DER PKCS#8 document holding an X448 secret key.
+    /// ## Errors:
+    /// * Any error of [`decode_pkcs8`].
+    /// * [`OperationError::InvalidPointEncoding`] if the document carries a public
+    ///   key that does not belong to the secret key.
+    pub fn from_pkcs8_der(der: &[u8]) -> Result<X448SecretKey, OperationError> {
+        let (mut k, public_key) = decode_pkcs8(KeyAlgorithm::X448, der)?;
+        let key = X448SecretKey::from_bytes(&k);
+        k.zeroize();
+        let key = key?;
+
+        match public_key {
+            Some(public_key) if public_key[..] != key.public_key().to_bytes()[..] => {
+                Err(OperationError::InvalidPointEncoding)
+            }
+            _ => Ok(key),
+        }
+    }
+
+    /// Decodes a PEM PKCS#8 document, see [`X448SecretKey::from_pkcs8_der`].
+    pub fn from_pkcs8_pem(pem: &str) -> Result<X448SecretKey, OperationError> {
+        let mut der = pem_decode(PRIVATE_KEY_LABEL, pem)?;
+        let key = Self::from_pkcs8_der(&der);
+        der.zeroize();
+        key
+    }
+}
+
+impl KeyPair {
+    /// Encodes the public key as a DER SubjectPublicKeyInfo for Ed448.
+    pub fn to_public_key_der(&self) -> Vec<u8> {
+        encode_spki(KeyAlgorithm::Ed448, &encode_point(&self.pub_key))
+    }
+
+    /// Encodes the public key as a PEM SubjectPublicKeyInfo for Ed448.
+    pub fn to_public_key_pem(&self) -> String {
+        pem_encode(PUBLIC_KEY_LABEL, &self.to_public_key_der())
+    }
+
+    /// # KeyPair Import
+    /// Creates a key pair from an Ed448 PKCS#8 document, see
+    /// [`SigningKey::from_pkcs8_der`] and [`KeyPair::from_seed`].
+    ///
+    /// A key pair only keeps its secret scalar, not the seed it was expanded
+    /// from, so the reverse direction is [`SigningKey::to_pkcs8_der`].
+    pub fn from_pkcs8_der(der: &[u8], owner: String) -> Result<KeyPair, OperationError> {
+        let mut seed = SigningKey::from_pkcs8_der(der)?.to_bytes();
+        let key_pair = KeyPair::from_seed(&seed, owner);
+        seed.zeroize();
+        key_pair
+    }
+
+    /// Creates a key pair from an Ed448 PEM PKCS#8 document, see [`KeyPair::from_pkcs8_der`].
+    pub fn from_pkcs8_pem(pem: &str, owner: String) -> Result<KeyPair, OperationError> {
+        let mut der = pem_decode(PRIVATE_KEY_LABEL, pem)?;
+        let key_pair = Self::from_pkcs8_der(&der, owner);
+        der.zeroize();
+        key_pair
+    }
+}
+
+// Encodes a DER TLV. Every structure in this module is shorter than 64KiB.
+fn tlv(tag: u8, contents: &[u8]) -> Vec<u8> {
+    let len = contents.len();
+    let mut out = vec![tag];
+    if len < 0x80 {
+        out.push(len as u8);
+    } else if len < 0x100 {
+        out.extend_from_slice(&[0x81, len as u8]);
+    } else {
+        out.extend_from_slice(&[0x82, (len >> 8) as u8, len as u8]);
+    }
+    out.extend_from_slice(contents);
+    out
+}
+
+fn read_identifier(reader: &mut DerReader, alg: KeyAlgorithm) -> Result<(), OperationError> {
+    let mut identifier = DerReader::new(reader.read(TAG_SEQUENCE)?);
+    let oid = identifier.read(TAG_OID)?;
+    // RFC 8410 3: the parameters field must be absent
+    identifier.finish()?;
+    if oid == alg.oid() {
+        Ok(())
+    } else {
+        Err(OperationError::UnsupportedKeyAlgorithm)
+    }
+}
+
+// Reads consecutive DER TLVs with definite, minimally encoded lengths.
+struct DerReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> DerReader<'a> {
+    fn new(data: &'a [u8]) -> DerReader<'a> {
+        DerReader { data }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.data.first().copied()
+    }
+
+    fn read(&mut self, tag: u8) -> Result<&'a [u8], OperationError> {
+        let err = OperationError::InvalidDerEncoding;
+        let (&t, rest) = self.data.split_first().ok_or(err.clone())?;
+        let (&first, rest) = rest.split_first().ok_or(err.clone())?;
+        if t != tag {
+            return Err(err);
+        }
+        let (len, rest) = match first {
+            0..=0x7F => (first as usize, rest),
+            0x81 if rest.first().is_some_and(|&b| b >= 0x80) => (rest[0] as usize, &rest[1..]),
+            0x82 if rest.len() >= 2 && rest[0] != 0 => {
+                (((rest[0] as usize) << 8) | rest[1] as usize, &rest[2..])
+            }
+            _ => return Err(err),
+        };
+        if rest.len() < len {
+            return Err(err);
+        }
+        let (contents, rest) = rest.split_at(len);
+        self.data = rest;
+        Ok(contents)
+    }
+
+    fn finish(&self) -> Result<(), OperationError> {
+        if self.data.is_empty() {
+            Ok(())
+        } else {
+            Err(OperationError::InvalidDerEncoding)
+        }
+    }
+}
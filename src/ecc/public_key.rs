@@ -0,0 +1,177 @@
+//! Public-key-only export and fingerprints.
+//!
+//! A [`PublicKey`](crate::ecc::public_key::PublicKey) is the part of a
+//! [`KeyPair`](crate::ecc::keypair::KeyPair) that can be handed to others.
+//! Its [`Fingerprint`](crate::ecc::public_key::Fingerprint) commits to both the
+//! point and the owner label, so two people can compare it over another
+//! channel, as hex, as words or as emoji, before trusting the key.
+use super::{
+    edwards448::{encode_point, serde_point},
+    keypair::KeyPair,
+};
+use crate::{sha3::shake_functions::tuple_hash, SecParam};
+use serde::{Deserialize, Serialize};
+use std::{fmt, fs::File, io::Read};
+use tiny_ed448_goldilocks::curve::extended_edwards::ExtendedPoint;
+
+/// Length in bytes of a key fingerprint.
+pub const FINGERPRINT_BYTES: usize = 32;
+/// Number of words in [`Fingerprint::to_words`], 8 bits each.
+pub const FINGERPRINT_WORDS: usize = 12;
+/// Number of emoji in [`Fingerprint::to_emoji`], 6 bits each.
+pub const FINGERPRINT_EMOJI: usize = 16;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+/// The public half of a [`KeyPair`].
+pub struct PublicKey {
+    /// String indicating the owner of the key, can be arbitrary
+    pub owner: String,
+    /// Public encryption key
+    #[serde(with = "serde_point")]
+    pub pub_key: ExtendedPoint,
+    /// Date key was generated
+    pub date_created: String,
+}
+
+/// A short digest of a [`PublicKey`] for out-of-band comparison.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fingerprint([u8; FINGERPRINT_BYTES]);
+
+impl PublicKey {
+    /// # Key Fingerprint
+    /// Computes the fingerprint of this key.
+    /// ## Algorithm:
+    /// * fingerprint ← TupleHash256((encode_point(𝑉), owner), 256, “FP”)
+    pub fn fingerprint(&self) -> Fingerprint {
+        let encoded = encode_point(&self.pub_key);
+        let digest = tuple_hash(
+            &[&encoded, self.owner.as_bytes()],
+            8 * FINGERPRINT_BYTES,
+            "FP",
+            SecParam::D512,
+        );
+        let mut fingerprint = [0u8; FINGERPRINT_BYTES];
+        fingerprint.copy_from_slice(&digest);
+        Fingerprint(fingerprint)
+    }
+
+    /// # PublicKey Saving
+    ///
+    /// Saves the public key to a JSON file.
+    pub fn write_to_file(&self, filename: &str) -> std::io::Result<()> {
+        let json_public_key = serde_json::to_string_pretty(self).unwrap();
+        std::fs::write(filename, json_public_key)
+    }
+
+    /// # PublicKey Loading
+    ///
+    /// Reads a JSON file and creates a `PublicKey` from its contents.
+    ///
+    /// ## Errors:
+    ///
+    /// Returns an error if:
+    /// - The file cannot be opened or read.
+    /// - The JSON content cannot be parsed into a `PublicKey`, including when
+    ///   the point is not a valid public key.
+    pub fn read_from_file(filename: &str) -> Result<PublicKey, Box<dyn std::error::Error>> {
+        let mut file = File::open(filename)?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents)?;
+
+        let public_key: PublicKey = serde_json::from_str(&contents)?;
+        Ok(public_key)
+    }
+}
+
+impl KeyPair {
+    /// The public half of this key pair, without the secret scalar.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey {
+            owner: self.owner.clone(),
+            pub_key: self.pub_key,
+            date_created: self.date_created.clone(),
+        }
+    }
+
+    /// The fingerprint of [`KeyPair::public_key`].
+    pub fn fingerprint(&self) -> Fingerprint {
+        self.public_key().fingerprint()
+    }
+}
+
+impl Fingerprint {
+    /// The raw fingerprint bytes.
+    pub fn to_bytes(&self) -> [u8; FINGERPRINT_BYTES] {
+        self.0
+    }
+
+    /// The fingerprint as hex in groups of four digits.
+    pub fn to_hex(&self) -> String {
+        self.0
+            .chunks(2)
+            .map(hex::encode)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The first 96 bits of the fingerprint as words, one per byte.
+    pub fn to_words(&self) -> Vec<&'static str> {
+        self.0[..FINGERPRINT_WORDS]
+            .iter()
+            .map(|&b| WORDS[b as usize])
+            .collect()
+    }
+
+    /// The first 96 bits of the fingerprint as emoji, one per 6 bits.
+    pub fn to_emoji(&self) -> String {
+        (0..FINGERPRINT_EMOJI)
+            .map(|i| {
+                let bit = 6 * i;
+                let pair = u16::from_be_bytes([self.0[bit / 8], self.0[bit / 8 + 1]]);
+                EMOJI[((pair >> (10 - bit % 8)) & 0x3F) as usize]
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+const WORDS: [&str; 256] = [
+    "acorn", "actor", "adobe", "agent", "alarm", "album", "alley", "amber", "anchor", "angle",
+    "ankle", "apple", "apron", "arch", "arena", "arrow", "atlas", "attic", "award", "axis",
+    "bacon", "badge", "bagel", "baker", "bamboo", "banjo", "barn", "basil", "basket", "beach",
+    "beard", "beaver", "bell", "bench", "berry", "bison", "blade", "blanket", "blossom", "boat",
+    "bolt", "book", "boot", "bottle", "bounce", "bread", "brick", "bridge", "broom", "bubble",
+    "bucket", "buffalo", "cabin", "cactus", "camel", "candle", "canoe", "canyon", "carbon",
+    "carpet", "castle", "cedar", "cello", "chalk", "cherry", "chess", "chief", "cider", "cinema",
+    "circus", "clam", "cliff", "clock", "cloud", "clover", "cobra", "comet", "coral", "cotton",
+    "cougar", "crane", "crater", "crown", "cube", "daisy", "dance", "delta", "desert", "diary",
+    "dingo", "dolphin", "domino", "donkey", "dragon", "drum", "eagle", "earth", "easel", "eclipse",
+    "elbow", "elm", "ember", "engine", "falcon", "feather", "fern", "ferry", "fiddle", "flame",
+    "flute", "forest", "fossil", "fox", "frog", "galaxy", "garden", "garlic", "gecko", "geyser",
+    "ginger", "glacier", "globe", "goose", "grape", "gravel", "guitar", "hammer", "harbor", "harp",
+    "hazel", "helmet", "honey", "hornet", "igloo", "island", "ivory", "jacket", "jaguar", "jelly",
+    "jewel", "jungle", "kayak", "kettle", "kiwi", "koala", "ladder", "lagoon", "lamp", "lantern",
+    "lava", "lemon", "lily", "lizard", "llama", "lobster", "locket", "lotus", "magnet", "mango",
+    "maple", "marble", "meadow", "melon", "meteor", "mint", "mirror", "mitten", "moose", "mosaic",
+    "moth", "muffin", "nectar", "needle", "nest", "nickel", "noodle", "nutmeg", "oasis", "ocean",
+    "olive", "onion", "opal", "orbit", "otter", "owl", "oyster", "paddle", "panda", "paper",
+    "parrot", "peach", "pearl", "pebble", "pepper", "piano", "pickle", "pillow", "pilot", "pine",
+    "planet", "plum", "pocket", "pony", "poppy", "puzzle", "quartz", "quilt", "rabbit", "radar",
+    "radish", "raven", "reef", "ribbon", "river", "robin", "rocket", "rose", "ruby", "saddle",
+    "salmon", "sandal", "satin", "scarf", "shark", "shell", "silver", "sketch", "sled", "sparrow",
+    "spider", "spoon", "squid", "stamp", "statue", "storm", "sugar", "summit", "swan", "tiger",
+    "timber", "toast", "tomato", "topaz", "torch", "tulip", "tundra", "turtle", "valley", "velvet",
+    "violin", "walnut", "walrus", "whale", "willow", "yacht", "zebra",
+];
+
+const EMOJI: [&str; 64] = [
+    "🐶", "🐱", "🐭", "🐹", "🐰", "🦊", "🐻", "🐼", "🐨", "🐯", "🦁", "🐮", "🐷", "🐸", "🐵", "🐔",
+    "🐧", "🐦", "🦆", "🦉", "🐴", "🦄", "🐝", "🐛", "🦋", "🐌", "🐞", "🐢", "🐍", "🐙", "🦀", "🐟",
+    "🐬", "🐳", "🦈", "🐊", "🐘", "🦒", "🌵", "🌲", "🌻", "🌹", "🍄", "🌙", "⭐", "🔥", "🌈", "⛄",
+    "🍎", "🍋", "🍌", "🍉", "🍇", "🍓", "🍒", "🍍", "🥕", "🌽", "🍩", "🎈", "🎸", "🚀", "⚓", "🔑",
+];
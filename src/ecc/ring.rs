@@ -0,0 +1,191 @@
+//! Linkable spontaneous anonymous group (LSAG) ring signatures over Ed448.
+//!
+//! A [`RingSignature`](crate::ecc::ring::RingSignature) proves that a message
+//! was signed by the secret key of one of the public keys in a ring, chosen
+//! freely by the signer, without revealing which one. Every signature carries
+//! the [`KeyImage`](crate::ecc::ring::KeyImage) 𝐼 = x*Hₚ(𝑃) of the signing
+//! key 𝑃 = x*𝑮. The key image is the same for every signature of that key,
+//! whatever the ring or message, and reveals nothing else about the key. A
+//! verifier that stores the key images it has seen therefore detects a key
+//! signing twice, e.g. a second submission from the same registered source.
+//!
+//! The construction follows Liu, Wei and Wong (ACISP 2004) in the form used by
+//! Monero. Hₚ is [`hash_to_curve`](crate::ecc::hash_to_curve::hash_to_curve),
+//! whose output has no known discrete logarithm to 𝑮, and challenges are
+//! KMAC256 hashes of the ring, the key image and the message. Signatures hold
+//! one scalar per ring member, and signing and verification take time linear
+//! in the ring size.
+use super::{
+    edwards448::{encode_point, multiscalar_mul, random_scalar, scalar_from_bytes_wide},
+    hash_to_curve::hash_to_curve,
+    keypair::{KeyPair, SecretScalar},
+};
+use crate::{
+    sha3::shake_functions::{kmac_xof, tuple_hash},
+    OperationError, SecParam,
+};
+use serde::{Deserialize, Serialize};
+use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+
+/// Domain separation tag of Hₚ.
+const KEY_IMAGE_DST: &[u8] = b"capycrypt-LSAG-edwards448_XOF:SHAKE256_ELL2_RO_";
+// Hashes to scalars are 114 bytes long and reduced mod r.
+const WIDE_BITS: usize = 912;
+
+/// The key image 𝐼 = x*Hₚ(𝑃) that links all signatures of one key.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct KeyImage(#[serde(with = "super::edwards448::serde_point")] pub ExtendedPoint);
+
+/// An LSAG signature (c₀, s₀, …, sₙ₋₁, 𝐼) for a ring of n public keys.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RingSignature {
+    /// Challenge c₀ at the first ring member
+    #[serde(with = "super::edwards448::serde_scalar")]
+    pub c0: Scalar,
+    /// Responses sᵢ, one per ring member in ring order
+    #[serde(with = "super::edwards448::serde_scalar_vec")]
+    pub responses: Vec<Scalar>,
+    /// Key image 𝐼 of the signing key
+    pub key_image: KeyImage,
+}
+
+impl KeyImage {
+    /// The key image x*Hₚ(𝑃) of a key pair, e.g. to check whether a key has
+    /// already signed.
+    pub fn new(key: &KeyPair) -> KeyImage {
+        KeyImage(hash_point(&key.pub_key) * *key.priv_key.expose())
+    }
+}
+
+impl RingSignature {
+    /// # Ring Signing
+    /// Signs `msg` as an anonymous member of `ring`, which must contain the
+    /// public key of `key` and is passed to [`RingSignature::verify`] in the
+    /// same order.
+    /// ## Algorithm:
+    /// * π ← position of 𝑃 = x*𝑮 in the ring, 𝐼 ← x*Hₚ(𝑃)
+    /// * a ← Random(912) mod r
+    /// * cπ₊₁ ← H(𝑳, 𝐼, m, a*𝑮, a*Hₚ(𝑃))
+    /// * for i = π + 1, …, π - 1 mod n:
+    ///   * sᵢ ← Random(912) mod r
+    ///   * cᵢ₊₁ ← H(𝑳, 𝐼, m, sᵢ*𝑮 + cᵢ*𝑃ᵢ, sᵢ*Hₚ(𝑃ᵢ) + cᵢ*𝐼)
+    /// * sπ ← (a - cπ*x) mod r
+    ///
+    /// H(𝑳, 𝐼, m, 𝐿, 𝑅) is kmac_xof(t, 𝐿 || 𝑅, 912, “LSAG”) mod r with
+    /// t = TupleHash256((𝑃₀ || … || 𝑃ₙ₋₁, 𝐼, m), 512, “LSAG”).
+    /// ## Errors:
+    /// * [`OperationError::SignerNotInKeyList`] if the public key of `key` is
+    ///   not in the ring.
+    pub fn sign(
+        msg: &[u8],
+        ring: &[ExtendedPoint],
+        key: &KeyPair,
+    ) -> Result<RingSignature, OperationError> {
+        let pi = ring
+            .iter()
+            .position(|p| *p == key.pub_key)
+            .ok_or(OperationError::SignerNotInKeyList)?;
+        let n = ring.len();
+        let x = key.priv_key.expose();
+        let key_image = KeyImage::new(key);
+        let transcript = transcript(msg, ring, &key_image);
+
+        let a = SecretScalar::new(random_scalar());
+        let mut c = vec![Scalar::from(0_u64); n];
+        let mut s = vec![Scalar::from(0_u64); n];
+        c[(pi + 1) % n] = challenge(
+            &transcript,
+            &(ExtendedPoint::generator() * *a.expose()),
+            &(hash_point(&key.pub_key) * *a.expose()),
+        );
+        for j in 1..n {
+            let i = (pi + j) % n;
+            s[i] = random_scalar();
+            c[(i + 1) % n] = ring_step(&transcript, &ring[i], &key_image, &c[i], &s[i]);
+        }
+        s[pi] = a.expose().sub_mod(&c[pi].mul_mod(x));
+
+        Ok(RingSignature {
+            c0: c[0],
+            responses: s,
+            key_image,
+        })
+    }
+
+    /// # Ring Signature Verification
+    /// Checks that `msg` was signed by the secret key of one of the public
+    /// keys in `ring`. To detect double signing, compare the
+    /// [`RingSignature::key_image`] with those of previously accepted
+    /// signatures.
+    /// ## Algorithm:
+    /// * for i = 0, …, n - 1: cᵢ₊₁ ← H(𝑳, 𝐼, m, sᵢ*𝑮 + cᵢ*𝑃ᵢ, sᵢ*Hₚ(𝑃ᵢ) + cᵢ*𝐼)
+    /// * accept iff cₙ = c₀ and 𝐼 ≠ 𝒪
+    /// ## Errors:
+    /// * [`OperationError::InvalidKeyList`] if the ring is empty.
+    /// * [`OperationError::SignatureVerificationFailure`] if the signature
+    ///   does not verify or has a response count other than the ring size.
+    pub fn verify(&self, msg: &[u8], ring: &[ExtendedPoint]) -> Result<(), OperationError> {
+        if ring.is_empty() {
+            return Err(OperationError::InvalidKeyList);
+        }
+        if self.responses.len() != ring.len() || self.key_image.0 == ExtendedPoint::id_point() {
+            return Err(OperationError::SignatureVerificationFailure);
+        }
+        let transcript = transcript(msg, ring, &self.key_image);
+        let c = ring.iter().zip(&self.responses).fold(self.c0, |c, (p, s)| {
+            ring_step(&transcript, p, &self.key_image, &c, s)
+        });
+        if c == self.c0 {
+            Ok(())
+        } else {
+            Err(OperationError::SignatureVerificationFailure)
+        }
+    }
+
+    /// Whether both signatures were made with the same secret key.
+    pub fn is_linked(&self, other: &RingSignature) -> bool {
+        self.key_image == other.key_image
+    }
+}
+
+/// Hₚ(𝑃) = hash_to_curve(encode(𝑃), KEY_IMAGE_DST)
+fn hash_point(p: &ExtendedPoint) -> ExtendedPoint {
+    hash_to_curve(&encode_point(p), KEY_IMAGE_DST)
+}
+
+/// t ← TupleHash256((𝑃₀ || … || 𝑃ₙ₋₁, 𝐼, m), 512, “LSAG”)
+fn transcript(msg: &[u8], ring: &[ExtendedPoint], key_image: &KeyImage) -> Vec<u8> {
+    let ring_bytes: Vec<u8> = ring.iter().flat_map(encode_point).collect();
+    tuple_hash(
+        &[&ring_bytes, &encode_point(&key_image.0), msg],
+        512,
+        "LSAG",
+        SecParam::D512,
+    )
+}
+
+/// c ← kmac_xof(t, 𝐿 || 𝑅, 912, “LSAG”) mod r
+fn challenge(transcript: &[u8], l: &ExtendedPoint, r: &ExtendedPoint) -> Scalar {
+    let mut data = encode_point(l).to_vec();
+    data.extend_from_slice(&encode_point(r));
+    scalar_from_bytes_wide(&kmac_xof(
+        transcript,
+        &data,
+        WIDE_BITS,
+        "LSAG",
+        SecParam::D512,
+    ))
+}
+
+/// cᵢ₊₁ ← H(𝑳, 𝐼, m, sᵢ*𝑮 + cᵢ*𝑃ᵢ, sᵢ*Hₚ(𝑃ᵢ) + cᵢ*𝐼)
+fn ring_step(
+    transcript: &[u8],
+    p: &ExtendedPoint,
+    key_image: &KeyImage,
+    c: &Scalar,
+    s: &Scalar,
+) -> Scalar {
+    let l = multiscalar_mul(&[*s, *c], &[ExtendedPoint::generator(), *p]);
+    let r = multiscalar_mul(&[*s, *c], &[hash_point(p), key_image.0]);
+    challenge(transcript, &l, &r)
+}
@@ -0,0 +1,458 @@
+//! Shamir secret sharing of scalars and of arbitrary byte strings.
+//!
+//! A secret is the constant term of a random polynomial f of degree t - 1, and
+//! the share of shareholder i is f(i). Any t shares determine f and with it
+//! the secret, while t - 1 shares reveal nothing about it.
+//!
+//! Scalars are shared over the scalar field of the curve, optionally with a
+//! commitment to f that lets every shareholder check its share:
+//! * Feldman: (a₀*𝑮, …, aₜ₋₁*𝑮). Reveals the public key a₀*𝑮 of the secret.
+//! * Pedersen: (a₀*𝑮 + b₀*𝑯, …), with a second random polynomial g and a
+//!   second generator 𝑯. Reveals nothing about the secret.
+//!
+//! Byte strings such as passphrases are shared byte by byte over GF(2⁸). Byte
+//! shares carry no commitment: combining a wrong or corrupted share silently
+//! yields a wrong secret.
+use super::{
+    edwards448::{nums_generator, random_scalar, scalar_invert},
+    keypair::SecretScalar,
+};
+use crate::{sha3::aux_functions::byte_utils::get_random_bytes, OperationError};
+use serde::{Deserialize, Serialize};
+use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+use zeroize::Zeroize;
+
+/// Label the Pedersen generator 𝑯 is derived from, see [`pedersen_generator`].
+pub const PEDERSEN_GENERATOR_LABEL: &[u8] = b"capycrypt pedersen H";
+
+/// The share f(i) of a scalar held by shareholder i.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ScalarShare {
+    /// Index i of the shareholder, nonzero
+    pub index: u16,
+    /// Share f(i)
+    pub value: SecretScalar,
+}
+
+/// A [`ScalarShare`] together with the share g(i) of the blinding polynomial.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PedersenShare {
+    /// Share f(i) of the secret
+    pub share: ScalarShare,
+    /// Share g(i) of the blinding polynomial
+    pub blinding: SecretScalar,
+}
+
+/// Feldman commitment (a₀*𝑮, …, aₜ₋₁*𝑮) to the sharing polynomial.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FeldmanCommitment(
+    #[serde(with = "super::edwards448::serde_point_vec")] pub Vec<ExtendedPoint>,
+);
+
+/// Pedersen commitment (a₀*𝑮 + b₀*𝑯, …, aₜ₋₁*𝑮 + bₜ₋₁*𝑯) to the sharing
+/// polynomial f and the blinding polynomial g.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PedersenCommitment(
+    #[serde(with = "super::edwards448::serde_point_vec")] pub Vec<ExtendedPoint>,
+);
+
+/// The share of a byte string held by shareholder i: f(i) for every byte.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ByteShare {
+    /// Index i of the shareholder, nonzero
+    pub index: u8,
+    /// Shares of the secret bytes
+    #[serde(with = "hex::serde")]
+    pub data: Vec<u8>,
+}
+
+/// The second generator 𝑯 of Pedersen commitments, derived with
+/// [`nums_generator`] from [`PEDERSEN_GENERATOR_LABEL`].
+pub fn pedersen_generator() -> ExtendedPoint {
+    nums_generator(PEDERSEN_GENERATOR_LABEL)
+}
+
+/// # Scalar Splitting
+/// Splits `secret` into `count` shares with indices 1 to `count`, any
+/// `threshold` of which recover it.
+/// ## Errors:
+/// * [`OperationError::InvalidThresholdParameters`] unless
+///   1 ≤ `threshold` ≤ `count`.
+pub fn split(
+    secret: &Scalar,
+    threshold: u16,
+    count: u16,
+) -> Result<Vec<ScalarShare>, OperationError> {
+    check_threshold(threshold, count as usize)?;
+    let mut f = random_polynomial(secret, threshold);
+    let shares = scalar_shares(&f, count);
+    zeroize_polynomial(&mut f);
+    Ok(shares)
+}
+
+/// # Verifiable Scalar Splitting (Feldman)
+/// Splits `secret` as in [`split`] and commits to the sharing polynomial.
+/// The commitment reveals the public key `secret`*𝑮.
+/// ## Errors:
+/// * [`OperationError::InvalidThresholdParameters`] unless
+///   1 ≤ `threshold` ≤ `count`.
+pub fn split_feldman(
+    secret: &Scalar,
+    threshold: u16,
+    count: u16,
+) -> Result<(Vec<ScalarShare>, FeldmanCommitment), OperationError> {
+    check_threshold(threshold, count as usize)?;
+    let mut f = random_polynomial(secret, threshold);
+    let commitment = FeldmanCommitment::from_polynomial(&f);
+    let shares = scalar_shares(&f, count);
+    zeroize_polynomial(&mut f);
+    Ok((shares, commitment))
+}
+
+/// # Verifiable Scalar Splitting (Pedersen)
+/// Splits `secret` as in [`split`] and commits to the sharing polynomial f
+/// under a random blinding polynomial g, so that the commitment reveals
+/// nothing about `secret`.
+/// ## Algorithm:
+/// * f ← secret + a₁x + … + aₜ₋₁xᵗ⁻¹, g ← b₀ + b₁x + … with random aⱼ, bⱼ
+/// * commitment: (aⱼ*𝑮 + bⱼ*𝑯) for j = 0, …, t - 1
+/// * share i: (f(i), g(i))
+/// ## Errors:
+/// * [`OperationError::InvalidThresholdParameters`] unless
+///   1 ≤ `threshold` ≤ `count`.
+pub fn split_pedersen(
+    secret: &Scalar,
+    threshold: u16,
+    count: u16,
+) -> Result<(Vec<PedersenShare>, PedersenCommitment), OperationError> {
+    check_threshold(threshold, count as usize)?;
+    let mut f = random_polynomial(secret, threshold);
+    let mut g = random_polynomial(&random_scalar(), threshold);
+    let h = pedersen_generator();
+    let commitment = PedersenCommitment(
+        f.iter()
+            .zip(&g)
+            .map(|(a, b)| ExtendedPoint::generator() * *a + h * *b)
+            .collect(),
+    );
+    let shares = (1..=count)
+        .map(|i| PedersenShare {
+            share: ScalarShare {
+                index: i,
+                value: SecretScalar::new(evaluate(&f, i)),
+            },
+            blinding: SecretScalar::new(evaluate(&g, i)),
+        })
+        .collect();
+    zeroize_polynomial(&mut f);
+    zeroize_polynomial(&mut g);
+    Ok((shares, commitment))
+}
+
+/// # Scalar Recovery
+/// Recovers the secret from at least `threshold` shares by Lagrange
+/// interpolation at 0. Fewer shares give a wrong result without an error.
+/// ## Algorithm:
+/// * secret ← Σ f(i)·λᵢ with λᵢ = Π xⱼ / (xⱼ - xᵢ) over the other shares j
+/// ## Errors:
+/// * [`OperationError::InconsistentShares`] if `shares` is empty.
+/// * [`OperationError::InvalidParticipantIdentifier`] if an index is zero or
+///   appears twice.
+pub fn combine(shares: &[ScalarShare]) -> Result<Scalar, OperationError> {
+    if shares.is_empty() {
+        return Err(OperationError::InconsistentShares);
+    }
+    check_indices(shares.iter().map(|s| s.index))?;
+
+    let indices: Vec<u16> = shares.iter().map(|s| s.index).collect();
+    let secret = shares
+        .iter()
+        .zip(lagrange_coefficients(&indices))
+        .fold(Scalar::from(0_u64), |acc, (share, lambda)| {
+            acc.add_mod(&share.value.expose().mul_mod(&lambda))
+        });
+    Ok(secret)
+}
+
+impl FeldmanCommitment {
+    /// The public key a₀*𝑮 of the shared secret.
+    pub fn public_key(&self) -> ExtendedPoint {
+        self.0.first().copied().unwrap_or(ExtendedPoint::id_point())
+    }
+
+    /// The public share f(i)*𝑮 of shareholder i.
+    pub fn public_share(&self, index: u16) -> ExtendedPoint {
+        evaluate_commitment(&self.0, index)
+    }
+
+    /// # Feldman Share Verification
+    /// ## Algorithm:
+    /// * accept iff f(i)*𝑮 = Σ iʲ*(aⱼ*𝑮)
+    /// ## Errors:
+    /// * [`OperationError::InvalidSecretShare`] if the check fails.
+    pub fn verify(&self, share: &ScalarShare) -> Result<(), OperationError> {
+        if self.holds(share.index, share.value.expose()) {
+            Ok(())
+        } else {
+            Err(OperationError::InvalidSecretShare(share.index))
+        }
+    }
+
+    // (a₀*𝑮, …, aₜ₋₁*𝑮)
+    pub(crate) fn from_polynomial(f: &[Scalar]) -> FeldmanCommitment {
+        FeldmanCommitment(f.iter().map(|a| ExtendedPoint::generator() * *a).collect())
+    }
+
+    // Checks f(i)*𝑮 against the commitment.
+    pub(crate) fn holds(&self, index: u16, value: &Scalar) -> bool {
+        index != 0 && ExtendedPoint::generator() * *value == self.public_share(index)
+    }
+}
+
+impl PedersenCommitment {
+    /// # Pedersen Share Verification
+    /// ## Algorithm:
+    /// * accept iff f(i)*𝑮 + g(i)*𝑯 = Σ iʲ*(aⱼ*𝑮 + bⱼ*𝑯)
+    /// ## Errors:
+    /// * [`OperationError::InvalidSecretShare`] if the check fails.
+    pub fn verify(&self, share: &PedersenShare) -> Result<(), OperationError> {
+        let index = share.share.index;
+        let lhs = ExtendedPoint::generator() * *share.share.value.expose()
+            + pedersen_generator() * *share.blinding.expose();
+        if index != 0 && lhs == evaluate_commitment(&self.0, index) {
+            Ok(())
+        } else {
+            Err(OperationError::InvalidSecretShare(index))
+        }
+    }
+}
+
+/// # Byte String Splitting
+/// Splits `secret` into `count` shares with indices 1 to `count`, any
+/// `threshold` of which recover it. Every share is as long as `secret`.
+/// ## Algorithm:
+/// * for every byte s of the secret: f ← s + a₁x + … + aₜ₋₁xᵗ⁻¹ over GF(2⁸)
+///   with random aⱼ, and share i receives f(i)
+/// ## Errors:
+/// * [`OperationError::InvalidThresholdParameters`] unless
+///   1 ≤ `threshold` ≤ `count` ≤ 255.
+pub fn split_bytes(
+    secret: &[u8],
+    threshold: u8,
+    count: u8,
+) -> Result<Vec<ByteShare>, OperationError> {
+    check_threshold(threshold as u16, count as usize)?;
+    let mut shares: Vec<ByteShare> = (1..=count)
+        .map(|index| ByteShare {
+            index,
+            data: Vec::with_capacity(secret.len()),
+        })
+        .collect();
+
+    let mut f = vec![0u8; threshold as usize];
+    for &s in secret {
+        f[0] = s;
+        let mut coefficients = get_random_bytes(threshold as u64 - 1);
+        f[1..].copy_from_slice(&coefficients);
+        coefficients.zeroize();
+        for share in shares.iter_mut() {
+            share.data.push(gf_evaluate(&f, share.index));
+        }
+    }
+    f.zeroize();
+    Ok(shares)
+}
+
+/// # Byte String Recovery
+/// Recovers the secret from at least `threshold` shares. Fewer shares give a
+/// wrong result without an error.
+/// ## Errors:
+/// * [`OperationError::InconsistentShares`] if `shares` is empty or the
+///   shares differ in length.
+/// * [`OperationError::InvalidParticipantIdentifier`] if an index is zero or
+///   appears twice.
+pub fn combine_bytes(shares: &[ByteShare]) -> Result<Vec<u8>, OperationError> {
+    let len = shares
+        .first()
+        .ok_or(OperationError::InconsistentShares)?
+        .data
+        .len();
+    if shares.iter().any(|s| s.data.len() != len) {
+        return Err(OperationError::InconsistentShares);
+    }
+    check_indices(shares.iter().map(|s| s.index as u16))?;
+
+    // λᵢ = Π xⱼ / (xⱼ + xᵢ), as subtraction is addition in GF(2⁸)
+    let lambdas: Vec<u8> = shares
+        .iter()
+        .map(|share| {
+            let (num, den) = shares
+                .iter()
+                .filter(|other| other.index != share.index)
+                .fold((1u8, 1u8), |(num, den), other| {
+                    (
+                        gf_mul(num, other.index),
+                        gf_mul(den, other.index ^ share.index),
+                    )
+                });
+            gf_mul(num, gf_inv(den))
+        })
+        .collect();
+
+    Ok((0..len)
+        .map(|k| {
+            shares
+                .iter()
+                .zip(&lambdas)
+                .fold(0u8, |acc, (share, &lambda)| {
+                    acc ^ gf_mul(share.data[k], lambda)
+                })
+        })
+        .collect())
+}
+
+impl ByteShare {
+    /// Encodes the share as its index followed by its data.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = vec![self.index];
+        out.extend_from_slice(&self.data);
+        out
+    }
+
+    /// Parses a share encoded with [`ByteShare::to_bytes`].
+    /// ## Errors:
+    /// * [`OperationError::InvalidParticipantIdentifier`] if `bytes` is empty
+    ///   or the index is zero.
+    pub fn from_bytes(bytes: &[u8]) -> Result<ByteShare, OperationError> {
+        match bytes.split_first() {
+            Some((&index, data)) if index != 0 => Ok(ByteShare {
+                index,
+                data: data.to_vec(),
+            }),
+            _ => Err(OperationError::InvalidParticipantIdentifier),
+        }
+    }
+}
+
+impl Drop for ByteShare {
+    fn drop(&mut self) {
+        self.data.zeroize();
+    }
+}
+
+pub(crate) fn check_threshold(threshold: u16, count: usize) -> Result<(), OperationError> {
+    if 1 <= threshold && threshold as usize <= count {
+        Ok(())
+    } else {
+        Err(OperationError::InvalidThresholdParameters)
+    }
+}
+
+pub(crate) fn check_indices(indices: impl Iterator<Item = u16>) -> Result<(), OperationError> {
+    let mut indices: Vec<u16> = indices.collect();
+    indices.sort_unstable();
+    if indices.first() == Some(&0) || indices.windows(2).any(|w| w[0] == w[1]) {
+        Err(OperationError::InvalidParticipantIdentifier)
+    } else {
+        Ok(())
+    }
+}
+
+/// The Lagrange coefficients λᵢ = Πⱼ≠ᵢ xⱼ / (xⱼ - xᵢ) that interpolate a
+/// polynomial at 0 from its values at the distinct, nonzero `indices`.
+pub(crate) fn lagrange_coefficients(indices: &[u16]) -> Vec<Scalar> {
+    let xs: Vec<Scalar> = indices.iter().map(|&i| Scalar::from(i as u64)).collect();
+    (0..xs.len())
+        .map(|i| {
+            let (num, den) = xs.iter().enumerate().filter(|(j, _)| *j != i).fold(
+                (Scalar::from(1_u64), Scalar::from(1_u64)),
+                |(num, den), (_, x_j)| (num.mul_mod(x_j), den.mul_mod(&x_j.sub_mod(&xs[i]))),
+            );
+            // indices are distinct and nonzero, so den is invertible
+            num.mul_mod(&scalar_invert(&den).unwrap())
+        })
+        .collect()
+}
+
+pub(crate) fn random_polynomial(constant: &Scalar, threshold: u16) -> Vec<Scalar> {
+    let mut f = vec![*constant];
+    f.extend((1..threshold).map(|_| random_scalar()));
+    f
+}
+
+pub(crate) fn zeroize_polynomial(f: &mut [Scalar]) {
+    for a in f.iter_mut() {
+        a.val.zeroize();
+    }
+}
+
+fn scalar_shares(f: &[Scalar], count: u16) -> Vec<ScalarShare> {
+    (1..=count)
+        .map(|i| ScalarShare {
+            index: i,
+            value: SecretScalar::new(evaluate(f, i)),
+        })
+        .collect()
+}
+
+pub(crate) fn evaluate(f: &[Scalar], index: u16) -> Scalar {
+    let x = Scalar::from(index as u64);
+    f.iter()
+        .rev()
+        .fold(Scalar::from(0_u64), |acc, a| acc.mul_mod(&x).add_mod(a))
+}
+
+fn evaluate_commitment(commitment: &[ExtendedPoint], index: u16) -> ExtendedPoint {
+    let x = Scalar::from(index as u64);
+    commitment
+        .iter()
+        .rev()
+        .fold(ExtendedPoint::id_point(), |acc, c| acc * x + *c)
+}
+
+fn gf_evaluate(f: &[u8], x: u8) -> u8 {
+    f.iter().rev().fold(0u8, |acc, &a| gf_mul(acc, x) ^ a)
+}
+
+// Multiplication in GF(2⁸) modulo x⁸ + x⁴ + x³ + x + 1, without secret-dependent branches.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    for _ in 0..8 {
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        let carry = 0u8.wrapping_sub(a >> 7);
+        a = (a << 1) ^ (carry & 0x1b);
+        b >>= 1;
+    }
+    product
+}
+
+// a⁻¹ = a²⁵⁴
+fn gf_inv(a: u8) -> u8 {
+    let mut result = 1u8;
+    let mut base = a;
+    let mut e = 254u8;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = gf_mul(result, base);
+        }
+        base = gf_mul(base, base);
+        e >>= 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod shamir_tests {
+    use super::{gf_inv, gf_mul};
+
+    #[test]
+    fn gf256_arithmetic() {
+        // FIPS 197, Section 4.2
+        assert_eq!(gf_mul(0x57, 0x83), 0xc1);
+        assert_eq!(gf_mul(0x57, 0x13), 0xfe);
+        assert_eq!(gf_inv(0x53), 0xca);
+        for a in 1..=255u8 {
+            assert_eq!(gf_mul(a, gf_inv(a)), 1);
+        }
+    }
+}
@@ -1,7 +1,10 @@
-use super::keypair::KeyPair;
+use super::{
+    edwards448::{find_invalid, multiscalar_mul, scalar_from_bytes_wide, scalar_negate},
+    keypair::KeyPair,
+};
 use crate::{
     sha3::{
-        aux_functions::byte_utils::{bytes_to_scalar, scalar_to_bytes},
+        aux_functions::byte_utils::{bytes_to_scalar, get_random_bytes, scalar_to_bytes},
         shake_functions::kmac_xof,
     },
     Message, OperationError, SecParam,
@@ -9,6 +12,10 @@ use crate::{
 use serde::{Deserialize, Serialize};
 use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
 
+/// The legacy capyCRYPT Schnorr signature scheme. Its signatures can only be
+/// verified by this crate; use [`eddsa`](crate::ecc::eddsa) for RFC 8032 Ed448.
+///
+/// Many signatures can be checked at once with [`verify_batch`].
 pub trait Signable {
     fn sign(&mut self, key: &KeyPair, d: SecParam);
     fn verify(&mut self, pub_key: &ExtendedPoint) -> Result<(), OperationError>;
@@ -20,15 +27,19 @@ pub struct Signature {
     /// keyed hash of signed message
     pub h: Vec<u8>,
     /// public nonce
+    #[serde(with = "super::edwards448::serde_scalar")]
     pub z: Scalar,
+    /// nonce point 𝑈 = 𝑍*𝑮 + h*𝑉, which lets [`verify_batch`] check the
+    /// signature without recomputing it. Not needed by [`Signable::verify`].
+    #[serde(default, with = "super::edwards448::serde_point_option")]
+    pub u: Option<ExtendedPoint>,
 }
 
 impl Signable for Message {
     /// # Schnorr Signatures
-    /// Signs a [`Message`] under passphrase pw.
+    /// Signs a [`Message`] under the secret scalar s of a [`KeyPair`].
     ///
     /// ## Algorithm:
-    /// * `s` ← kmac_xof(pw, “”, 448, “K”); s ← 4s
     /// * `k` ← kmac_xof(s, m, 448, “N”); k ← 4k
     /// * `𝑈` ← k*𝑮;
     /// * `ℎ` ← kmac_xof(𝑈ₓ , m, 448, “T”); 𝑍 ← (𝑘 – ℎ𝑠) mod r
@@ -38,21 +49,17 @@ impl Signable for Message {
     /// * d: u64: encryption security strength in bits. Can only be 224, 256, 384, or 512.
     #[allow(non_snake_case)]
     fn sign(&mut self, key: &KeyPair, d: SecParam) {
-        let s_bytes = kmac_xof(&key.priv_key, &[], 448, "SK", d);
-        let s = bytes_to_scalar(&s_bytes).mul_mod(&Scalar::from(4_u64));
+        let s = *key.priv_key.expose();
         let s_bytes = scalar_to_bytes(&s);
 
         let k_bytes = kmac_xof(&s_bytes, &self.msg, 448, "N", d);
         let k = bytes_to_scalar(&k_bytes) * Scalar::from(4_u64);
 
         let U = ExtendedPoint::generator() * k;
-        let ux_bytes = U.to_affine().x.to_bytes();
-
-        let h = kmac_xof(&ux_bytes, &self.msg, 448, "T", d);
-        let h_big = bytes_to_scalar(&h);
+        let h = challenge(&U, &self.msg, d);
 
-        let z = k - h_big.mul_mod(&s);
-        self.sig = Some(Signature { h, z });
+        let z = k - reduce(&h).mul_mod(&s);
+        self.sig = Some(Signature { h, z, u: Some(U) });
         self.d = Some(d);
     }
 
@@ -68,20 +75,114 @@ impl Signable for Message {
     /// ## Assumes:
     /// * Some(key.pub_key)
     /// * Some([`Message`].sig)
-    #[allow(non_snake_case)]
     fn verify(&mut self, pub_key: &ExtendedPoint) -> Result<(), OperationError> {
         let sig = self.sig.as_ref().ok_or(OperationError::SignatureNotSet)?;
         let d = self.d.ok_or(OperationError::SecurityParameterNotSet)?;
 
-        let h_scalar = bytes_to_scalar(&sig.h);
-        let U = ExtendedPoint::generator() * sig.z + (*pub_key * h_scalar);
-
-        let h_p = kmac_xof(&U.to_affine().x.to_bytes(), &self.msg, 448, "T", d);
-
-        if h_p == sig.h {
+        if verify_signature(&self.msg, sig, pub_key, d) {
             Ok(())
         } else {
             Err(OperationError::SignatureVerificationFailure)
         }
     }
 }
+
+/// # Schnorr Batch Verification
+/// Verifies many (message, signature, public key) triples signed with the same
+/// [`SecParam`] at once, accepting exactly when [`Signable::verify`] accepts
+/// every item, except with probability 2⁻¹²⁸.
+/// ## Algorithm:
+/// * check hᵢ = kmac_xof(𝑈ᵢₓ, mᵢ, 448, “T”) with the nonce point 𝑈ᵢ carried by
+///   the signature
+/// * cᵢ ← Random(128)
+/// * accept iff (Σ cᵢ𝑍ᵢ)*𝑮 + Σ (cᵢhᵢ)*𝑉ᵢ - Σ cᵢ*𝑈ᵢ = 𝒪, computed with one
+///   [`multiscalar_mul`] over 2n + 1 points
+/// * if a batch is rejected, its halves are checked the same way until the
+///   failing items are isolated, and each of those is checked with
+///   [`Signable::verify`]
+///
+/// Signatures without 𝑈, such as those deserialized from the format that
+/// predates it, are checked with [`Signable::verify`] directly.
+/// ## Errors:
+/// * [`OperationError::BatchVerificationFailure`] with the sorted indices of
+///   all items that do not verify.
+pub fn verify_batch(
+    items: &[(&[u8], &Signature, &ExtendedPoint)],
+    d: SecParam,
+) -> Result<(), OperationError> {
+    let mut invalid = Vec::new();
+    let mut entries = Vec::with_capacity(items.len());
+    for (index, (msg, sig, pub_key)) in items.iter().enumerate() {
+        match sig.u {
+            Some(u) if challenge(&u, msg, d) == sig.h => entries.push(BatchEntry {
+                index,
+                u,
+                z: sig.z,
+                h: reduce(&sig.h),
+                v: **pub_key,
+            }),
+            _ if verify_signature(msg, sig, pub_key, d) => {}
+            _ => invalid.push(index),
+        }
+    }
+
+    // A signature whose 𝑈 is wrong may still verify on its own
+    invalid.extend(
+        find_invalid(&entries, &batch_equation_holds)
+            .into_iter()
+            .map(|entry| entry.index)
+            .filter(|&i| !verify_signature(items[i].0, items[i].1, items[i].2, d)),
+    );
+    if invalid.is_empty() {
+        Ok(())
+    } else {
+        invalid.sort_unstable();
+        Err(OperationError::BatchVerificationFailure(invalid))
+    }
+}
+
+// A batch item with its challenge h reduced mod r. Public keys and nonce points
+// lie in the prime-order subgroup, so the batch equation holds exactly when
+// 𝑈 = 𝑍*𝑮 + h*𝑉 for every item.
+struct BatchEntry {
+    index: usize,
+    u: ExtendedPoint,
+    z: Scalar,
+    h: Scalar,
+    v: ExtendedPoint,
+}
+
+fn batch_equation_holds(entries: &[BatchEntry]) -> bool {
+    let mut z_sum = Scalar::from(0_u64);
+    let mut scalars = Vec::with_capacity(2 * entries.len() + 1);
+    let mut points = Vec::with_capacity(2 * entries.len() + 1);
+    for entry in entries {
+        let c = scalar_from_bytes_wide(&get_random_bytes(16));
+        z_sum = z_sum.add_mod(&c.mul_mod(&entry.z));
+        scalars.push(c.mul_mod(&entry.h));
+        points.push(entry.v);
+        scalars.push(scalar_negate(&c));
+        points.push(entry.u);
+    }
+    scalars.push(z_sum);
+    points.push(ExtendedPoint::generator());
+    multiscalar_mul(&scalars, &points) == ExtendedPoint::id_point()
+}
+
+// 𝑈 ← 𝑍*𝑮 + h*𝑉, accept iff h = kmac_xof(𝑈ₓ, m, 448, “T”)
+#[allow(non_snake_case)]
+fn verify_signature(msg: &[u8], sig: &Signature, pub_key: &ExtendedPoint, d: SecParam) -> bool {
+    let U = ExtendedPoint::generator() * sig.z + (*pub_key * bytes_to_scalar(&sig.h));
+    challenge(&U, msg, d) == sig.h
+}
+
+/// h ← kmac_xof(𝑈ₓ, m, 448, “T”), the challenge of [`Signable::sign`] for the
+/// nonce point 𝑈.
+pub(crate) fn challenge(u: &ExtendedPoint, msg: &[u8], d: SecParam) -> Vec<u8> {
+    kmac_xof(&u.to_affine().x.to_bytes(), msg, 448, "T", d)
+}
+
+/// h as a scalar, reduced mod r by the multiplication.
+pub(crate) fn reduce(h: &[u8]) -> Scalar {
+    bytes_to_scalar(h).mul_mod(&Scalar::from(1_u64))
+}
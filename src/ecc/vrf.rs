@@ -0,0 +1,213 @@
+//! ECVRF verifiable random function on edwards448, following RFC 9381.
+//!
+//! The holder of a [`KeyPair`](crate::ecc::keypair::KeyPair) maps an input α to
+//! a pseudorandom output β together with a proof π. Anyone with the public key
+//! can check π and recompute β from it, yet nobody without the secret key can
+//! predict β, and the key holder cannot produce a second valid output for the
+//! same α. This makes β suitable for leader election and lottery draws.
+//!
+//! RFC 9381 does not define a suite for edwards448. This module instantiates
+//! the ECVRF-EDWARDS25519-SHA512-ELL2 construction with the edwards448
+//! parameters instead:
+//! * suite_string "ECVRF-EDWARDS448-SHAKE256-ELL2". RFC 9381 assigns
+//!   single-byte suite strings, so a multi-byte name cannot collide with any
+//!   of its suites.
+//! * hashing to the curve with
+//!   [`encode_to_curve`](crate::ecc::hash_to_curve::encode_to_curve) of the
+//!   suite `edwards448_XOF:SHAKE256_ELL2_NU_`
+//! * SHAKE256 with 114 bytes of output as the hash function, as in Ed448
+//! * the nonce generation of RFC 9381 5.4.2.2 with the 57-byte encoding of the
+//!   secret scalar x as the secret key
+//! * challenges of cLen = 28 bytes, points of ptLen = 57 bytes and scalars of
+//!   qLen = 57 bytes, giving proofs of 142 bytes
+use super::{
+    edwards448::{
+        decode_point, encode_point, scalar_from_bytes_wide, scalar_from_canonical_bytes,
+        scalar_to_bytes_le, POINT_BYTES, SCALAR_BYTES,
+    },
+    hash_to_curve::encode_to_curve,
+    keypair::KeyPair,
+};
+use crate::{sha3::shake_functions::shake256, OperationError};
+use crypto_bigint::U448;
+use serde::{Deserialize, Serialize};
+use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+use zeroize::Zeroize;
+
+/// Identifies the ECVRF-EDWARDS448-SHAKE256-ELL2 suite in every hash.
+const SUITE_STRING: &[u8] = b"ECVRF-EDWARDS448-SHAKE256-ELL2";
+/// Domain separation tag of the hash to curve, "ECVRF_" || h2c_suite_ID_string || suite_string.
+const ENCODE_TO_CURVE_DST: &[u8] =
+    b"ECVRF_edwards448_XOF:SHAKE256_ELL2_NU_ECVRF-EDWARDS448-SHAKE256-ELL2";
+/// Length in bytes of the challenge c.
+const CHALLENGE_BYTES: usize = 28;
+/// Length in bytes of the hash function output and of β.
+pub const OUTPUT_BYTES: usize = 114;
+/// Length in bytes of an encoded proof π = Γ || c || s.
+pub const PROOF_BYTES: usize = POINT_BYTES + CHALLENGE_BYTES + SCALAR_BYTES;
+
+/// A VRF proof π = (Γ, c, s) for one input.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct VrfProof {
+    /// Γ = x*H, where H is the input hashed to the curve
+    #[serde(with = "super::edwards448::serde_point")]
+    pub gamma: ExtendedPoint,
+    /// Challenge c, an integer of 28 bytes
+    #[serde(with = "super::edwards448::serde_scalar")]
+    pub c: Scalar,
+    /// Response s = k + c*x
+    #[serde(with = "super::edwards448::serde_scalar")]
+    pub s: Scalar,
+}
+
+/// # ECVRF Proving
+/// Computes the proof π for `alpha` under `key_pair`, following RFC 9381 5.1.
+/// The proof is deterministic, so proving the same input twice yields the same
+/// proof.
+/// ## Algorithm:
+/// * H ← encode_to_curve(𝑌 || α), where 𝑌 is the public key
+/// * Γ ← x*H
+/// * k ← SHAKE256(SHAKE256(x, 114)[57..114] || H, 114) mod r, RFC 9381 5.4.2.2
+/// * c ← challenge(𝑌, H, Γ, k*𝑮, k*H)
+/// * s ← (k + c*x) mod r
+/// ## Arguments:
+/// * key_pair: &[`KeyPair`] : the prover's key pair (x, 𝑌)
+/// * alpha: &[u8] : the VRF input
+/// ## Returns:
+/// * return -> [`VrfProof`]: the proof π, from which
+///   [`VrfProof::proof_to_hash`] yields the output β.
+pub fn prove(key_pair: &KeyPair, alpha: &[u8]) -> VrfProof {
+    let x = key_pair.priv_key.expose();
+    let h = hash_to_curve(&key_pair.pub_key, alpha);
+    let gamma = h * *x;
+
+    let k = nonce(x, &h);
+    let c = challenge(&[
+        key_pair.pub_key,
+        h,
+        gamma,
+        ExtendedPoint::generator() * k,
+        h * k,
+    ]);
+    let s = k.add_mod(&c.mul_mod(x));
+    VrfProof { gamma, c, s }
+}
+
+/// # ECVRF Verification
+/// Checks the proof π for `alpha` under the public key 𝑌 and returns the
+/// VRF output β, following RFC 9381 5.3.
+/// ## Algorithm:
+/// * reject 𝑌 = 𝒪
+/// * H ← encode_to_curve(𝑌 || α)
+/// * 𝑈 ← s*𝑮 - c*𝑌, 𝑉 ← s*H - c*Γ
+/// * accept iff c = challenge(𝑌, H, Γ, 𝑈, 𝑉)
+/// ## Arguments:
+/// * pub_key: &ExtendedPoint : the prover's public key 𝑌
+/// * alpha: &[u8] : the VRF input
+/// * proof: &[`VrfProof`] : the proof π
+/// ## Returns:
+/// * return -> Result<[u8; 114], OperationError>: β = proof_to_hash(π)
+/// ## Errors:
+/// * [`OperationError::ProofVerificationFailure`] if 𝑌 is the identity or the
+///   proof does not verify.
+pub fn verify(
+    pub_key: &ExtendedPoint,
+    alpha: &[u8],
+    proof: &VrfProof,
+) -> Result<[u8; OUTPUT_BYTES], OperationError> {
+    if *pub_key == ExtendedPoint::id_point() {
+        return Err(OperationError::ProofVerificationFailure);
+    }
+    let h = hash_to_curve(pub_key, alpha);
+    let u = ExtendedPoint::generator() * proof.s + (*pub_key * proof.c).negate();
+    let v = h * proof.s + (proof.gamma * proof.c).negate();
+    if challenge(&[*pub_key, h, proof.gamma, u, v]) == proof.c {
+        Ok(proof.proof_to_hash())
+    } else {
+        Err(OperationError::ProofVerificationFailure)
+    }
+}
+
+impl VrfProof {
+    /// # ECVRF Proof to Hash
+    /// Derives the VRF output β from a proof, following RFC 9381 5.2. Only call
+    /// this on proofs that [`verify`] accepted, or use the output returned by
+    /// [`verify`] directly.
+    /// ## Algorithm:
+    /// * β ← SHAKE256(suite_string || 0x03 || 4*Γ || 0x00, 114)
+    pub fn proof_to_hash(&self) -> [u8; OUTPUT_BYTES] {
+        let mut input = SUITE_STRING.to_vec();
+        input.push(0x03);
+        input.extend_from_slice(&encode_point(&self.gamma.double().double()));
+        input.push(0x00);
+        shake256(&input, 8 * OUTPUT_BYTES).try_into().unwrap()
+    }
+
+    /// Decodes the 142-byte encoding Γ || c || s of a proof.
+    /// ## Errors:
+    /// * [`OperationError::InvalidProofLength`] if `bytes` is not 142 bytes long.
+    /// * [`OperationError::InvalidPointEncoding`] if Γ does not decode.
+    /// * [`OperationError::InvalidScalarEncoding`] unless s is reduced modulo r.
+    pub fn from_bytes(bytes: &[u8]) -> Result<VrfProof, OperationError> {
+        if bytes.len() != PROOF_BYTES {
+            return Err(OperationError::InvalidProofLength);
+        }
+        let (gamma, rest) = bytes.split_at(POINT_BYTES);
+        let (c, s) = rest.split_at(CHALLENGE_BYTES);
+        Ok(VrfProof {
+            gamma: decode_point(gamma)?,
+            c: challenge_to_scalar(c),
+            s: scalar_from_canonical_bytes(s)?,
+        })
+    }
+
+    /// Encodes the proof as Γ || c || s in 142 bytes.
+    pub fn to_bytes(&self) -> [u8; PROOF_BYTES] {
+        let mut out = [0u8; PROOF_BYTES];
+        out[..POINT_BYTES].copy_from_slice(&encode_point(&self.gamma));
+        out[POINT_BYTES..POINT_BYTES + CHALLENGE_BYTES]
+            .copy_from_slice(&scalar_to_bytes_le(&self.c)[..CHALLENGE_BYTES]);
+        out[POINT_BYTES + CHALLENGE_BYTES..].copy_from_slice(&scalar_to_bytes_le(&self.s));
+        out
+    }
+}
+
+/// H ← encode_to_curve(𝑌 || α) with the public key as salt, RFC 9381 5.4.1.2.
+fn hash_to_curve(pub_key: &ExtendedPoint, alpha: &[u8]) -> ExtendedPoint {
+    let mut input = encode_point(pub_key).to_vec();
+    input.extend_from_slice(alpha);
+    encode_to_curve(&input, ENCODE_TO_CURVE_DST)
+}
+
+/// k ← SHAKE256(truncated_hashed_sk_string || H, 114) mod r, RFC 9381 5.4.2.2,
+/// where truncated_hashed_sk_string is the second half of SHAKE256(x, 114)
+/// like the prefix of an RFC 8032 Ed448 key.
+fn nonce(x: &Scalar, h: &ExtendedPoint) -> Scalar {
+    let mut sk_string = scalar_to_bytes_le(x);
+    let mut hashed_sk_string = shake256(&sk_string, 8 * OUTPUT_BYTES);
+    let mut k_input = hashed_sk_string[SCALAR_BYTES..].to_vec();
+    k_input.extend_from_slice(&encode_point(h));
+    let k = scalar_from_bytes_wide(&shake256(&k_input, 8 * OUTPUT_BYTES));
+    sk_string.zeroize();
+    hashed_sk_string.zeroize();
+    k_input.zeroize();
+    k
+}
+
+/// c ← SHAKE256(suite_string || 0x02 || P₁ || … || P₅ || 0x00)[..28], RFC 9381 5.4.3.
+fn challenge(points: &[ExtendedPoint; 5]) -> Scalar {
+    let mut input = SUITE_STRING.to_vec();
+    input.push(0x02);
+    for p in points {
+        input.extend_from_slice(&encode_point(p));
+    }
+    input.push(0x00);
+    challenge_to_scalar(&shake256(&input, 8 * CHALLENGE_BYTES))
+}
+
+/// Reads a 28-byte little-endian challenge, which is always smaller than r.
+fn challenge_to_scalar(bytes: &[u8]) -> Scalar {
+    let mut wide = [0u8; 56];
+    wide[..CHALLENGE_BYTES].copy_from_slice(bytes);
+    Scalar::from(U448::from_le_slice(&wide))
+}
@@ -0,0 +1,244 @@
+//! X448 Diffie–Hellman key agreement as specified in RFC 7748.
+//!
+//! Public keys are u-coordinates on curve448. Curve448 is 4-isogenous to the
+//! edwards448 curve of RFC 8032 through ι(x, y) = (y²/x², ...), which sends the
+//! RFC 8032 base point to u = 5. Edwards public keys of this crate therefore
+//! convert to X448 public keys of the same secret scalar, see
+//! [`X448PublicKey::from_edwards`](crate::ecc::x448::X448PublicKey::from_edwards) and
+//! [`edwards_diffie_hellman`](crate::ecc::x448::edwards_diffie_hellman).
+use super::edwards448::{
+    dual_isogeny, encode_point, is_equal, isogeny, pow_p34, INV_FOUR, POINT_BYTES,
+};
+use crate::{sha3::aux_functions::byte_utils::get_random_bytes, OperationError};
+use crypto_bigint::{
+    subtle::{Choice, ConditionallySelectable},
+    Encoding,
+};
+use tiny_ed448_goldilocks::curve::{
+    extended_edwards::ExtendedPoint,
+    field::{field_element::FieldElement, scalar::Scalar},
+};
+use zeroize::Zeroize;
+
+/// Length in bytes of X448 scalars, u-coordinates and shared secrets.
+pub const X448_BYTES: usize = 56;
+/// The u-coordinate of the curve448 base point.
+pub const X448_BASEPOINT: [u8; X448_BYTES] = {
+    let mut u = [0u8; X448_BYTES];
+    u[0] = 5;
+    u
+};
+
+/// An X448 secret key: a 56-byte string that is clamped on use.
+#[derive(Clone)]
+pub struct X448SecretKey([u8; X448_BYTES]);
+
+/// An X448 public key: the u-coordinate of a point on curve448.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct X448PublicKey([u8; X448_BYTES]);
+
+impl X448SecretKey {
+    /// Wraps a 56-byte X448 secret key.
+    /// ## Errors:
+    /// * [`OperationError::InvalidSecretLength`] unless `bytes` is 56 bytes long.
+    pub fn from_bytes(bytes: &[u8]) -> Result<X448SecretKey, OperationError> {
+        bytes
+            .try_into()
+            .map(X448SecretKey)
+            .map_err(|_| OperationError::InvalidSecretLength)
+    }
+
+    /// Generates a random X448 secret key.
+    pub fn generate() -> X448SecretKey {
+        Self::from_bytes(&get_random_bytes(X448_BYTES as u64)).unwrap()
+    }
+
+    /// The raw 56-byte secret key.
+    pub fn to_bytes(&self) -> [u8; X448_BYTES] {
+        self.0
+    }
+
+    /// The public key X448(k, 5).
+    pub fn public_key(&self) -> X448PublicKey {
+        X448PublicKey(x448(&self.0, &X448_BASEPOINT))
+    }
+
+    /// # X448 Key Agreement
+    /// Computes the shared secret X448(k, u) with the peer's public key.
+    /// ## Errors:
+    /// * [`OperationError::NonContributoryKeyAgreement`] if the shared secret is
+    ///   all zeros, which happens exactly when `their_public` has small order.
+    pub fn diffie_hellman(
+        &self,
+        their_public: &X448PublicKey,
+    ) -> Result<[u8; X448_BYTES], OperationError> {
+        check_contributory(x448(&self.0, &their_public.0))
+    }
+}
+
+impl Drop for X448SecretKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl X448PublicKey {
+    /// Wraps a 56-byte u-coordinate.
+    /// ## Errors:
+    /// * [`OperationError::InvalidPointEncoding`] unless `bytes` is 56 bytes long.
+    pub fn from_bytes(bytes: &[u8]) -> Result<X448PublicKey, OperationError> {
+        bytes
+            .try_into()
+            .map(X448PublicKey)
+            .map_err(|_| OperationError::InvalidPointEncoding)
+    }
+
+    /// The 56-byte u-coordinate.
+    pub fn to_bytes(&self) -> [u8; X448_BYTES] {
+        self.0
+    }
+
+    /// # Edwards to X448 Conversion
+    /// Converts an Edwards public key s*𝑮 to the X448 public key of the same
+    /// secret scalar s.
+    /// ## Algorithm:
+    /// * (x, y) ← ψ(4⁻¹ * P), the RFC 8032 point s*B
+    /// * u ← y² / x²
+    pub fn from_edwards(p: &ExtendedPoint) -> X448PublicKey {
+        let (x, y) = dual_isogeny(&(*p * Scalar::from(INV_FOUR)));
+        let u = y.square() * x.square().invert();
+        X448PublicKey(u.to_bytes())
+    }
+
+    /// # X448 to Edwards Conversion
+    /// Converts an X448 public key back to an Edwards public key. A u-coordinate
+    /// only determines a point up to sign, so `sign` selects the point whose
+    /// RFC 8032 encoding has that x-coordinate sign bit (0 or 1).
+    /// ## Algorithm:
+    /// * v ← √(u³ + 156326u² + u)
+    /// * (x, y) ← ι̂(u, v), the dual of ι given in RFC 7748 4.2, so that ι(x, y) = 4(u, v)
+    /// * P ← 4⁻¹ * φ(x, y)
+    /// ## Errors:
+    /// * [`OperationError::InvalidPointEncoding`] if `u` is not the u-coordinate
+    ///   of a point of the prime-order subgroup of curve448.
+    pub fn to_edwards(&self, sign: u8) -> Result<ExtendedPoint, OperationError> {
+        let u = FieldElement::from_bytes(&self.0);
+        let one = FieldElement::one();
+        let a = FieldElement::from_raw_slice([156326, 0, 0, 0, 0, 0, 0, 0]);
+
+        let uu = u.square();
+        let vv = (uu * u) + (a * uu) + u;
+        let v = vv * pow_p34(&vv);
+        if !is_equal(&v.square(), &vv) {
+            return Err(OperationError::InvalidPointEncoding);
+        }
+
+        // RFC 7748 4.2 birational map from curve448 to edwards448
+        let two = one + one;
+        let four = two + two;
+        let u3 = uu * u;
+        let u5 = u3 * uu;
+        let x_num = four * v * (uu - one);
+        let x_den = uu.square() - two * uu + four * vv + one;
+        let y_num = (u5 - two * u3 - four * u * vv + u).negate();
+        let y_den = u5 - two * uu * vv - two * u3 - two * vv + u;
+        let den = x_den * y_den;
+        if is_equal(&den, &FieldElement::zero()) {
+            return Err(OperationError::InvalidPointEncoding);
+        }
+        let inv = den.invert();
+        let x = x_num * y_den * inv;
+        let y = y_num * x_den * inv;
+
+        let mut p = isogeny(&x, &y) * Scalar::from(INV_FOUR);
+        if is_negative_encoding(&p) != (sign & 1) {
+            p = p.negate();
+        }
+        // Points with a small-order component do not survive the round trip
+        if p == ExtendedPoint::id_point() || Self::from_edwards(&p) != *self {
+            return Err(OperationError::InvalidPointEncoding);
+        }
+        Ok(p)
+    }
+}
+
+/// # X448 Function
+/// The X448 function of RFC 7748 Section 5: clamps the scalar `k` and runs
+/// the constant-time Montgomery ladder on the u-coordinate `u`.
+pub fn x448(k: &[u8; X448_BYTES], u: &[u8; X448_BYTES]) -> [u8; X448_BYTES] {
+    let mut k = *k;
+    k[0] &= 252;
+    k[55] |= 128;
+    ladder(&k, &FieldElement::from_bytes(u)).to_bytes()
+}
+
+/// # Edwards Key Agreement
+/// Computes the X448 shared secret for the Edwards secret scalar `s`, as
+/// held by a [`KeyPair`](crate::ecc::keypair::KeyPair), with the peer's X448
+/// public key. The result equals the peer's [`X448SecretKey::diffie_hellman`]
+/// with [`X448PublicKey::from_edwards`] of our public key.
+///
+/// s is not clamped; instead the ladder runs on t = 4 * (4⁻¹s mod r), which
+/// agrees with s on the prime-order subgroup and clears any small-order
+/// component of `their_public`, as clamping does.
+/// ## Errors:
+/// * [`OperationError::NonContributoryKeyAgreement`] if the shared secret is all zeros.
+pub fn edwards_diffie_hellman(
+    s: &Scalar,
+    their_public: &X448PublicKey,
+) -> Result<[u8; X448_BYTES], OperationError> {
+    let t = s.mul_mod(&Scalar::from(INV_FOUR)).val << 2;
+    let mut k = [0u8; X448_BYTES];
+    k.copy_from_slice(&t.to_le_bytes());
+    check_contributory(ladder(&k, &FieldElement::from_bytes(&their_public.0)).to_bytes())
+}
+
+// RFC 7748 Section 5 Montgomery ladder over all 448 bits of k.
+fn ladder(k: &[u8; X448_BYTES], u: &FieldElement) -> FieldElement {
+    let a24 = FieldElement::from_raw_slice([39081, 0, 0, 0, 0, 0, 0, 0]);
+    let x_1 = *u;
+    let mut x_2 = FieldElement::one();
+    let mut z_2 = FieldElement::zero();
+    let mut x_3 = *u;
+    let mut z_3 = FieldElement::one();
+    let mut swap = 0u8;
+
+    for t in (0..448).rev() {
+        let k_t = (k[t / 8] >> (t % 8)) & 1;
+        swap ^= k_t;
+        FieldElement::conditional_swap(&mut x_2, &mut x_3, Choice::from(swap));
+        FieldElement::conditional_swap(&mut z_2, &mut z_3, Choice::from(swap));
+        swap = k_t;
+
+        let a = x_2 + z_2;
+        let aa = a.square();
+        let b = x_2 - z_2;
+        let bb = b.square();
+        let e = aa - bb;
+        let c = x_3 + z_3;
+        let d = x_3 - z_3;
+        let da = d * a;
+        let cb = c * b;
+        x_3 = (da + cb).square();
+        z_3 = x_1 * (da - cb).square();
+        x_2 = aa * bb;
+        z_2 = e * (aa + a24 * e);
+    }
+    FieldElement::conditional_swap(&mut x_2, &mut x_3, Choice::from(swap));
+    FieldElement::conditional_swap(&mut z_2, &mut z_3, Choice::from(swap));
+
+    x_2 * z_2.invert()
+}
+
+fn check_contributory(shared: [u8; X448_BYTES]) -> Result<[u8; X448_BYTES], OperationError> {
+    if shared == [0u8; X448_BYTES] {
+        Err(OperationError::NonContributoryKeyAgreement)
+    } else {
+        Ok(shared)
+    }
+}
+
+// Sign bit of the RFC 8032 encoding of the point represented by p.
+fn is_negative_encoding(p: &ExtendedPoint) -> u8 {
+    encode_point(p)[POINT_BYTES - 1] >> 7
+}
@@ -30,7 +30,7 @@ impl KEMEncryptable for Message {
     /// * `Message.sym_nonce` with random bytes 𝑧.
     /// ## Algorithm:
     /// * Encrypt a secret using the KEM public key 𝑉 to generate
-    /// shared secret.
+    ///   shared secret.
     /// * Generate a random nonce 𝑧
     /// * (ke || ka) ← kmac_xof(𝑧 || secret, "", 1024, "S")
     /// * 𝑐 ← kmac_xof(ke, "", |m|, "SKE") ⊕ m
@@ -1,4 +1,5 @@
 #![warn(clippy::just_underscores_and_digits)]
+use aes::aes_functions::AesKeySize;
 use ecc::signable::Signature;
 use serde::{Deserialize, Serialize};
 use std::{fs::File, io::Read};
@@ -23,12 +24,68 @@ pub enum OperationError {
     SignatureNotSet,
     UnsupportedCapacity,
     AESCTRDecryptionFailure,
+    AESCTRCounterOverflow,
+    AESCTRRangeOutOfBounds,
+    InvalidNonceLength,
+    UnsupportedAESKeyLength,
+    AESKeySizeMismatch,
+    AESKeySizeNotSet,
+    AESCBCDecryptionFailure,
+    InvalidPointEncoding,
+    InvalidScalarEncoding,
+    InvalidSignatureLength,
+    ContextTooLong,
+    NonContributoryKeyAgreement,
+    UnsupportedKeystoreVersion,
+    KeystorePublicKeyMismatch,
+    KdfIterationsTooLarge,
+    InvalidDerEncoding,
+    InvalidPemEncoding,
+    UnsupportedKeyAlgorithm,
+    InvalidDerivationPath,
+    HardenedPublicDerivation,
+    DerivedKeyInvalid,
+    BatchVerificationFailure(Vec<usize>),
+    InvalidKeyList,
+    SignerNotInKeyList,
+    NonceKeyMismatch,
+    InvalidThresholdParameters,
+    InvalidParticipantIdentifier,
+    UnknownParticipant,
+    InsufficientSigners,
+    IncorrectNumberOfPackages,
+    InvalidSecretShare(u16),
+    InvalidProofOfKnowledge(u16),
+    InvalidSignatureShare(u16),
+    InconsistentShares,
+    ExpandLengthTooLarge,
     SecretNotSet,
     InvalidSecretLength,
+    InvalidProofLength,
+    ProofVerificationFailure,
+    InvalidOpening,
+    UnsupportedRangeSize,
+    ValueOutOfRange,
+    DiscreteLogNotFound,
+    DiscreteLogRangeTooLarge,
+    InvalidDecryptionShare(u16),
+    InvalidOprfInput,
+    PakeConfirmationFailure,
+    BlindSessionInProgress,
+    NoBlindSession,
+    InvalidAdaptorSecret,
     DecapsulationFailure,
     KEMError,
 }
 
+impl std::fmt::Display for OperationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for OperationError {}
+
 /// Module for SHA-3 primitives
 pub mod sha3 {
     /// Submodule that implements NIST 800-185 compliant functions
@@ -46,13 +103,55 @@ pub mod sha3 {
 pub mod aes {
     pub mod aes_constants;
     pub mod aes_functions;
+    /// Submodule that implements the AES-NI hardware backend
+    pub mod aes_ni;
     pub mod encryptable;
 }
 
 pub mod ecc {
+    /// Submodule that implements Schnorr adaptor signatures
+    pub mod adaptor;
+    /// Submodule that implements blind Schnorr signatures
+    pub mod blind_schnorr;
+    /// Submodule that implements RFC 8032 Ed448 and Ed448ph signatures
+    pub mod eddsa;
+    /// Submodule that maps between the twisted curve and RFC 8032 encodings
+    pub mod edwards448;
+    /// Submodule that implements additively homomorphic exponential ElGamal
+    pub mod elgamal;
     pub mod encryptable;
+    /// Submodule that implements FROST threshold signatures
+    pub mod frost;
+    /// Submodule that implements RFC 9380 hashing to edwards448
+    pub mod hash_to_curve;
+    /// Submodule that implements hierarchical deterministic key derivation
+    pub mod hd;
     pub mod keypair;
+    /// Submodule that implements passphrase-encrypted key files
+    pub mod keystore;
+    /// Submodule that implements MuSig2 multi-signatures
+    pub mod musig;
+    /// Submodule that implements Schnorr and Chaum–Pedersen zero-knowledge proofs
+    pub mod nizk;
+    /// Submodule that implements RFC 9497 oblivious pseudorandom functions
+    pub mod oprf;
+    /// Submodule that implements the CPace password-authenticated key exchange
+    pub mod pake;
+    /// Submodule that implements Pedersen commitments and range proofs
+    pub mod pedersen;
+    /// Submodule that implements RFC 8410 PKCS#8, SubjectPublicKeyInfo and PEM encodings
+    pub mod pkcs8;
+    /// Submodule that implements public-key-only export and key fingerprints
+    pub mod public_key;
+    /// Submodule that implements linkable ring signatures
+    pub mod ring;
+    /// Submodule that implements Shamir secret sharing with Feldman and Pedersen commitments
+    pub mod shamir;
     pub mod signable;
+    /// Submodule that implements the ECVRF verifiable random function
+    pub mod vrf;
+    /// Submodule that implements X448 key agreement
+    pub mod x448;
 }
 
 pub mod kem {
@@ -70,13 +169,20 @@ pub struct Message {
     /// Nonce used in symmetric encryption
     pub sym_nonce: Option<Vec<u8>>,
     /// Nonce used in asymmetric encryption
+    #[serde(default, with = "ecc::edwards448::serde_point_option")]
     pub asym_nonce: Option<ExtendedPoint>,
     /// Hash value (also known as message digest)
     pub digest: Vec<u8>,
     /// Schnorr signatures on the input message
     pub sig: Option<Signature>,
+    /// RFC 8032 Ed448 signature on the input message
+    #[serde(default)]
+    pub ed448_sig: Option<Vec<u8>>,
     /// ML-KEM encrypted secret as a byte array
     pub kem_ciphertext: Option<Vec<u8>>,
+    /// AES variant used for symmetric encryption
+    #[serde(default)]
+    pub aes_key_size: Option<AesKeySize>,
 }
 
 impl Message {
@@ -89,7 +195,9 @@ impl Message {
             asym_nonce: None,
             digest: vec![],
             sig: None,
+            ed448_sig: None,
             kem_ciphertext: Some(vec![]),
+            aes_key_size: None,
         }
     }
 
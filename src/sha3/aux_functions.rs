@@ -48,23 +48,17 @@ pub(crate) mod nist_800_185 {
         res
     }
 
-    /// rightEncode function is used to encode bit strings in a way that may be parsed
-    /// unambiguously from the beginning of the string by prepending the encoding of
-    /// the length of the string to the beginning of the string.
-    /// * `return`: left_encode(len(s)) + s
+    /// # NIST SP 800-185 2.3.1
+    /// The right_encode function encodes an integer so that it may be parsed
+    /// unambiguously from the end of a string, by appending the number of
+    /// bytes of its big-endian encoding.
+    /// * `return`: big-endian bytes of `value` (at least one) + their count
     pub(crate) fn right_encode(value: u64) -> Vec<u8> {
-        if value == 0 {
-            return vec![0, 1];
-        }
-        let mut b = Vec::new();
-        b.write_u64::<BigEndian>(value).unwrap();
-        let mut i: u8 = 1;
-        while i < 8 && b[i as usize] == 0 {
-            i += 1;
-        }
-        // Prepend number of encoded bytes
-        b[0] = 9 - i;
-        b[0..(9 - i as usize)].to_vec()
+        let bytes = value.to_be_bytes();
+        let skip = bytes.iter().take_while(|b| **b == 0).count().min(7);
+        let mut res = bytes[skip..].to_vec();
+        res.push((8 - skip) as u8);
+        res
     }
 }
 
@@ -16,6 +16,7 @@ impl SpongeEncryptable for Message {
     /// * `Message.data` with result of encryption.
     /// * `Message.t` with keyed hash of plaintext.
     /// * `Message.sym_nonce` with z, as defined below.
+    ///
     /// SECURITY NOTE: ciphertext length == plaintext length
     /// ## Algorithm:
     /// * z ← Random(512)
@@ -25,7 +26,7 @@ impl SpongeEncryptable for Message {
     /// ## Arguments:
     /// * `pw: &[u8]`: symmetric encryption key, can be blank but shouldnt be
     /// * `d: u64`: requested security strength in bits. Supported
-    /// bitstrengths are 224, 256, 384, or 512.
+    ///   bitstrengths are 224, 256, 384, or 512.
     fn sha3_encrypt(&mut self, pw: &[u8], d: SecParam) {
         self.d = Some(d);
         let z = get_random_bytes(512);
@@ -15,7 +15,7 @@ impl SpongeHashable for Message {
     /// Replaces `Message.digest` with result of operation.
     /// ## Arguments:
     /// * `d: u64`: requested security strength in bits. Supported
-    /// bitstrengths are 224, 256, 384, or 512.
+    ///   bitstrengths are 224, 256, 384, or 512.
     fn compute_sha3_hash(&mut self, d: SecParam) {
         self.digest = shake(&mut self.msg, d)
     }
@@ -29,7 +29,7 @@ impl SpongeHashable for Message {
     /// * `message: &mut Vec<u8>`: message to encrypt
     /// * `s: &mut str`: domain seperation string
     /// * `d: u64`: requested security strength in bits. Supported
-    /// bitstrengths are 224, 256, 384, or 512.
+    ///   bitstrengths are 224, 256, 384, or 512.
     fn compute_tagged_hash(&mut self, pw: &[u8], s: &str, d: SecParam) {
         self.digest = kmac_xof(pw, &self.msg, d.bit_length(), s, d);
     }
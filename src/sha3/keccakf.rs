@@ -1,6 +1,6 @@
 // rol64 func rotates x by y
 fn rotate_left64(x: u64, y: u64) -> u64 {
-    ((x) << (y)) | ((x) >> (64 - (y)))
+    x.rotate_left(y as u32)
 }
 
 /// keccakF1600 applies the Keccak permutation to a 1600b-wide
@@ -60,11 +60,11 @@ pub(crate) fn keccakf_1600(a: &mut [u64; 25]) {
         bc2 = a[2] ^ a[7] ^ a[12] ^ a[17] ^ a[22];
         bc3 = a[3] ^ a[8] ^ a[13] ^ a[18] ^ a[23];
         bc4 = a[4] ^ a[9] ^ a[14] ^ a[19] ^ a[24];
-        d0 = bc4 ^ (bc1 << 1 | bc1 >> 63);
-        d1 = bc0 ^ (bc2 << 1 | bc2 >> 63);
-        d2 = bc1 ^ (bc3 << 1 | bc3 >> 63);
-        d3 = bc2 ^ (bc4 << 1 | bc4 >> 63);
-        d4 = bc3 ^ (bc0 << 1 | bc0 >> 63);
+        d0 = bc4 ^ bc1.rotate_left(1);
+        d1 = bc0 ^ bc2.rotate_left(1);
+        d2 = bc1 ^ bc3.rotate_left(1);
+        d3 = bc2 ^ bc4.rotate_left(1);
+        d4 = bc3 ^ bc0.rotate_left(1);
 
         bc0 = a[0] ^ d0;
         t = a[6] ^ d1;
@@ -151,11 +151,11 @@ pub(crate) fn keccakf_1600(a: &mut [u64; 25]) {
         bc2 = a[2] ^ a[7] ^ a[12] ^ a[17] ^ a[22];
         bc3 = a[3] ^ a[8] ^ a[13] ^ a[18] ^ a[23];
         bc4 = a[4] ^ a[9] ^ a[14] ^ a[19] ^ a[24];
-        d0 = bc4 ^ (bc1 << 1 | bc1 >> 63);
-        d1 = bc0 ^ (bc2 << 1 | bc2 >> 63);
-        d2 = bc1 ^ (bc3 << 1 | bc3 >> 63);
-        d3 = bc2 ^ (bc4 << 1 | bc4 >> 63);
-        d4 = bc3 ^ (bc0 << 1 | bc0 >> 63);
+        d0 = bc4 ^ bc1.rotate_left(1);
+        d1 = bc0 ^ bc2.rotate_left(1);
+        d2 = bc1 ^ bc3.rotate_left(1);
+        d3 = bc2 ^ bc4.rotate_left(1);
+        d4 = bc3 ^ bc0.rotate_left(1);
 
         bc0 = a[0] ^ d0;
         t = a[16] ^ d1;
@@ -242,11 +242,11 @@ pub(crate) fn keccakf_1600(a: &mut [u64; 25]) {
         bc2 = a[2] ^ a[7] ^ a[12] ^ a[17] ^ a[22];
         bc3 = a[3] ^ a[8] ^ a[13] ^ a[18] ^ a[23];
         bc4 = a[4] ^ a[9] ^ a[14] ^ a[19] ^ a[24];
-        d0 = bc4 ^ (bc1 << 1 | bc1 >> 63);
-        d1 = bc0 ^ (bc2 << 1 | bc2 >> 63);
-        d2 = bc1 ^ (bc3 << 1 | bc3 >> 63);
-        d3 = bc2 ^ (bc4 << 1 | bc4 >> 63);
-        d4 = bc3 ^ (bc0 << 1 | bc0 >> 63);
+        d0 = bc4 ^ bc1.rotate_left(1);
+        d1 = bc0 ^ bc2.rotate_left(1);
+        d2 = bc1 ^ bc3.rotate_left(1);
+        d3 = bc2 ^ bc4.rotate_left(1);
+        d4 = bc3 ^ bc0.rotate_left(1);
 
         bc0 = a[0] ^ d0;
         t = a[11] ^ d1;
@@ -333,11 +333,11 @@ pub(crate) fn keccakf_1600(a: &mut [u64; 25]) {
         bc2 = a[2] ^ a[7] ^ a[12] ^ a[17] ^ a[22];
         bc3 = a[3] ^ a[8] ^ a[13] ^ a[18] ^ a[23];
         bc4 = a[4] ^ a[9] ^ a[14] ^ a[19] ^ a[24];
-        d0 = bc4 ^ (bc1 << 1 | bc1 >> 63);
-        d1 = bc0 ^ (bc2 << 1 | bc2 >> 63);
-        d2 = bc1 ^ (bc3 << 1 | bc3 >> 63);
-        d3 = bc2 ^ (bc4 << 1 | bc4 >> 63);
-        d4 = bc3 ^ (bc0 << 1 | bc0 >> 63);
+        d0 = bc4 ^ bc1.rotate_left(1);
+        d1 = bc0 ^ bc2.rotate_left(1);
+        d2 = bc1 ^ bc3.rotate_left(1);
+        d3 = bc2 ^ bc4.rotate_left(1);
+        d4 = bc3 ^ bc0.rotate_left(1);
 
         bc0 = a[0] ^ d0;
         t = a[1] ^ d1;
@@ -31,6 +31,27 @@ pub(crate) fn shake(n: &mut Vec<u8>, d: impl BitLength) -> Vec<u8> {
     sponge_squeeze(&mut sponge_absorb(n, c), d.bit_length(), Rate::from(&d))
 }
 
+/// # SHAKE256
+/// Extendable-output function of NIST FIPS 202 Section 6.2.
+/// ## Arguments:
+/// * `x: &[u8]`: input message
+/// * `l: usize`: requested output length in bits
+/// ## Returns:
+/// * `return  -> Vec<u8>`: SHAKE256 output of length `l` bits
+pub fn shake256(x: &[u8], l: usize) -> Vec<u8> {
+    let mut n = x.to_vec();
+    let bytes_to_pad = RATE_IN_BYTES - n.len() % RATE_IN_BYTES;
+    match bytes_to_pad {
+        1 => n.extend_from_slice(&[0x9F]), // delim suffix
+        _ => n.extend_from_slice(&[0x1F]), // delim suffix
+    }
+    sponge_squeeze(
+        &mut sponge_absorb(&mut n, Capacity::C512),
+        l,
+        Rate::from(&Capacity::C512),
+    )
+}
+
 /// # Customizable SHAKE
 /// Implements FIPS 202 Section 3. Returns: customizable and
 /// domain-seperated length `L` SHA3XOF hash of input string.
@@ -54,7 +75,11 @@ pub(crate) fn cshake(x: &[u8], l: usize, n: &str, s: &str, d: SecParam) -> Vec<u
 
     let mut out = byte_pad(&mut encoded_n, bytepad_w);
     out.extend_from_slice(x);
-    out.push(0x04);
+    let rate_in_bytes = Rate::from(&d).value() / 8;
+    match rate_in_bytes - out.len() % rate_in_bytes {
+        1 => out.push(0x84), // delim suffix
+        _ => out.push(0x04), // delim suffix
+    }
 
     if n.is_empty() && s.is_empty() {
         shake(&mut out, d);
@@ -71,8 +96,8 @@ pub(crate) fn cshake(x: &[u8], l: usize, n: &str, s: &str, d: SecParam) -> Vec<u
 /// * `l: u64`: requested bit output length
 /// * `s: &str`: customization string
 /// * `d: u64`: the security parameter for the operation. NIST-standard values for d consist of the following:
-/// d = 512; 256 bits of security
-/// d = 256; 128 bits of security
+///   - d = 512; 256 bits of security
+///   - d = 256; 128 bits of security
 ///
 /// ## Returns:
 /// * `return  -> Vec<u8>`: kmac_xof of `x` under `k`
@@ -88,6 +113,27 @@ pub fn kmac_xof(k: &[u8], x: &[u8], l: usize, s: &str, d: SecParam) -> Vec<u8> {
     cshake(&bp, l, "KMAC", s, d)
 }
 
+/// # TupleHash
+/// Hashes a tuple of byte strings as specified in NIST SP 800-185 section 5,
+/// so that distinct tuples never hash the same input, e.g. ("ab", "c") and ("a", "bc").
+/// ## Algorithm:
+/// * z ← encode_string(x₁) || ... || encode_string(xₙ) || right_encode(l)
+/// * return cSHAKE(z, l, “TupleHash”, s)
+/// ## Arguments:
+/// * `x: &[&[u8]]`: the tuple of byte strings
+/// * `l: usize`: requested bit output length
+/// * `s: &str`: customization string
+/// * `d: SecParam`: D256 for TupleHash128, D512 for TupleHash256
+pub fn tuple_hash(x: &[&[u8]], l: usize, s: &str, d: SecParam) -> Vec<u8> {
+    let mut z = Vec::new();
+    for x_i in x {
+        z.extend_from_slice(&encode_string(x_i));
+    }
+    z.extend_from_slice(&right_encode(l as u64));
+
+    cshake(&z, l, "TupleHash", s, d)
+}
+
 /// TESTS
 #[cfg(test)]
 mod shake_tests {
@@ -203,6 +249,75 @@ mod shake_tests {
     }
 }
 
+#[cfg(test)]
+mod shake256_tests {
+    use crate::sha3::shake_functions::shake256;
+
+    #[test]
+    fn test_shake256_empty() {
+        let expected = "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762f\
+                        d75dc4ddd8c0f200cb05019d67b592f6fc821c49479ab48640292eacb3b7c4be";
+        assert_eq!(hex::encode(shake256(&[], 512)), expected);
+    }
+
+    #[test]
+    fn test_shake256_prefix_consistency() {
+        // Output lengths only truncate the same stream, across the rate boundary too
+        let data = vec![0xa3; 135];
+        let long = shake256(&data, 8 * 300);
+        assert_eq!(shake256(&data, 8 * 57), long[..57]);
+        assert_ne!(shake256(&data[..134], 8 * 57), long[..57]);
+    }
+}
+
+/// TupleHash256 samples from NIST SP 800-185 and inputs that end one byte
+/// short of a block, checked against an independent implementation.
+#[cfg(test)]
+mod tuple_hash_tests {
+    use crate::{sha3::shake_functions::tuple_hash, SecParam};
+
+    #[test]
+    fn test_tuple_hash_256_samples() {
+        let x: [&[u8]; 2] = [&[0x00, 0x01, 0x02], &[0x10, 0x11, 0x12, 0x13, 0x14, 0x15]];
+        let expected = "cfb7058caca5e668f81a12a20a2195ce97a925f1dba3e7449a56f82201ec6073\
+                        11ac2696b1ab5ea2352df1423bde7bd4bb78c9aed1a853c78672f9eb23bbe194";
+        assert_eq!(
+            hex::encode(tuple_hash(&x, 512, "", SecParam::D512)),
+            expected
+        );
+
+        let expected = "147c2191d5ed7efd98dbd96d7ab5a11692576f5fe2a5065f3e33de6bba9f3aa1\
+                        c4e9a068a289c61c95aab30aee1e410b0b607de3620e24a4e3bf9852a1d4367e";
+        assert_eq!(
+            hex::encode(tuple_hash(&x, 512, "My Tuple App", SecParam::D512)),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_tuple_hash_block_boundary() {
+        let expected = "c0132039080200ca8de6d08b2e2633c9a4e34a25ba60d24c9ba990c9403e59bf";
+        let x = [0xa5; 129];
+        assert_eq!(
+            hex::encode(tuple_hash(&[&x], 256, "", SecParam::D512)),
+            expected
+        );
+        let expected = "72b8ff393f738a64806bd50cb5eb2a2dd9bbf74dcfbc701fbd8515d5b93bf05d";
+        let x = [0xa5; 130];
+        assert_eq!(
+            hex::encode(tuple_hash(&[&x], 256, "", SecParam::D512)),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_tuple_hash_is_unambiguous() {
+        let a = tuple_hash(&[b"ab", b"c"], 256, "", SecParam::D512);
+        let b = tuple_hash(&[b"a", b"bc"], 256, "", SecParam::D512);
+        assert_ne!(a, b);
+    }
+}
+
 #[cfg(test)]
 mod cshake_tests {
     use crate::{
@@ -240,6 +355,21 @@ mod cshake_tests {
         ];
         assert_eq!(expected.to_vec(), res)
     }
+
+    /// The encoded input ends one byte short of a rate block, so the domain
+    /// suffix and the final padding bit share a byte.
+    #[test]
+    fn test_cshake_rate_boundary() {
+        let res = cshake(&[0xa5; 135], 512, "N", "", SecParam::D512);
+        let expected = "5e09f4d09ce5f6fe0df25cc7d992a2236bbe7bba91e5669cf20b5a937bf79e17\
+                        1471a6efd0e38cda986eb13415126bca2cc9d3e3c725bde43bf5156909bd40ba";
+        assert_eq!(hex::encode(res), expected);
+
+        let res = cshake(&[0xa5; 134], 512, "N", "", SecParam::D512);
+        let expected = "f220cce04daac412a5d1754d79d4217f7f17527e715b0ec2c4f72918755821a1\
+                        5627092a2d113ffd750af75d24b59c5908913297ef3aaf376a5f23a463e04622";
+        assert_eq!(hex::encode(res), expected);
+    }
 }
 
 #[cfg(test)]
@@ -285,4 +415,14 @@ mod kmac_tests {
         ];
         assert_eq!(res, expected)
     }
+
+    /// KMAC256 of a 133-byte message, whose cSHAKE input ends one byte short
+    /// of a rate block.
+    #[test]
+    fn test_kmac_rate_boundary() {
+        let res = kmac_xof(b"capycrypt", &[0xa5; 133], 512, "", SecParam::D512);
+        let expected = "3d7b8acc2ce2a9d3efb28d62b0e45350247d1526dbeda1ca54eed6bf5fd188c2\
+                        8a9fe76263fc4449bcbca11860d16d05058c880194783c8c599f0862b9a98881";
+        assert_eq!(hex::encode(res), expected)
+    }
 }
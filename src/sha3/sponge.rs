@@ -10,7 +10,7 @@ use crate::sha3::keccakf::keccakf_1600;
 pub(crate) fn sponge_absorb<C: BitLength>(m: &mut Vec<u8>, capacity: C) -> [u64; 25] {
     let c = capacity.bit_length();
     let r = (1600 - c) / 8;
-    if (m.len() % r) != 0 {
+    if !m.len().is_multiple_of(r) {
         pad_ten_one(m, r);
     }
     bytes_to_state(m, r)
@@ -143,20 +143,20 @@ mod sponge_tests {
         assert_eq!(right_encode(val), expected);
 
         let val = 0xFFFFFFFFFFFFFF;
-        let expected = [8, 255, 255, 255, 255, 255, 255, 255];
+        let expected = [255, 255, 255, 255, 255, 255, 255, 7];
         assert_eq!(right_encode(val), expected);
 
         let val = 10000000000;
-        let expected = [6, 0, 0, 2, 84, 11];
+        let expected = [2, 84, 11, 228, 0, 5];
         assert_eq!(right_encode(val), expected);
 
         let val = 10000000000000000000;
-        let expected = [8, 199, 35, 4, 137, 232, 0, 0];
+        let expected = [138, 199, 35, 4, 137, 232, 0, 0, 8];
         assert_eq!(right_encode(val), expected);
 
         let val: [u8; 200] = NIST_DATA_SPONGE_INIT;
         let val_len = val.len();
-        let expected = [2, 0];
+        let expected = [200, 1];
 
         let res = right_encode(val_len as u64);
         assert_eq!(res, expected);
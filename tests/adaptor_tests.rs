@@ -0,0 +1,174 @@
+#[cfg(test)]
+mod adaptor_tests {
+    use capycrypt::{
+        ecc::{
+            adaptor::PreSignature,
+            keypair::KeyPair,
+            signable::{verify_batch, Signable, Signature},
+        },
+        sha3::aux_functions::byte_utils::get_random_bytes,
+        Message, OperationError, SecParam,
+    };
+    use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+
+    fn adaptor_pair() -> (Scalar, ExtendedPoint) {
+        let t = *KeyPair::generate("adaptor".to_string()).priv_key.expose();
+        (t, ExtendedPoint::generator() * t)
+    }
+
+    fn verifies(msg: &[u8], sig: Signature, pub_key: &ExtendedPoint, d: SecParam) -> bool {
+        let mut message = Message::new(msg.to_vec());
+        message.sig = Some(sig);
+        message.d = Some(d);
+        message.verify(pub_key).is_ok()
+    }
+
+    #[test]
+    fn adapt_and_extract() {
+        let key = KeyPair::generate("alice".to_string());
+        let (t, adaptor) = adaptor_pair();
+        for d in [
+            SecParam::D224,
+            SecParam::D256,
+            SecParam::D384,
+            SecParam::D512,
+        ] {
+            let msg = get_random_bytes(64);
+            let pre_sig = PreSignature::sign(&msg, &key, &adaptor, d);
+            assert!(pre_sig.verify(&msg, &key.pub_key, &adaptor, d).is_ok());
+
+            let sig = pre_sig.adapt(&t, &adaptor).unwrap();
+            assert!(verifies(&msg, sig.clone(), &key.pub_key, d));
+            assert_eq!(pre_sig.extract(&sig, &adaptor).unwrap(), t);
+        }
+    }
+
+    #[test]
+    fn pre_signature_alone_does_not_verify() {
+        let key = KeyPair::generate("alice".to_string());
+        let (_, adaptor) = adaptor_pair();
+        let pre_sig = PreSignature::sign(b"payment", &key, &adaptor, SecParam::D512);
+        let sig = Signature {
+            h: pre_sig.h.clone(),
+            z: pre_sig.z,
+            u: None,
+        };
+        assert!(!verifies(b"payment", sig, &key.pub_key, SecParam::D512));
+    }
+
+    #[test]
+    fn invalid_pre_signatures_are_rejected() {
+        let key = KeyPair::generate("alice".to_string());
+        let other = KeyPair::generate("mallory".to_string());
+        let (_, adaptor) = adaptor_pair();
+        let (_, other_adaptor) = adaptor_pair();
+        let pre_sig = PreSignature::sign(b"payment", &key, &adaptor, SecParam::D512);
+
+        let d = SecParam::D512;
+        assert!(pre_sig
+            .verify(b"refund", &key.pub_key, &adaptor, d)
+            .is_err());
+        assert!(pre_sig
+            .verify(b"payment", &other.pub_key, &adaptor, d)
+            .is_err());
+        assert!(pre_sig
+            .verify(b"payment", &key.pub_key, &other_adaptor, d)
+            .is_err());
+        assert!(pre_sig
+            .verify(b"payment", &key.pub_key, &adaptor, SecParam::D256)
+            .is_err());
+    }
+
+    #[test]
+    fn adapted_signatures_batch_verify() {
+        let key = KeyPair::generate("alice".to_string());
+        let (t, adaptor) = adaptor_pair();
+        let d = SecParam::D512;
+        let pre_sig = PreSignature::sign(b"payment", &key, &adaptor, d);
+        let adapted = pre_sig.adapt(&t, &adaptor).unwrap();
+        assert!(adapted.u.is_some());
+
+        let mut message = Message::new(b"refund".to_vec());
+        message.sign(&key, d);
+        let signed = message.sig.unwrap();
+        let items: [(&[u8], &Signature, &ExtendedPoint); 2] = [
+            (b"payment", &adapted, &key.pub_key),
+            (b"refund", &signed, &key.pub_key),
+        ];
+        assert!(verify_batch(&items, d).is_ok());
+
+        // a pre-signature carrying a different nonce point is rejected
+        let mut tampered = pre_sig.clone();
+        tampered.u = tampered.u + ExtendedPoint::generator();
+        assert!(tampered
+            .verify(b"payment", &key.pub_key, &adaptor, d)
+            .is_err());
+    }
+
+    #[test]
+    fn wrong_secret_cannot_adapt_or_be_extracted() {
+        let key = KeyPair::generate("alice".to_string());
+        let (t, adaptor) = adaptor_pair();
+        let (other_t, other_adaptor) = adaptor_pair();
+        let pre_sig = PreSignature::sign(b"payment", &key, &adaptor, SecParam::D512);
+        assert!(matches!(
+            pre_sig.adapt(&other_t, &adaptor),
+            Err(OperationError::InvalidAdaptorSecret)
+        ));
+
+        let sig = pre_sig.adapt(&t, &adaptor).unwrap();
+        assert_eq!(
+            pre_sig.extract(&sig, &other_adaptor),
+            Err(OperationError::InvalidAdaptorSecret)
+        );
+        // an unrelated signature on the same message reveals nothing
+        let mut message = Message::new(b"payment".to_vec());
+        message.sign(&key, SecParam::D512);
+        assert_eq!(
+            pre_sig.extract(message.sig.as_ref().unwrap(), &adaptor),
+            Err(OperationError::InvalidAdaptorSecret)
+        );
+    }
+
+    #[test]
+    fn atomic_swap() {
+        let alice = KeyPair::generate("alice".to_string());
+        let bob = KeyPair::generate("bob".to_string());
+        let d = SecParam::D512;
+
+        // Bob picks the adaptor secret and shares only the point
+        let (t, adaptor) = adaptor_pair();
+        let alice_pre = PreSignature::sign(b"alice pays bob", &alice, &adaptor, d);
+        let bob_pre = PreSignature::sign(b"bob pays alice", &bob, &adaptor, d);
+        assert!(alice_pre
+            .verify(b"alice pays bob", &alice.pub_key, &adaptor, d)
+            .is_ok());
+        assert!(bob_pre
+            .verify(b"bob pays alice", &bob.pub_key, &adaptor, d)
+            .is_ok());
+
+        // Bob claims Alice's payment and publishes the signature
+        let alice_sig = alice_pre.adapt(&t, &adaptor).unwrap();
+        assert!(verifies(
+            b"alice pays bob",
+            alice_sig.clone(),
+            &alice.pub_key,
+            d
+        ));
+
+        // Alice learns t from it and claims Bob's payment
+        let learned = alice_pre.extract(&alice_sig, &adaptor).unwrap();
+        let bob_sig = bob_pre.adapt(&learned, &adaptor).unwrap();
+        assert!(verifies(b"bob pays alice", bob_sig, &bob.pub_key, d));
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let key = KeyPair::generate("alice".to_string());
+        let (_, adaptor) = adaptor_pair();
+        let pre_sig = PreSignature::sign(b"payment", &key, &adaptor, SecParam::D512);
+        let decoded: PreSignature =
+            serde_json::from_str(&serde_json::to_string(&pre_sig).unwrap()).unwrap();
+        assert_eq!(decoded, pre_sig);
+    }
+}
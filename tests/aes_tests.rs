@@ -1,8 +1,12 @@
 #[cfg(test)]
 mod aes_modes_tests {
     use capycrypt::{
-        aes::encryptable::AesEncryptable, sha3::aux_functions::byte_utils::get_random_bytes,
-        Message,
+        aes::{
+            aes_functions::{ctr_block, AesKeySize},
+            encryptable::{aes_ctr_apply_at, AesEncryptable},
+        },
+        sha3::aux_functions::byte_utils::get_random_bytes,
+        Message, OperationError,
     };
 
     #[test]
@@ -12,7 +16,7 @@ mod aes_modes_tests {
         // Get 5mb random data
         let mut input = Message::new(get_random_bytes(5242880));
 
-        input.aes_encrypt_cbc(&key); // Encrypt the input
+        input.aes_encrypt_cbc(&key).unwrap(); // Encrypt the input
         assert!(input.aes_decrypt_cbc(&key).is_ok()); // Verify operation success
     }
 
@@ -23,7 +27,7 @@ mod aes_modes_tests {
         // Get 5mb random data
         let mut input = Message::new(get_random_bytes(5242880));
 
-        input.aes_encrypt_cbc(&key); // Encrypt the input
+        input.aes_encrypt_cbc(&key).unwrap(); // Encrypt the input
         assert!(input.aes_decrypt_cbc(&key).is_ok()); // Verify operation success
     }
 
@@ -34,7 +38,7 @@ mod aes_modes_tests {
         // Get 5mb random data
         let mut input = Message::new(get_random_bytes(5242880));
 
-        input.aes_encrypt_cbc(&key); // Encrypt the input
+        input.aes_encrypt_cbc(&key).unwrap(); // Encrypt the input
         assert!(input.aes_decrypt_cbc(&key).is_ok()); // Verify operation success
     }
 
@@ -45,7 +49,7 @@ mod aes_modes_tests {
         // Get 5mb random data
         let mut input = Message::new(get_random_bytes(5242880));
 
-        input.aes_encrypt_ctr(&key); // Encrypt the input
+        input.aes_encrypt_ctr(&key).unwrap(); // Encrypt the input
         assert!(input.aes_decrypt_ctr(&key).is_ok()); // Verify operation success
     }
 
@@ -56,7 +60,7 @@ mod aes_modes_tests {
         // Get 5mb random data
         let mut input = Message::new(get_random_bytes(5242880));
 
-        input.aes_encrypt_ctr(&key); // Encrypt the input
+        input.aes_encrypt_ctr(&key).unwrap(); // Encrypt the input
         assert!(input.aes_decrypt_ctr(&key).is_ok()); // Verify operation success
     }
 
@@ -67,9 +71,135 @@ mod aes_modes_tests {
         // Get 5mb random data
         let mut input = Message::new(get_random_bytes(5242880));
 
-        input.aes_encrypt_ctr(&key); // Encrypt the input
+        input.aes_encrypt_ctr(&key).unwrap(); // Encrypt the input
         assert!(input.aes_decrypt_ctr(&key).is_ok()); // Verify operation success
     }
+
+    #[test]
+    fn aes_unsupported_key_length() {
+        for len in [0, 8, 15, 17, 31, 33, 64] {
+            let key = get_random_bytes(len);
+            let mut input = Message::new(get_random_bytes(64));
+            assert_eq!(
+                input.aes_encrypt_cbc(&key),
+                Err(OperationError::UnsupportedAESKeyLength)
+            );
+            assert_eq!(
+                input.aes_encrypt_ctr(&key),
+                Err(OperationError::UnsupportedAESKeyLength)
+            );
+        }
+    }
+
+    #[test]
+    fn aes_key_size_recorded_and_checked() {
+        let key = get_random_bytes(24);
+        let mut input = Message::new(get_random_bytes(100));
+        input.aes_encrypt_ctr(&key).unwrap();
+        assert_eq!(input.aes_key_size, Some(AesKeySize::Aes192));
+
+        // A key of a different size is rejected before any decryption
+        let ciphertext = input.msg.clone();
+        assert_eq!(
+            input.aes_decrypt_ctr(&key[..16]),
+            Err(OperationError::AESKeySizeMismatch)
+        );
+        assert_eq!(input.msg, ciphertext);
+    }
+
+    #[test]
+    fn aes_cbc_tampered_ciphertext() {
+        let key = get_random_bytes(32);
+        let mut input = Message::new(get_random_bytes(1000));
+        input.aes_encrypt_cbc(&key).unwrap();
+
+        input.msg[10] ^= 1;
+        let ciphertext = input.msg.clone();
+        assert_eq!(
+            input.aes_decrypt_cbc(&key),
+            Err(OperationError::AESCBCDecryptionFailure)
+        );
+        assert_eq!(input.msg, ciphertext);
+    }
+
+    #[test]
+    fn aes_ctr_tampered_ciphertext() {
+        let key = get_random_bytes(16);
+        let mut input = Message::new(get_random_bytes(1000));
+        input.aes_encrypt_ctr(&key).unwrap();
+
+        input.msg[10] ^= 1;
+        let ciphertext = input.msg.clone();
+        assert_eq!(
+            input.aes_decrypt_ctr(&key),
+            Err(OperationError::AESCTRDecryptionFailure)
+        );
+        assert_eq!(input.msg, ciphertext);
+    }
+
+    #[test]
+    fn aes_ctr_decrypt_range() {
+        let key = get_random_bytes(32);
+        let plaintext = get_random_bytes(1000);
+        let mut input = Message::new(plaintext.clone());
+        input.aes_encrypt_ctr(&key).unwrap();
+
+        // Block aligned, unaligned, within a single block, and up to the end
+        for (offset, len) in [(0, 1000), (16, 64), (5, 100), (33, 7), (990, 10), (1000, 0)] {
+            let decrypted = input.aes_decrypt_ctr_range(&key, offset, len).unwrap();
+            let offset = offset as usize;
+            assert_eq!(decrypted, plaintext[offset..offset + len]);
+        }
+
+        assert_eq!(
+            input.aes_decrypt_ctr_range(&key, 990, 11),
+            Err(OperationError::AESCTRRangeOutOfBounds)
+        );
+    }
+
+    #[test]
+    fn aes_ctr_apply_at_matches_full_decryption() {
+        let key = get_random_bytes(16);
+        let plaintext = get_random_bytes(4096);
+        let mut input = Message::new(plaintext.clone());
+        input.aes_encrypt_ctr(&key).unwrap();
+        let iv = input.sym_nonce.clone().unwrap();
+
+        // Decrypt the ciphertext in uneven, independently processed pieces
+        let mut decrypted = Vec::new();
+        for chunk in input.msg.chunks(333) {
+            let mut piece = chunk.to_vec();
+            aes_ctr_apply_at(&key, &iv, decrypted.len() as u64, &mut piece).unwrap();
+            decrypted.extend_from_slice(&piece);
+        }
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn aes_ctr_counter_overflow() {
+        let key = get_random_bytes(16);
+        let iv = get_random_bytes(16);
+
+        // The end of the 2⁶⁴-byte keystream can still be reached...
+        let mut last_block = [0u8; 16];
+        assert!(aes_ctr_apply_at(&key, &iv, u64::MAX - 16, &mut last_block).is_ok());
+
+        // ...but running past it is an error rather than a wraparound.
+        let mut data = [0u8; 32];
+        assert_eq!(
+            aes_ctr_apply_at(&key, &iv, u64::MAX - 16, &mut data),
+            Err(OperationError::AESCTRCounterOverflow)
+        );
+    }
+
+    #[test]
+    fn aes_ctr_counter_block_layout() {
+        let nonce = hex::decode("0001020304050607").unwrap();
+        assert_eq!(
+            hex::encode(ctr_block(&nonce, 0x0102)),
+            "00010203040506070000000000000102"
+        );
+    }
 }
 
 #[cfg(test)]
@@ -110,3 +240,79 @@ mod aes_functions_tests {
         assert_eq!(hex::encode(a), expected)
     }
 }
+
+#[cfg(test)]
+mod aes_backend_tests {
+    use capycrypt::{
+        aes::aes_functions::{Backend, AES},
+        sha3::aux_functions::byte_utils::get_random_bytes,
+    };
+
+    // FIPS 197 Appendix C example vectors: (key, ciphertext) for the
+    // plaintext 00112233445566778899aabbccddeeff.
+    const FIPS_197_VECTORS: [(&str, &str); 3] = [
+        (
+            "000102030405060708090a0b0c0d0e0f",
+            "69c4e0d86a7b0430d8cdb78070b4c55a",
+        ),
+        (
+            "000102030405060708090a0b0c0d0e0f1011121314151617",
+            "dda97ca4864cdfe06eaf70a0ec0d7191",
+        ),
+        (
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+            "8ea2b7ca516745bfeafc49904b496089",
+        ),
+    ];
+
+    #[test]
+    fn test_fips_197_vectors_all_backends() {
+        for backend in [Backend::Portable, Backend::detect()] {
+            for (key, expected) in FIPS_197_VECTORS {
+                let aes = AES::with_backend(&hex::decode(key).unwrap(), backend).unwrap();
+                let mut block = hex::decode("00112233445566778899aabbccddeeff").unwrap();
+
+                aes.encrypt(&mut block, 0);
+                assert_eq!(hex::encode(&block), expected);
+
+                aes.decrypt(&mut block, 0);
+                assert_eq!(hex::encode(&block), "00112233445566778899aabbccddeeff");
+            }
+        }
+    }
+
+    #[test]
+    fn test_hardware_matches_portable() {
+        // Without AES-NI both sides fall back to the portable backend, which
+        // still exercises the comparison but says nothing about the hardware.
+        let detected = Backend::detect();
+        if detected != Backend::AesNi {
+            eprintln!("AES-NI not available, comparing the portable backend with itself");
+        }
+
+        for key_len in [16, 24, 32] {
+            let key = get_random_bytes(key_len);
+            let portable = AES::with_backend(&key, Backend::Portable).unwrap();
+            let hardware = AES::with_backend(&key, Backend::AesNi).unwrap();
+            assert_eq!(portable.backend(), Backend::Portable);
+            assert_eq!(hardware.backend(), detected);
+
+            // Encrypt and decrypt 64 blocks at every block offset.
+            let data = get_random_bytes(16 * 64);
+            let mut expected = data.clone();
+            let mut actual = data.clone();
+            for block_index in (0..data.len()).step_by(16) {
+                portable.encrypt(&mut expected, block_index);
+                hardware.encrypt(&mut actual, block_index);
+            }
+            assert_eq!(expected, actual);
+
+            for block_index in (0..data.len()).step_by(16) {
+                portable.decrypt(&mut expected, block_index);
+                hardware.decrypt(&mut actual, block_index);
+            }
+            assert_eq!(expected, data);
+            assert_eq!(actual, data);
+        }
+    }
+}
@@ -0,0 +1,172 @@
+#[cfg(test)]
+mod batch_tests {
+    use capycrypt::{
+        ecc::eddsa::{verify_batch, Ed448Signature, SigningKey, VerifyingKey},
+        sha3::aux_functions::byte_utils::get_random_bytes,
+        OperationError,
+    };
+
+    fn signed_batch(n: usize, context: &[u8]) -> Vec<(Vec<u8>, Ed448Signature, VerifyingKey)> {
+        (0..n)
+            .map(|i| {
+                let key = SigningKey::generate();
+                let msg = get_random_bytes(i as u64 * 7);
+                let sig = key.sign(&msg, context).unwrap();
+                (msg, sig, key.verifying_key())
+            })
+            .collect()
+    }
+
+    fn items(
+        batch: &[(Vec<u8>, Ed448Signature, VerifyingKey)],
+    ) -> Vec<(&[u8], &Ed448Signature, &VerifyingKey)> {
+        batch
+            .iter()
+            .map(|(msg, sig, key)| (msg.as_slice(), sig, key))
+            .collect()
+    }
+
+    #[test]
+    fn valid_batch_verifies() {
+        let batch = signed_batch(32, b"");
+        assert!(verify_batch(&items(&batch), b"").is_ok());
+        assert!(verify_batch(&items(&batch[..1]), b"").is_ok());
+        assert!(verify_batch(&[], b"").is_ok());
+
+        let batch = signed_batch(8, b"ctx");
+        assert!(verify_batch(&items(&batch), b"ctx").is_ok());
+        assert!(verify_batch(&items(&batch), b"").is_err());
+    }
+
+    #[test]
+    fn bad_items_are_identified() {
+        let mut batch = signed_batch(32, b"");
+        batch[3].0.push(0);
+        batch[17].2 = SigningKey::generate().verifying_key();
+        batch[30].1 = batch[0].1;
+
+        assert_eq!(
+            verify_batch(&items(&batch), b""),
+            Err(OperationError::BatchVerificationFailure(vec![3, 17, 30]))
+        );
+    }
+
+    #[test]
+    fn malformed_signatures_are_identified() {
+        let batch = signed_batch(4, b"");
+        let mut bytes = batch[1].1.to_bytes();
+        // S ≥ L is not canonical and y ≥ p is not a valid point encoding
+        bytes[57..].fill(0xff);
+        let bad_s = Ed448Signature::from_bytes(&bytes);
+        let mut bytes = batch[2].1.to_bytes();
+        bytes[..56].fill(0xff);
+        let bad_r = Ed448Signature::from_bytes(&bytes);
+
+        let (bad_s, bad_r) = (bad_s.unwrap(), bad_r.unwrap());
+        assert!(batch[1].2.verify(&batch[1].0, &bad_s, b"").is_err());
+        assert!(batch[2].2.verify(&batch[2].0, &bad_r, b"").is_err());
+
+        let mut list = items(&batch);
+        list[1].1 = &bad_s;
+        list[2].1 = &bad_r;
+        assert_eq!(
+            verify_batch(&list, b""),
+            Err(OperationError::BatchVerificationFailure(vec![1, 2]))
+        );
+    }
+
+    #[test]
+    fn batch_agrees_with_single_verification() {
+        let batch = signed_batch(5, b"");
+        for (i, (msg, sig, key)) in batch.iter().enumerate() {
+            assert!(key.verify(msg, sig, b"").is_ok());
+            assert!(verify_batch(&items(&batch[i..=i]), b"").is_ok());
+        }
+        assert!(matches!(
+            verify_batch(&items(&batch), &[0; 256]),
+            Err(OperationError::ContextTooLong)
+        ));
+    }
+}
+
+#[cfg(test)]
+mod schnorr_batch_tests {
+    use capycrypt::{
+        ecc::{
+            keypair::KeyPair,
+            signable::{verify_batch, Signable, Signature},
+        },
+        sha3::aux_functions::byte_utils::get_random_bytes,
+        Message, OperationError, SecParam,
+    };
+    use tiny_ed448_goldilocks::curve::extended_edwards::ExtendedPoint;
+
+    fn signed_batch(n: usize) -> Vec<(Vec<u8>, Signature, ExtendedPoint)> {
+        (0..n)
+            .map(|i| {
+                let key = KeyPair::generate("signer".to_string());
+                let mut msg = Message::new(get_random_bytes(i as u64 * 7));
+                msg.sign(&key, SecParam::D512);
+                (msg.msg.to_vec(), msg.sig.unwrap(), key.pub_key)
+            })
+            .collect()
+    }
+
+    fn items(
+        batch: &[(Vec<u8>, Signature, ExtendedPoint)],
+    ) -> Vec<(&[u8], &Signature, &ExtendedPoint)> {
+        batch
+            .iter()
+            .map(|(msg, sig, key)| (msg.as_slice(), sig, key))
+            .collect()
+    }
+
+    fn verifies(msg: &[u8], sig: &Signature, pub_key: &ExtendedPoint) -> bool {
+        let mut msg = Message::new(msg.to_vec());
+        msg.sig = Some(sig.clone());
+        msg.d = Some(SecParam::D512);
+        msg.verify(pub_key).is_ok()
+    }
+
+    #[test]
+    fn valid_batch_verifies() {
+        let batch = signed_batch(32);
+        assert!(verify_batch(&items(&batch), SecParam::D512).is_ok());
+        assert!(verify_batch(&items(&batch[..1]), SecParam::D512).is_ok());
+        assert!(verify_batch(&[], SecParam::D512).is_ok());
+        assert!(verify_batch(&items(&batch), SecParam::D256).is_err());
+    }
+
+    #[test]
+    fn bad_items_are_identified() {
+        let mut batch = signed_batch(32);
+        batch[3].0.push(0);
+        batch[17].2 = KeyPair::generate("other".to_string()).pub_key;
+        batch[30].1 = batch[0].1.clone();
+
+        assert_eq!(
+            verify_batch(&items(&batch), SecParam::D512),
+            Err(OperationError::BatchVerificationFailure(vec![3, 17, 30]))
+        );
+    }
+
+    #[test]
+    fn batch_agrees_with_single_verification() {
+        let mut batch = signed_batch(6);
+        // Signatures without a nonce point, or with a wrong one, still verify
+        // on their own and must not fail the batch.
+        batch[1].1.u = None;
+        batch[2].1.u = batch[3].1.u;
+        // A nonce point that matches h does not rescue a wrong response.
+        batch[4].1.z = batch[5].1.z;
+
+        for (i, (msg, sig, key)) in batch.iter().enumerate() {
+            let single = verify_batch(&items(&batch[i..=i]), SecParam::D512).is_ok();
+            assert_eq!(single, verifies(msg, sig, key));
+        }
+        assert_eq!(
+            verify_batch(&items(&batch), SecParam::D512),
+            Err(OperationError::BatchVerificationFailure(vec![4]))
+        );
+    }
+}
@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod blind_schnorr_tests {
+    use capycrypt::{
+        ecc::{
+            blind_schnorr::{BlindResponse, BlindSigner, BlindingSession},
+            keypair::KeyPair,
+            signable::{Signable, Signature},
+        },
+        sha3::aux_functions::byte_utils::get_random_bytes,
+        Message, OperationError, SecParam,
+    };
+    use tiny_ed448_goldilocks::curve::field::scalar::Scalar;
+
+    /// Runs one issuance session and returns the unblinded signature.
+    fn issue(signer: &mut BlindSigner, msg: &[u8], d: SecParam) -> Signature {
+        let commitment = signer.commit().unwrap();
+        let (session, challenge) =
+            BlindingSession::blind(&signer.public_key(), &commitment, msg, d);
+        let response = signer.respond(&challenge).unwrap();
+        session.unblind(&response).unwrap()
+    }
+
+    #[test]
+    fn blind_signature_verifies() {
+        let mut signer = BlindSigner::new(KeyPair::generate("issuer".to_string()));
+        for d in [
+            SecParam::D224,
+            SecParam::D256,
+            SecParam::D384,
+            SecParam::D512,
+        ] {
+            let serial = get_random_bytes(32);
+            let mut token = Message::new(serial.clone());
+            token.sig = Some(issue(&mut signer, &serial, d));
+            token.d = Some(d);
+            assert!(token.verify(&signer.public_key()).is_ok());
+
+            let other = KeyPair::generate("other".to_string());
+            assert!(token.verify(&other.pub_key).is_err());
+            let mut forged = Message::new(get_random_bytes(32));
+            forged.sig = token.sig.clone();
+            forged.d = Some(d);
+            assert!(forged.verify(&signer.public_key()).is_err());
+        }
+    }
+
+    #[test]
+    fn signatures_are_unlinkable() {
+        let mut signer = BlindSigner::new(KeyPair::generate("issuer".to_string()));
+        let commitment = signer.commit().unwrap();
+        let (session, challenge) =
+            BlindingSession::blind(&signer.public_key(), &commitment, b"serial", SecParam::D512);
+        let response = signer.respond(&challenge).unwrap();
+        let sig = session.unblind(&response).unwrap();
+        // nothing the signer saw reappears in the signature
+        assert_ne!(sig.z, response.0);
+        assert_ne!(sig.z, challenge.0);
+
+        // the same message signed twice gives different signatures
+        let other = issue(&mut signer, b"serial", SecParam::D512);
+        assert_ne!(sig.h, other.h);
+    }
+
+    #[test]
+    fn signer_runs_one_session_at_a_time() {
+        let mut signer = BlindSigner::new(KeyPair::generate("issuer".to_string()));
+        let commitment = signer.commit().unwrap();
+        assert!(matches!(
+            signer.commit(),
+            Err(OperationError::BlindSessionInProgress)
+        ));
+
+        let (_, challenge) =
+            BlindingSession::blind(&signer.public_key(), &commitment, b"serial", SecParam::D512);
+        assert!(signer.respond(&challenge).is_ok());
+        // the nonce is gone after one response
+        assert!(matches!(
+            signer.respond(&challenge),
+            Err(OperationError::NoBlindSession)
+        ));
+
+        signer.commit().unwrap();
+        signer.abort();
+        assert!(matches!(
+            signer.respond(&challenge),
+            Err(OperationError::NoBlindSession)
+        ));
+        assert!(signer.commit().is_ok());
+    }
+
+    #[test]
+    fn invalid_response_is_rejected() {
+        let mut signer = BlindSigner::new(KeyPair::generate("issuer".to_string()));
+        let commitment = signer.commit().unwrap();
+        let (session, challenge) =
+            BlindingSession::blind(&signer.public_key(), &commitment, b"serial", SecParam::D512);
+        let response = signer.respond(&challenge).unwrap();
+        let tampered = BlindResponse(response.0.add_mod(&Scalar::from(1_u64)));
+        assert!(matches!(
+            session.unblind(&tampered),
+            Err(OperationError::SignatureVerificationFailure)
+        ));
+    }
+
+    #[test]
+    fn messages_serialize() {
+        let mut signer = BlindSigner::new(KeyPair::generate("issuer".to_string()));
+        let commitment = signer.commit().unwrap();
+        let commitment =
+            serde_json::from_str(&serde_json::to_string(&commitment).unwrap()).unwrap();
+        let (session, challenge) =
+            BlindingSession::blind(&signer.public_key(), &commitment, b"serial", SecParam::D256);
+        let challenge = serde_json::from_str(&serde_json::to_string(&challenge).unwrap()).unwrap();
+        let response = signer.respond(&challenge).unwrap();
+        let response = serde_json::from_str(&serde_json::to_string(&response).unwrap()).unwrap();
+
+        let mut token = Message::new(b"serial".to_vec());
+        token.sig = Some(session.unblind(&response).unwrap());
+        token.d = Some(SecParam::D256);
+        assert!(token.verify(&signer.public_key()).is_ok());
+    }
+}
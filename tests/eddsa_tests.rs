@@ -0,0 +1,157 @@
+#[cfg(test)]
+mod eddsa_tests {
+    use capycrypt::{
+        ecc::{
+            eddsa::{Ed448Signable, Ed448Signature, SigningKey, VerifyingKey},
+            edwards448::encode_point,
+            keypair::KeyPair,
+        },
+        sha3::aux_functions::byte_utils::get_random_bytes,
+        Message, OperationError, SecParam,
+    };
+
+    struct TestVector {
+        secret: &'static str,
+        public: &'static str,
+        msg: &'static str,
+        context: &'static str,
+        prehashed: bool,
+        sig: &'static str,
+    }
+
+    // RFC 8032 Section 7.4 and 7.5
+    const RFC_8032_VECTORS: [TestVector; 6] = [
+        TestVector {
+            secret: "6c82a562cb808d10d632be89c8513ebf6c929f34ddfa8c9f63c9960ef6e348a3528c8a3fcc2f044e39a3fc5b94492f8f032e7549a20098f95b",
+            public: "5fd7449b59b461fd2ce787ec616ad46a1da1342485a70e1f8a0ea75d80e96778edf124769b46c7061bd6783df1e50f6cd1fa1abeafe8256180",
+            msg: "",
+            context: "",
+            prehashed: false,
+            sig: "533a37f6bbe457251f023c0d88f976ae2dfb504a843e34d2074fd823d41a591f2b233f034f628281f2fd7a22ddd47d7828c59bd0a21bfd3980ff0d2028d4b18a9df63e006c5d1c2d345b925d8dc00b4104852db99ac5c7cdda8530a113a0f4dbb61149f05a7363268c71d95808ff2e652600",
+        },
+        TestVector {
+            secret: "c4eab05d357007c632f3dbb48489924d552b08fe0c353a0d4a1f00acda2c463afbea67c5e8d2877c5e3bc397a659949ef8021e954e0a12274e",
+            public: "43ba28f430cdff456ae531545f7ecd0ac834a55d9358c0372bfa0c6c6798c0866aea01eb00742802b8438ea4cb82169c235160627b4c3a9480",
+            msg: "03",
+            context: "",
+            prehashed: false,
+            sig: "26b8f91727bd62897af15e41eb43c377efb9c610d48f2335cb0bd0087810f4352541b143c4b981b7e18f62de8ccdf633fc1bf037ab7cd779805e0dbcc0aae1cbcee1afb2e027df36bc04dcecbf154336c19f0af7e0a6472905e799f1953d2a0ff3348ab21aa4adafd1d234441cf807c03a00",
+        },
+        TestVector {
+            secret: "c4eab05d357007c632f3dbb48489924d552b08fe0c353a0d4a1f00acda2c463afbea67c5e8d2877c5e3bc397a659949ef8021e954e0a12274e",
+            public: "43ba28f430cdff456ae531545f7ecd0ac834a55d9358c0372bfa0c6c6798c0866aea01eb00742802b8438ea4cb82169c235160627b4c3a9480",
+            msg: "03",
+            context: "666f6f",
+            prehashed: false,
+            sig: "d4f8f6131770dd46f40867d6fd5d5055de43541f8c5e35abbcd001b32a89f7d2151f7647f11d8ca2ae279fb842d607217fce6e042f6815ea000c85741de5c8da1144a6a1aba7f96de42505d7a7298524fda538fccbbb754f578c1cad10d54d0d5428407e85dcbc98a49155c13764e66c3c00",
+        },
+        TestVector {
+            secret: "2ec5fe3c17045abdb136a5e6a913e32ab75ae68b53d2fc149b77e504132d37569b7e766ba74a19bd6162343a21c8590aa9cebca9014c636df5",
+            public: "79756f014dcfe2079f5dd9e718be4171e2ef2486a08f25186f6bff43a9936b9bfe12402b08ae65798a3d81e22e9ec80e7690862ef3d4ed3a00",
+            msg: "15777532b0bdd0d1389f636c5f6b9ba734c90af572877e2d272dd078aa1e567cfa80e12928bb542330e8409f3174504107ecd5efac61ae7504dabe2a602ede89e5cca6257a7c77e27a702b3ae39fc769fc54f2395ae6a1178cab4738e543072fc1c177fe71e92e25bf03e4ecb72f47b64d0465aaea4c7fad372536c8ba516a6039c3c2a39f0e4d832be432dfa9a706a6e5c7e19f397964ca4258002f7c0541b590316dbc5622b6b2a6fe7a4abffd96105eca76ea7b98816af0748c10df048ce012d901015a51f189f3888145c03650aa23ce894c3bd889e030d565071c59f409a9981b51878fd6fc110624dcbcde0bf7a69ccce38fabdf86f3bef6044819de11",
+            context: "",
+            prehashed: false,
+            sig: "c650ddbb0601c19ca11439e1640dd931f43c518ea5bea70d3dcde5f4191fe53f00cf966546b72bcc7d58be2b9badef28743954e3a44a23f880e8d4f1cfce2d7a61452d26da05896f0a50da66a239a8a188b6d825b3305ad77b73fbac0836ecc60987fd08527c1a8e80d5823e65cafe2a3d00",
+        },
+        TestVector {
+            secret: "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42ef7822e0d5104127dc05d6dbefde69e3ab2cec7c867c6e2c49",
+            public: "259b71c19f83ef77a7abd26524cbdb3161b590a48f7d17de3ee0ba9c52beb743c09428a131d6b1b57303d90d8132c276d5ed3d5d01c0f53880",
+            msg: "616263",
+            context: "",
+            prehashed: true,
+            sig: "822f6901f7480f3d5f562c592994d9693602875614483256505600bbc281ae381f54d6bce2ea911574932f52a4e6cadd78769375ec3ffd1b801a0d9b3f4030cd433964b6457ea39476511214f97469b57dd32dbc560a9a94d00bff07620464a3ad203df7dc7ce360c3cd3696d9d9fab90f00",
+        },
+        TestVector {
+            secret: "833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42ef7822e0d5104127dc05d6dbefde69e3ab2cec7c867c6e2c49",
+            public: "259b71c19f83ef77a7abd26524cbdb3161b590a48f7d17de3ee0ba9c52beb743c09428a131d6b1b57303d90d8132c276d5ed3d5d01c0f53880",
+            msg: "616263",
+            context: "666f6f",
+            prehashed: true,
+            sig: "c32299d46ec8ff02b54540982814dce9a05812f81962b649d528095916a2aa481065b1580423ef927ecf0af5888f90da0f6a9a85ad5dc3f280d91224ba9911a3653d00e484e2ce232521481c8658df304bb7745a73514cdb9bf3e15784ab71284f8d0704a608c54a6b62d97beb511d132100",
+        },
+    ];
+
+    #[test]
+    fn rfc_8032_vectors() {
+        for tv in RFC_8032_VECTORS.iter() {
+            let key = SigningKey::from_seed(&hex::decode(tv.secret).unwrap()).unwrap();
+            let msg = hex::decode(tv.msg).unwrap();
+            let context = hex::decode(tv.context).unwrap();
+            assert_eq!(hex::encode(key.verifying_key().to_bytes()), tv.public);
+
+            let public = VerifyingKey::from_bytes(&hex::decode(tv.public).unwrap()).unwrap();
+            let sig = if tv.prehashed {
+                key.sign_prehashed(&msg, &context).unwrap()
+            } else {
+                key.sign(&msg, &context).unwrap()
+            };
+            assert_eq!(hex::encode(sig.to_bytes()), tv.sig);
+
+            let expected = Ed448Signature::from_bytes(&hex::decode(tv.sig).unwrap()).unwrap();
+            if tv.prehashed {
+                assert!(public.verify_prehashed(&msg, &expected, &context).is_ok());
+                assert!(public.verify(&msg, &expected, &context).is_err());
+            } else {
+                assert!(public.verify(&msg, &expected, &context).is_ok());
+                assert!(public.verify_prehashed(&msg, &expected, &context).is_err());
+            }
+            assert!(public.verify(&msg, &expected, b"bar").is_err());
+        }
+    }
+
+    #[test]
+    fn tampered_signatures_fail() {
+        let key = SigningKey::generate();
+        let msg = get_random_bytes(100);
+        let sig = key.sign(&msg, b"").unwrap().to_bytes();
+
+        for i in [0, 56, 57, 113] {
+            let mut bad = sig;
+            bad[i] ^= 0x01;
+            let bad = Ed448Signature::from_bytes(&bad).unwrap();
+            assert_eq!(
+                key.verifying_key().verify(&msg, &bad, b""),
+                Err(OperationError::SignatureVerificationFailure)
+            );
+        }
+        assert_eq!(
+            Ed448Signature::from_bytes(&sig[..113]),
+            Err(OperationError::InvalidSignatureLength)
+        );
+    }
+
+    #[test]
+    fn context_length_limit() {
+        let key = SigningKey::generate();
+        assert!(key.sign(b"msg", &[0u8; 255]).is_ok());
+        assert_eq!(
+            key.sign(b"msg", &[0u8; 256]),
+            Err(OperationError::ContextTooLong)
+        );
+    }
+
+    #[test]
+    fn message_sign_verify() {
+        let key = SigningKey::generate();
+        let mut msg = Message::new(get_random_bytes(5242880));
+        msg.ed448_sign(&key, b"capycrypt").unwrap();
+        assert!(msg.ed448_verify(&key.verifying_key(), b"capycrypt").is_ok());
+        assert!(msg.ed448_verify(&key.verifying_key(), b"").is_err());
+    }
+
+    #[test]
+    fn keypair_public_key_encoding() {
+        // The twisted-curve public key of a KeyPair encodes to the same
+        // RFC 8032 point as the verifying key for its scalar.
+        let key = SigningKey::generate();
+        let point = key.verifying_key().to_point();
+        assert_eq!(encode_point(&point), key.verifying_key().to_bytes());
+
+        let key_pair = KeyPair::new(b"pw", "owner".to_string(), SecParam::D256);
+        let encoded = encode_point(&key_pair.pub_key);
+        assert_eq!(
+            VerifyingKey::from_bytes(&encoded).unwrap().to_point(),
+            key_pair.pub_key
+        );
+    }
+}
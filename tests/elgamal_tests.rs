@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod elgamal_tests {
+    use capycrypt::{
+        ecc::{
+            elgamal::{discrete_log, Ciphertext, DecryptionShare, MAX_DISCRETE_LOG},
+            keypair::KeyPair,
+            shamir::split_feldman,
+        },
+        OperationError,
+    };
+    use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+
+    #[test]
+    fn encrypt_and_decrypt() {
+        let key_pair = KeyPair::generate("tally".to_string());
+        for value in [0, 1, 42, 9999, 10_000] {
+            let ct = Ciphertext::encrypt(&key_pair.pub_key, value);
+            assert_eq!(ct.decrypt(&key_pair, 10_000).unwrap(), value);
+        }
+        let ct = Ciphertext::encrypt(&key_pair.pub_key, 10_001);
+        assert_eq!(
+            ct.decrypt(&key_pair, 10_000),
+            Err(OperationError::DiscreteLogNotFound)
+        );
+
+        let other = KeyPair::generate("other".to_string());
+        let ct = Ciphertext::encrypt(&key_pair.pub_key, 7);
+        assert!(ct.decrypt(&other, 1000).is_err());
+    }
+
+    #[test]
+    fn ciphertexts_add_up() {
+        let key_pair = KeyPair::generate("tally".to_string());
+        let votes = [1, 0, 1, 1, 0, 1];
+        let tally = votes
+            .iter()
+            .map(|&v| Ciphertext::encrypt(&key_pair.pub_key, v))
+            .reduce(|a, b| a + b)
+            .unwrap();
+        assert_eq!(tally.decrypt(&key_pair, 100).unwrap(), 4);
+
+        let a = Ciphertext::encrypt(&key_pair.pub_key, 50);
+        let b = Ciphertext::encrypt(&key_pair.pub_key, 8);
+        assert_eq!((a - b).decrypt(&key_pair, 100).unwrap(), 42);
+    }
+
+    #[test]
+    fn rerandomized_ciphertexts_are_unlinkable() {
+        let key_pair = KeyPair::generate("tally".to_string());
+        let ct = Ciphertext::encrypt(&key_pair.pub_key, 5);
+        let fresh = ct.rerandomize(&key_pair.pub_key);
+        assert_ne!(fresh.c1, ct.c1);
+        assert_ne!(fresh.c2, ct.c2);
+        assert_eq!(fresh.decrypt(&key_pair, 10).unwrap(), 5);
+    }
+
+    #[test]
+    fn threshold_decryption() {
+        let key_pair = KeyPair::generate("election".to_string());
+        let (shares, commitment) = split_feldman(key_pair.priv_key.expose(), 3, 5).unwrap();
+        let ct =
+            Ciphertext::encrypt(&key_pair.pub_key, 3) + Ciphertext::encrypt(&key_pair.pub_key, 4);
+
+        let decryption_shares: Vec<DecryptionShare> =
+            shares.iter().map(|s| ct.decryption_share(s)).collect();
+        for share in &decryption_shares {
+            assert!(share
+                .verify(&ct, &commitment.public_share(share.index))
+                .is_ok());
+        }
+        assert_eq!(ct.combine_shares(&decryption_shares[2..], 100).unwrap(), 7);
+        assert_eq!(
+            ct.combine_shares(
+                &[
+                    decryption_shares[4].clone(),
+                    decryption_shares[0].clone(),
+                    decryption_shares[1].clone()
+                ],
+                100
+            )
+            .unwrap(),
+            7
+        );
+        // two trustees cannot decrypt
+        assert!(ct.combine_shares(&decryption_shares[..2], 100).is_err());
+        assert_eq!(
+            ct.combine_shares(
+                &[decryption_shares[0].clone(), decryption_shares[0].clone()],
+                100
+            ),
+            Err(OperationError::InvalidParticipantIdentifier)
+        );
+    }
+
+    #[test]
+    fn bad_decryption_share_is_detected() {
+        let key_pair = KeyPair::generate("election".to_string());
+        let (shares, commitment) = split_feldman(key_pair.priv_key.expose(), 2, 3).unwrap();
+        let ct = Ciphertext::encrypt(&key_pair.pub_key, 1);
+
+        let mut share = ct.decryption_share(&shares[1]);
+        share.point = share.point + ExtendedPoint::generator();
+        assert_eq!(
+            share.verify(&ct, &commitment.public_share(2)),
+            Err(OperationError::InvalidDecryptionShare(2))
+        );
+        // a valid share checked against another trustee's public share
+        let share = ct.decryption_share(&shares[0]);
+        assert!(share.verify(&ct, &commitment.public_share(3)).is_err());
+
+        let json = serde_json::to_string(&share).unwrap();
+        let decoded: DecryptionShare = serde_json::from_str(&json).unwrap();
+        assert!(decoded.verify(&ct, &commitment.public_share(1)).is_ok());
+    }
+
+    #[test]
+    fn small_discrete_logs() {
+        let g = ExtendedPoint::generator();
+        assert_eq!(discrete_log(&ExtendedPoint::id_point(), 0).unwrap(), 0);
+        assert_eq!(discrete_log(&g, 1).unwrap(), 1);
+        for (m, max) in [(99, 99), (100, 100), (65_535, 70_000), (3, 1 << 20)] {
+            assert_eq!(discrete_log(&(g * Scalar::from(m)), max).unwrap(), m);
+        }
+        assert!(discrete_log(&(g * Scalar::from(101_u64)), 100).is_err());
+        assert!(discrete_log(&-g, 1000).is_err());
+    }
+
+    #[test]
+    fn oversized_search_rejected() {
+        let g = ExtendedPoint::generator();
+        for max in [MAX_DISCRETE_LOG + 1, u64::MAX] {
+            assert_eq!(
+                discrete_log(&g, max).unwrap_err(),
+                OperationError::DiscreteLogRangeTooLarge
+            );
+        }
+        let key_pair = KeyPair::generate("owner".to_string());
+        let ciphertext = Ciphertext::encrypt(&key_pair.pub_key, 7);
+        assert_eq!(
+            ciphertext.decrypt(&key_pair, u64::MAX).unwrap_err(),
+            OperationError::DiscreteLogRangeTooLarge
+        );
+    }
+}
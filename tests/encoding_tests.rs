@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod encoding_tests {
+    use capycrypt::{
+        ecc::{
+            edwards448::encode_point, encryptable::KeyEncryptable, keypair::KeyPair,
+            signable::Signable,
+        },
+        sha3::aux_functions::byte_utils::get_random_bytes,
+        Message, SecParam,
+    };
+    use serde_json::Value;
+
+    #[test]
+    fn keypair_uses_compressed_encodings() {
+        let key_pair = KeyPair::generate("owner".to_string());
+        let json: Value = serde_json::to_value(&key_pair).unwrap();
+        assert_eq!(
+            json["pub_key"],
+            Value::String(hex::encode(encode_point(&key_pair.pub_key)))
+        );
+        assert_eq!(json["priv_key"].as_str().unwrap().len(), 2 * 57);
+
+        let decoded: KeyPair = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, key_pair);
+    }
+
+    #[test]
+    fn message_roundtrip_after_encryption_and_signing() {
+        let key_pair = KeyPair::generate("owner".to_string());
+        let mut msg = Message::new(get_random_bytes(256));
+        let plaintext = msg.msg.clone();
+        msg.key_encrypt(&key_pair.pub_key, SecParam::D256);
+        msg.sign(&key_pair, SecParam::D256);
+
+        let json = serde_json::to_string(&msg).unwrap();
+        let mut decoded: Message = serde_json::from_str(&json).unwrap();
+        assert!(decoded.verify(&key_pair.pub_key).is_ok());
+        assert!(decoded.key_decrypt(&key_pair).is_ok());
+        assert_eq!(decoded.msg, plaintext);
+
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["asym_nonce"].as_str().unwrap().len(), 2 * 57);
+        assert_eq!(value["sig"]["z"].as_str().unwrap().len(), 2 * 57);
+    }
+
+    #[test]
+    fn invalid_points_rejected_on_load() {
+        let key_pair = KeyPair::generate("owner".to_string());
+        let mut json: Value = serde_json::to_value(&key_pair).unwrap();
+
+        // the order-two point (0, -1)
+        let mut order_two = [0xff_u8; 57];
+        order_two[0] = 0xfe;
+        order_two[28] = 0xfe;
+        order_two[56] = 0;
+        json["pub_key"] = Value::String(hex::encode(order_two));
+        assert!(serde_json::from_value::<KeyPair>(json.clone()).is_err());
+
+        // y = 2 is not on the curve
+        let mut off_curve = [0u8; 57];
+        off_curve[0] = 2;
+        json["pub_key"] = Value::String(hex::encode(off_curve));
+        assert!(serde_json::from_value::<KeyPair>(json.clone()).is_err());
+
+        // a scalar that is not reduced modulo r
+        json["pub_key"] = Value::String(hex::encode(encode_point(&key_pair.pub_key)));
+        json["priv_key"] = Value::String(hex::encode([0xff_u8; 57]));
+        assert!(serde_json::from_value::<KeyPair>(json).is_err());
+    }
+
+    #[test]
+    fn message_without_nonce() {
+        let msg = Message::new(vec![1, 2, 3]);
+        let json = serde_json::to_string(&msg).unwrap();
+        let decoded: Message = serde_json::from_str(&json).unwrap();
+        assert!(decoded.asym_nonce.is_none());
+    }
+}
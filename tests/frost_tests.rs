@@ -0,0 +1,255 @@
+#[cfg(test)]
+mod frost_tests {
+    use capycrypt::{
+        ecc::{
+            eddsa::SigningKey,
+            frost::{
+                dkg, split_key, trusted_dealer_keygen, Identifier, KeyPackage, PublicKeyPackage,
+                SignatureShare, SigningPackage,
+            },
+            keypair::SecretScalar,
+        },
+        sha3::aux_functions::byte_utils::get_random_bytes,
+        OperationError,
+    };
+    use std::collections::BTreeMap;
+    use tiny_ed448_goldilocks::curve::field::scalar::Scalar;
+
+    fn dealer_keys(max_signers: u16, min_signers: u16) -> (Vec<KeyPackage>, PublicKeyPackage) {
+        let (shares, public) = trusted_dealer_keygen(max_signers, min_signers).unwrap();
+        let key_packages = shares
+            .iter()
+            .map(|share| KeyPackage::from_secret_share(share).unwrap())
+            .collect();
+        (key_packages, public)
+    }
+
+    fn sign(signers: &[&KeyPackage], msg: &[u8]) -> (SigningPackage, Vec<SignatureShare>) {
+        let (nonces, commitments): (Vec<_>, Vec<_>) = signers.iter().map(|k| k.commit()).unzip();
+        let package = SigningPackage::new(commitments, msg).unwrap();
+        let shares = signers
+            .iter()
+            .zip(nonces)
+            .map(|(key, nonces)| key.sign(&package, nonces).unwrap())
+            .collect();
+        (package, shares)
+    }
+
+    #[test]
+    fn any_threshold_subset_signs() {
+        let (keys, public) = dealer_keys(5, 3);
+        let msg = get_random_bytes(256);
+        for subset in [[0, 1, 2], [0, 2, 4], [4, 3, 1]] {
+            let signers: Vec<&KeyPackage> = subset.iter().map(|&i| &keys[i]).collect();
+            let (package, shares) = sign(&signers, &msg);
+            let sig = public.aggregate(&package, &shares).unwrap();
+            assert!(public.verifying_key().verify(&msg, &sig, b"").is_ok());
+            assert!(public.verifying_key().verify(b"other", &sig, b"").is_err());
+        }
+
+        let (package, shares) = sign(&[&keys[0], &keys[1], &keys[2], &keys[3], &keys[4]], &msg);
+        assert!(public.aggregate(&package, &shares).is_ok());
+    }
+
+    #[test]
+    fn too_few_signers() {
+        let (keys, _) = dealer_keys(3, 2);
+        let (nonces, commitments) = keys[0].commit();
+        let package = SigningPackage::new(vec![commitments], b"msg").unwrap();
+        assert_eq!(
+            keys[0].sign(&package, nonces).unwrap_err(),
+            OperationError::InsufficientSigners
+        );
+    }
+
+    #[test]
+    fn threshold_parameters() {
+        for (max, min) in [(3, 1), (2, 3), (0, 0)] {
+            assert!(matches!(
+                trusted_dealer_keygen(max, min),
+                Err(OperationError::InvalidThresholdParameters)
+            ));
+        }
+        assert!(trusted_dealer_keygen(2, 2).is_ok());
+    }
+
+    #[test]
+    fn split_existing_key() {
+        let signing_key = SigningKey::generate();
+        let (shares, public) = split_key(&signing_key, 3, 2).unwrap();
+        assert_eq!(public.verifying_key(), signing_key.verifying_key());
+
+        let keys: Vec<KeyPackage> = shares
+            .iter()
+            .map(|s| KeyPackage::from_secret_share(s).unwrap())
+            .collect();
+        let (package, shares) = sign(&[&keys[2], &keys[0]], b"artifact");
+        let sig = public.aggregate(&package, &shares).unwrap();
+        assert!(signing_key
+            .verifying_key()
+            .verify(b"artifact", &sig, b"")
+            .is_ok());
+    }
+
+    #[test]
+    fn bad_dealer_share_is_detected() {
+        let (mut shares, _) = trusted_dealer_keygen(3, 2).unwrap();
+        shares[1].value = SecretScalar::new(Scalar::from(42_u64));
+        assert_eq!(
+            KeyPackage::from_secret_share(&shares[1]).unwrap_err(),
+            OperationError::InvalidSecretShare(2)
+        );
+        assert!(shares[0].verify().is_ok());
+    }
+
+    #[test]
+    fn bad_signature_share_is_identified() {
+        let (keys, public) = dealer_keys(3, 2);
+        let (package, mut shares) = sign(&[&keys[0], &keys[2]], b"msg");
+        assert!(public.verify_signature_share(&package, &shares[0]).is_ok());
+
+        shares[1].z = shares[1].z.add_mod(&Scalar::from(1_u64));
+        assert_eq!(
+            public.verify_signature_share(&package, &shares[1]),
+            Err(OperationError::InvalidSignatureShare(3))
+        );
+        assert_eq!(
+            public.aggregate(&package, &shares).unwrap_err(),
+            OperationError::InvalidSignatureShare(3)
+        );
+        assert_eq!(
+            public.aggregate(&package, &shares[..1]).unwrap_err(),
+            OperationError::UnknownParticipant
+        );
+    }
+
+    #[test]
+    fn nonces_are_bound_to_the_package() {
+        let (keys, _) = dealer_keys(3, 2);
+        let (nonces, _) = keys[0].commit();
+        let (_, other) = keys[0].commit();
+        let (_, commitments) = keys[1].commit();
+        let package = SigningPackage::new(vec![other, commitments], b"msg").unwrap();
+        assert_eq!(
+            keys[0].sign(&package, nonces).unwrap_err(),
+            OperationError::NonceKeyMismatch
+        );
+
+        let (nonces, _) = keys[2].commit();
+        assert_eq!(
+            keys[2].sign(&package, nonces).unwrap_err(),
+            OperationError::UnknownParticipant
+        );
+        assert_eq!(
+            SigningPackage::new(vec![commitments, commitments], b"msg").unwrap_err(),
+            OperationError::InvalidParticipantIdentifier
+        );
+    }
+
+    fn run_dkg(max_signers: u16, min_signers: u16) -> Vec<(KeyPackage, PublicKeyPackage)> {
+        let ids: Vec<Identifier> = (1..=max_signers).collect();
+        let mut round1_secrets = BTreeMap::new();
+        let mut round1_packages = BTreeMap::new();
+        for &id in &ids {
+            let (secret, package) = dkg::part1(id, max_signers, min_signers).unwrap();
+            round1_secrets.insert(id, secret);
+            round1_packages.insert(id, package);
+        }
+        let others = |id: Identifier| {
+            let mut packages = round1_packages.clone();
+            packages.remove(&id);
+            packages
+        };
+
+        let mut round2_secrets = BTreeMap::new();
+        let mut inbox: BTreeMap<Identifier, BTreeMap<Identifier, dkg::Round2Package>> =
+            BTreeMap::new();
+        for (id, secret) in round1_secrets {
+            let (secret, packages) = dkg::part2(secret, &others(id)).unwrap();
+            for (to, package) in packages {
+                inbox.entry(to).or_default().insert(id, package);
+            }
+            round2_secrets.insert(id, secret);
+        }
+
+        ids.iter()
+            .map(|id| dkg::part3(&round2_secrets[id], &others(*id), &inbox[id]).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn dkg_keys_sign() {
+        let results = run_dkg(4, 3);
+        let public = &results[0].1;
+        for (key_package, other_public) in &results {
+            assert_eq!(other_public, public);
+            assert_eq!(key_package.group_public_key, public.group_public_key());
+            assert_eq!(
+                key_package.public_share(),
+                public.commitment.public_share(key_package.identifier)
+            );
+        }
+
+        let keys: Vec<&KeyPackage> = results.iter().map(|(k, _)| k).collect();
+        let (package, shares) = sign(&[keys[3], keys[1], keys[0]], b"deploy");
+        let sig = public.aggregate(&package, &shares).unwrap();
+        assert!(public.verifying_key().verify(b"deploy", &sig, b"").is_ok());
+    }
+
+    #[test]
+    fn dkg_rejects_bad_packages() {
+        let (_, package2) = dkg::part1(2, 3, 2).unwrap();
+        let (_, package3) = dkg::part1(3, 3, 2).unwrap();
+
+        let (secret, _) = dkg::part1(1, 3, 2).unwrap();
+        let packages = BTreeMap::from([(2, package2.clone())]);
+        assert_eq!(
+            dkg::part2(secret, &packages).map(|_| ()),
+            Err(OperationError::IncorrectNumberOfPackages)
+        );
+
+        // a proof of knowledge that does not belong to the commitment
+        let mut forged = package2;
+        forged.proof_r = package3.proof_r;
+        let (secret, _) = dkg::part1(1, 3, 2).unwrap();
+        let packages = BTreeMap::from([(2, forged), (3, package3)]);
+        assert_eq!(
+            dkg::part2(secret, &packages).map(|_| ()),
+            Err(OperationError::InvalidProofOfKnowledge(2))
+        );
+    }
+
+    #[test]
+    fn dkg_rejects_bad_share() {
+        let (s1, p1) = dkg::part1(1, 2, 2).unwrap();
+        let (s2, p2) = dkg::part1(2, 2, 2).unwrap();
+        let (r2_1, _) = dkg::part2(s1, &BTreeMap::from([(2, p2.clone())])).unwrap();
+        let (_, mut to_1) = dkg::part2(s2, &BTreeMap::from([(1, p1)])).unwrap();
+
+        to_1.get_mut(&1).unwrap().signing_share = SecretScalar::new(Scalar::from(7_u64));
+        let inbox = BTreeMap::from([(2, to_1.remove(&1).unwrap())]);
+        assert_eq!(
+            dkg::part3(&r2_1, &BTreeMap::from([(2, p2)]), &inbox).unwrap_err(),
+            OperationError::InvalidSecretShare(2)
+        );
+    }
+
+    #[test]
+    fn packages_serialize() {
+        let (keys, public) = dealer_keys(3, 2);
+        let json = serde_json::to_string(&public).unwrap();
+        assert_eq!(
+            serde_json::from_str::<PublicKeyPackage>(&json).unwrap(),
+            public
+        );
+        let json = serde_json::to_string(&keys[0]).unwrap();
+        assert_eq!(serde_json::from_str::<KeyPackage>(&json).unwrap(), keys[0]);
+
+        let (package, shares) = sign(&[&keys[0], &keys[1]], b"msg");
+        let package: SigningPackage =
+            serde_json::from_str(&serde_json::to_string(&package).unwrap()).unwrap();
+        let shares: Vec<SignatureShare> =
+            serde_json::from_str(&serde_json::to_string(&shares).unwrap()).unwrap();
+        assert!(public.aggregate(&package, &shares).is_ok());
+    }
+}
@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod hash_to_curve_tests {
+    use capycrypt::{
+        ecc::{
+            edwards448::encode_point,
+            hash_to_curve::{encode_to_curve, expand_message_xof, hash_to_curve, hash_to_field},
+        },
+        OperationError,
+    };
+
+    // The long messages of RFC 9380 Appendix J and K.
+    fn long_messages() -> (Vec<u8>, Vec<u8>) {
+        let q128 = [b"q128_".to_vec(), vec![b'q'; 128]].concat();
+        let a512 = [b"a512_".to_vec(), vec![b'a'; 512]].concat();
+        (q128, a512)
+    }
+
+    // Little-endian bytes of a big-endian hex field element.
+    fn field_bytes(hex: &str) -> Vec<u8> {
+        hex::decode(hex).unwrap().into_iter().rev().collect()
+    }
+
+    // RFC 8032 encoding of the affine point (x, y) given as big-endian hex.
+    fn encoding(x: &str, y: &str) -> Vec<u8> {
+        let mut out = field_bytes(y);
+        out.push((hex::decode(x).unwrap()[55] & 1) << 7);
+        out
+    }
+
+    #[test]
+    fn rfc_9380_expand_message_xof() {
+        const DST: &[u8] = b"QUUX-V01-CS02-with-expander-SHAKE256";
+        let (q128, a512) = long_messages();
+        let vectors: &[(&[u8], &str)] = &[
+            (b"", "2ffc05c48ed32b95d72e807f6eab9f7530dd1c2f013914c8fed38c5ccc15ad76"),
+            (b"abc", "b39e493867e2767216792abce1f2676c197c0692aed061560ead251821808e07"),
+            (b"abcdef0123456789", "245389cf44a13f0e70af8665fe5337ec2dcd138890bb7901c4ad9cfceb054b65"),
+            (&q128, "719b3911821e6428a5ed9b8e600f2866bcf23c8f0515e52d6c6c019a03f16f0e"),
+            (&a512, "9181ead5220b1963f1b5951f35547a5ea86a820562287d6ca4723633d17ccbbc"),
+            (b"", "7a1361d2d7d82d79e035b8880c5a3c86c5afa719478c007d96e6c88737a3f631dd74a2c88df79a4cb5e5d9f7504957c70d669ec6bfedc31e01e2bacc4ff3fdf9b6a00b17cc18d9d72ace7d6b81c2e481b4f73f34f9a7505dccbe8f5485f3d20c5409b0310093d5d6492dea4e18aa6979c23c8ea5de01582e9689612afbb353df"),
+            (b"abc", "a54303e6b172909783353ab05ef08dd435a558c3197db0c132134649708e0b9b4e34fb99b92a9e9e28fc1f1d8860d85897a8e021e6382f3eea10577f968ff6df6c45fe624ce65ca25932f679a42a404bc3681efe03fcd45ef73bb3a8f79ba784f80f55ea8a3c367408f30381299617f50c8cf8fbb21d0f1e1d70b0131a7b6fbe"),
+            (b"abcdef0123456789", "e42e4d9538a189316e3154b821c1bafb390f78b2f010ea404e6ac063deb8c0852fcd412e098e231e43427bd2be1330bb47b4039ad57b30ae1fc94e34993b162ff4d695e42d59d9777ea18d3848d9d336c25d2acb93adcad009bcfb9cde12286df267ada283063de0bb1505565b2eb6c90e31c48798ecdc71a71756a9110ff373"),
+            (&q128, "4ac054dda0a38a65d0ecf7afd3c2812300027c8789655e47aecf1ecc1a2426b17444c7482c99e5907afd9c25b991990490bb9c686f43e79b4471a23a703d4b02f23c669737a886a7ec28bddb92c3a98de63ebf878aa363a501a60055c048bea11840c4717beae7eee28c3cfa42857b3d130188571943a7bd747de831bd6444e0"),
+            (&a512, "09afc76d51c2cccbc129c2315df66c2be7295a231203b8ab2dd7f95c2772c68e500bc72e20c602abc9964663b7a03a389be128c56971ce81001a0b875e7fd17822db9d69792ddf6a23a151bf470079c518279aef3e75611f8f828994a9988f4a8a256ddb8bae161e658d5a2a09bcfe839c6396dc06ee5c8ff3c22d3b1f9deb7e"),
+        ];
+        for (msg, uniform_bytes) in vectors {
+            let expected = hex::decode(uniform_bytes).unwrap();
+            assert_eq!(
+                expand_message_xof(msg, DST, expected.len()).unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn rfc_9380_hash_to_field() {
+        const DST: &[u8] = b"QUUX-V01-CS02-with-edwards448_XOF:SHAKE256_ELL2_RO_";
+        let (q128, a512) = long_messages();
+        let vectors: &[(&[u8], &str, &str)] = &[
+            (b"", "0847c5ebf957d3370b1f98fde499fb3e659996d9fc9b5707176ade785ba72cd84b8a5597c12b1024be5f510fa5ba99642c4cec7f3f69d3e7", "f8cbd8a7ae8c8deed071f3ac4b93e7cfcb8f1eac1645d699fd6d3881cb295a5d3006d9449ed7cad412a77a1fe61e84a9e41d59ef384d6f9a"),
+            (b"abc", "04d975cd938ab49be3e81703d6a57cca84ed80d2ff6d4756d3f22947fb5b70ab0231f0087cbfb4b7cae73b41b0c9396b356a4831d9a14322", "2547ca887ac3db7b5fad3a098aa476e90078afe1358af6c63d677d6edfd2100bc004e0f5db94dd2560fc5b308e223241d00488c9ca6b0ef2"),
+            (b"abcdef0123456789", "10659ce25588db4e4be6f7c791a79eb21a7f24aaaca76a6ca3b83b80aaf95aa328fe7d569a1ac99f9cd216edf3915d72632f1a8b990e250c", "9243e5b6c480683fd533e81f4a778349a309ce00bd163a29eb9fa8dbc8f549242bef33e030db21cffacd408d2c4264b93e476c6a8590e7aa"),
+            (&q128, "c80390020e578f009ead417029eff6cd0926110922db63ab98395e3bdfdd5d8a65b1a2b8d495dc8c5e59b7f3518731f7dfc0f93ace5dee4b", "1c4dc6653a445bbef2add81d8e90a6c8591a788deb91d0d3f1519a2e4a460313041b77c1b0817f2e80b388e5c3e49f37d787dc1f85e4324a"),
+            (&a512, "163c79ab0210a4b5e4f44fb19437ea965bf5431ab233ef16606f0b03c5f16a3feb7d46a5a675ce8f606e9c2bf74ee5336c54a1e54919f13f", "f99666bde4995c4088333d6c2734687e815f80a99c6da02c47df4b51f6c9d9ed466b4fecf7d9884990a8e0d0be6907fa437e0b1a27f49265"),
+        ];
+        for (msg, u0, u1) in vectors {
+            let u = hash_to_field(msg, DST, 2).unwrap();
+            assert_eq!(u[0].to_bytes().to_vec(), field_bytes(u0));
+            assert_eq!(u[1].to_bytes().to_vec(), field_bytes(u1));
+        }
+    }
+
+    #[test]
+    fn rfc_9380_hash_to_curve() {
+        const DST: &[u8] = b"QUUX-V01-CS02-with-edwards448_XOF:SHAKE256_ELL2_RO_";
+        let (q128, a512) = long_messages();
+        let vectors: &[(&[u8], &str, &str)] = &[
+            (b"", "73036d4a88949c032f01507005c133884e2f0d81f9a950826245dda9e844fc78186c39daaa7147ead3e462cff60e9c6340b58134480b4d17", "94c1d61b43728e5d784ef4fcb1f38e1075f3aef5e99866911de5a234f1aafdc26b554344742e6ba0420b71b298671bbeb2b7736618634610"),
+            (b"abc", "4e0158acacffa545adb818a6ed8e0b870e6abc24dfc1dc45cf9a052e98469275d9ff0c168d6a5ac7ec05b742412ee090581f12aa398f9f8c", "894d3fa437b2d2e28cdc3bfaade035430f350ec5239b6b406b5501da6f6d6210ff26719cad83b63e97ab26a12df6dec851d6bf38e294af9a"),
+            (b"abcdef0123456789", "2c25b4503fadc94b27391933b557abdecc601c13ed51c5de68389484f93dbd6c22e5f962d9babf7a39f39f994312f8ca23344847e1fbf176", "d5e6f5350f430e53a110f5ac7fcc82a96cb865aeca982029522d32601e41c042a9dfbdfbefa2b0bdcdc3bc58cca8a7cd546803083d3a8548"),
+            (&q128, "a1861a9464ae31249a0e60bf38791f3663049a3f5378998499a83292e159a2fecff838eb9bc6939e5c6ae76eb074ad4aae39b55b72ca0b9a", "580a2798c5b904f8adfec5bd29fb49b4633cd9f8c2935eb4a0f12e5dfa0285680880296bb729c6405337525fb5ed3dff930c137314f60401"),
+            (&a512, "987c5ac19dd4b47835466a50b2d9feba7c8491b8885a04edf577e15a9f2c98b203ec2cd3e5390b3d20bba0fa6fc3eecefb5029a317234401", "5e273fcfff6b007bb6771e90509275a71ff1480c459ded26fc7b10664db0a68aaa98bc7ecb07e49cf05b80ae5ac653fbdd14276bbd35ccbc"),
+        ];
+        for (msg, x, y) in vectors {
+            assert_eq!(
+                encode_point(&hash_to_curve(msg, DST)).to_vec(),
+                encoding(x, y)
+            );
+        }
+    }
+
+    #[test]
+    fn rfc_9380_encode_to_curve() {
+        const DST: &[u8] = b"QUUX-V01-CS02-with-edwards448_XOF:SHAKE256_ELL2_NU_";
+        let (q128, a512) = long_messages();
+        let vectors: &[(&[u8], &str, &str)] = &[
+            (b"", "eb5a1fc376fd73230af2de0f3374087cc7f279f0460114cf0a6c12d6d044c16de34ec2350c34b26bf110377655ab77936869d085406af71e", "df5dcea6d42e8f494b279a500d09e895d26ac703d75ca6d118e8ca58bf6f608a2a383f292fce1563ff995dce75aede1fdc8e7c0c737ae9ad"),
+            (b"abc", "4623a64bceaba3202df76cd8b6e3daf70164f3fcbda6d6e340f7fab5cdf89140d955f722524f5fe4d968fef6ba2853ff4ea086c2f67d8110", "abaac321a169761a8802ab5b5d10061fec1a83c670ac6bc95954700317ee5f82870120e0e2c5a21b12a0c7ad17ebd343363604c4bcecafd1"),
+            (b"abcdef0123456789", "e9eb562e76db093baa43a31b7edd04ec4aadcef3389a7b9c58a19cf87f8ae3d154e134b6b3ed45847a741e33df51903da681629a4b8bcc2e", "0cf6606927ad7eb15dbc193993bc7e4dda744b311a8ec4274c8f738f74f605934582474c79260f60280fe35bd37d4347e59184cbfa12cbc4"),
+            (&q128, "122a3234d34b26c69749f23356452bf9501efa2d94859d5ef741fef024156d9d191a03a2ad24c38186f93e02d05572575968b083d8a39738", "ddf55e74eb4414c2c1fa4aa6bc37c4ab470a3fed6bb5af1e43570309b162fb61879bb15f9ea49c712efd42d0a71666430f9f0d4a20505050"),
+            (&a512, "221704949b1ce1ab8dd174dc9b8c56fcffa27179569ce9219c0c2fe183d3d23343a4c42a0e2e9d6b9d0feb1df3883ec489b6671d1fa64089", "ebdecfdc87142d1a919034bf22ecfad934c9a85effff14b594ae2c00943ca62a39d6ee3be9df0bb504ce8a9e1669bc6959c42ad6a1d3b686"),
+        ];
+        for (msg, x, y) in vectors {
+            assert_eq!(
+                encode_point(&encode_to_curve(msg, DST)).to_vec(),
+                encoding(x, y)
+            );
+        }
+    }
+
+    #[test]
+    fn domain_separation() {
+        let p = hash_to_curve(b"msg", b"capycrypt test A");
+        assert_ne!(p, hash_to_curve(b"msg", b"capycrypt test B"));
+        assert_ne!(p, encode_to_curve(b"msg", b"capycrypt test A"));
+
+        // tags longer than 255 bytes are hashed first
+        let long_dst = vec![b'd'; 300];
+        assert_eq!(expand_message_xof(b"msg", &long_dst, 64).unwrap().len(), 64);
+        assert_ne!(
+            hash_to_curve(b"msg", &long_dst),
+            hash_to_curve(b"msg", &long_dst[..299])
+        );
+    }
+
+    #[test]
+    fn oversized_output_rejected() {
+        assert_eq!(
+            expand_message_xof(b"msg", b"capycrypt test", 65535)
+                .unwrap()
+                .len(),
+            65535
+        );
+        assert_eq!(
+            expand_message_xof(b"msg", b"capycrypt test", 65536).unwrap_err(),
+            OperationError::ExpandLengthTooLarge
+        );
+        assert_eq!(
+            hash_to_field(b"msg", b"capycrypt test", 780).unwrap().len(),
+            780
+        );
+        assert_eq!(
+            hash_to_field(b"msg", b"capycrypt test", 781).unwrap_err(),
+            OperationError::ExpandLengthTooLarge
+        );
+    }
+}
@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod hd_tests {
+    use capycrypt::{
+        ecc::{
+            edwards448::encode_point,
+            encryptable::KeyEncryptable,
+            hd::{DerivationPath, ExtendedSecretKey, HARDENED},
+            signable::Signable,
+        },
+        Message, OperationError, SecParam,
+    };
+
+    struct Vector {
+        path: &'static str,
+        public: &'static str,
+        chain_code: &'static str,
+    }
+
+    // Seed 000102...0f, checked against an independent implementation
+    const VECTORS: [Vector; 4] = [
+        Vector {
+            path: "m",
+            public: "84d9733d4a343aa0447de6b6544e0d3edbbe726274cda13bbb75623c1177363a6d9c935884a951d7d1a69be8a6a1b1e7825e19a1aa826af880",
+            chain_code: "0de63a23d4abb403ac524f87dcae989045f76e32065911cd32b148a7a742f68893f9f20c14cf763506b0fe89fb3325504751892641a446402637c227d3c3b1ed",
+        },
+        Vector {
+            path: "m/0'",
+            public: "864f1d4f674e5fd8687297b25ec738af51fe5cd15c6e9224db41a44d1d4ac567cc4247f816e25014763b716bee2f45a7b2bd55b2025096d200",
+            chain_code: "6a5f01878a6f5b14e4f4c624e5a63a66a8909d596962a603710d35949c11fd293e1d11ed0f8fce80eacefd2ebf7842a739156b6470df497c69f38b5225f338d0",
+        },
+        Vector {
+            path: "m/44'/1'/0'",
+            public: "b6945c96207cf3fe189d95e3520c7ee16e04e27f20fc09b65b86ce78b4f09a22472ae8166b6c88ca102f85fe6f91168c62d8d025211fe14d80",
+            chain_code: "65c26e7411d5a33a185f2f345a21e420c1c9bb2c62adfae67bba39b3aba17b7b5f45f7ca94b2a4d54b9b6b17f7b2436255d54ce8da1e20e2ea186289ab006ff1",
+        },
+        Vector {
+            path: "m/44'/1'/0'/0/7",
+            public: "98e7d26dc095b8b27be4a4e1c372d984c076ea925ca725c5dc49649858833606827125b27518939f3afc9fcd0654c2ee640cb0795da1725f80",
+            chain_code: "6f992f70715951f148338b0d32226933f3f7ff2a0416acf4ac9cefa07c966d8345a1bcabff1dc88420611436febd396ae9603b5a0aba0f1d0cea8769167c9637",
+        },
+    ];
+
+    fn master() -> ExtendedSecretKey {
+        ExtendedSecretKey::from_seed(&(0..16).collect::<Vec<u8>>()).unwrap()
+    }
+
+    #[test]
+    fn deterministic_vectors() {
+        let master = master();
+        for vector in VECTORS {
+            let key = master.derive_path(vector.path).unwrap();
+            let public = key.public_key();
+            assert_eq!(hex::encode(encode_point(&public.pub_key)), vector.public);
+            assert_eq!(hex::encode(public.chain_code), vector.chain_code);
+        }
+    }
+
+    #[test]
+    fn path_parsing() {
+        assert_eq!(
+            DerivationPath::parse("m/44'/1h/0").unwrap().indices(),
+            [HARDENED + 44, HARDENED + 1, 0]
+        );
+        assert!(DerivationPath::parse("m").unwrap().indices().is_empty());
+        for bad in [
+            "",
+            "44'/0",
+            "m/",
+            "m//1",
+            "m/x",
+            "m/-1",
+            "m/+1",
+            "m/2147483648",
+            "m/1''",
+        ] {
+            assert_eq!(
+                DerivationPath::parse(bad),
+                Err(OperationError::InvalidDerivationPath),
+                "{}",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn depth_and_child_number() {
+        let key = master().derive_path("m/44'/1'/0'/0/7").unwrap();
+        assert_eq!(key.depth, 5);
+        assert_eq!(key.child_number, 7);
+        assert_eq!(key.public_key().depth, 5);
+    }
+
+    #[test]
+    fn public_derivation_matches_secret_derivation() {
+        let account = master().derive_path("m/44'/1'/0'").unwrap();
+        let xpub = account.public_key();
+        for path in ["m/0", "m/0/7", "m/1/2/3"] {
+            assert_eq!(
+                xpub.derive_path(path).unwrap(),
+                account.derive_path(path).unwrap().public_key()
+            );
+        }
+        assert_eq!(
+            xpub.derive_child(HARDENED),
+            Err(OperationError::HardenedPublicDerivation)
+        );
+        assert_eq!(
+            xpub.derive_path("m/0/1'"),
+            Err(OperationError::HardenedPublicDerivation)
+        );
+    }
+
+    #[test]
+    fn hardened_and_normal_children_differ() {
+        let master = master();
+        assert_ne!(
+            master.derive_child(0).unwrap().public_key(),
+            master.derive_child(HARDENED).unwrap().public_key()
+        );
+    }
+
+    #[test]
+    fn derived_keypair_works() {
+        let key = master().derive_path("m/44'/1'/0'/0/0").unwrap();
+        let key_pair = key.to_keypair("device 0".to_string());
+        assert_eq!(key_pair.pub_key, key.public_key().pub_key);
+
+        let mut msg = Message::new(vec![7; 100]);
+        msg.key_encrypt(&key_pair.pub_key, SecParam::D256);
+        assert!(msg.key_decrypt(&key_pair).is_ok());
+        msg.sign(&key_pair, SecParam::D256);
+        assert!(msg.verify(&key_pair.pub_key).is_ok());
+    }
+
+    #[test]
+    fn short_seed_rejected() {
+        assert_eq!(
+            ExtendedSecretKey::from_seed(&[0u8; 15]).err(),
+            Some(OperationError::InvalidSecretLength)
+        );
+    }
+}
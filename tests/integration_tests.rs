@@ -37,7 +37,7 @@ fn test_key_gen_enc_dec_256() {
     // Encrypt the message
     msg.key_encrypt(&key_pair.pub_key, SecParam::D256);
     // Decrypt and verify
-    assert!(msg.key_decrypt(&key_pair.priv_key).is_ok());
+    assert!(msg.key_decrypt(&key_pair).is_ok());
 }
 
 #[test]
@@ -56,7 +56,7 @@ fn test_key_gen_enc_dec_512() {
     );
 
     msg.key_encrypt(&key_pair.pub_key, SecParam::D512);
-    assert!(msg.key_decrypt(&key_pair.priv_key).is_ok());
+    assert!(msg.key_decrypt(&key_pair).is_ok());
 }
 
 #[test]
@@ -104,7 +104,7 @@ fn test_symmetric_encryptable() {
     // Get 5mb random data
     let mut msg = Message::new(get_random_bytes(5242880));
     // Encrypt the data
-    msg.aes_encrypt_ctr(&pw);
+    msg.aes_encrypt_ctr(&pw).unwrap();
     // Decrypt the data
     assert!(msg.aes_decrypt_ctr(&pw).is_ok());
     // Encrypt the data
@@ -274,7 +274,7 @@ mod decryption_test {
 
         new_msg.key_encrypt(&key_pair1.pub_key, D512);
         let new_msg2 = new_msg.msg.clone();
-        let res = new_msg.key_decrypt(&key_pair2.priv_key);
+        let res = new_msg.key_decrypt(&key_pair2);
 
         assert!(res.is_err());
         assert_eq!(*new_msg.msg, *new_msg2, "Message after reverting a failed decryption does not match the original encrypted message");
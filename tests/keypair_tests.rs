@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod keypair_tests {
+    use capycrypt::{
+        ecc::{
+            eddsa::SigningKey, encryptable::KeyEncryptable, keypair::KeyPair, signable::Signable,
+            x448::X448SecretKey,
+        },
+        sha3::aux_functions::byte_utils::get_random_bytes,
+        Message, OperationError,
+        SecParam::{D256, D512},
+    };
+
+    #[test]
+    fn passphrase_is_not_stored() {
+        let pw = b"correct horse battery staple";
+        let key_pair = KeyPair::new(pw, "owner".to_string(), D256);
+        let json = serde_json::to_string(&key_pair).unwrap();
+        assert!(!json.contains(&serde_json::to_string(&pw.to_vec()).unwrap()));
+        assert!(!format!("{:?}", key_pair).contains(&format!("{:?}", key_pair.priv_key.expose())));
+        // deriving twice gives the same key
+        assert_eq!(
+            KeyPair::new(pw, "owner".to_string(), D256).priv_key,
+            key_pair.priv_key
+        );
+    }
+
+    #[test]
+    fn seeded_key_matches_ed448_key() {
+        let seed = get_random_bytes(57);
+        let key_pair = KeyPair::from_seed(&seed, "owner".to_string()).unwrap();
+        let signing_key = SigningKey::from_seed(&seed).unwrap();
+        assert_eq!(key_pair.pub_key, signing_key.verifying_key().to_point());
+        assert!(matches!(
+            KeyPair::from_seed(&seed[..32], "owner".to_string()),
+            Err(OperationError::InvalidSecretLength)
+        ));
+    }
+
+    #[test]
+    fn generated_key_encrypts_and_signs() {
+        let key_pair = KeyPair::generate("owner".to_string());
+        let mut msg = Message::new(get_random_bytes(1024));
+        let original = msg.msg.clone();
+
+        msg.key_encrypt(&key_pair.pub_key, D512);
+        assert!(msg.key_decrypt(&key_pair).is_ok());
+        assert_eq!(msg.msg, original);
+
+        msg.sign(&key_pair, D512);
+        assert!(msg.verify(&key_pair.pub_key).is_ok());
+        assert!(msg
+            .verify(&KeyPair::generate("other".to_string()).pub_key)
+            .is_err());
+    }
+
+    #[test]
+    fn wrong_key_does_not_decrypt() {
+        let key_pair = KeyPair::generate("owner".to_string());
+        let mut msg = Message::new(get_random_bytes(64));
+        msg.key_encrypt(&key_pair.pub_key, D256);
+        assert!(msg
+            .key_decrypt(&KeyPair::generate("other".to_string()))
+            .is_err());
+    }
+
+    #[test]
+    fn key_agreement_with_x448_key() {
+        let key_pair = KeyPair::generate("owner".to_string());
+        let peer = X448SecretKey::generate();
+        assert_eq!(
+            key_pair.x448_diffie_hellman(&peer.public_key()).unwrap(),
+            peer.diffie_hellman(&key_pair.x448_public_key()).unwrap()
+        );
+    }
+}
@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod keystore_tests {
+    use capycrypt::{
+        ecc::{
+            keypair::KeyPair,
+            keystore::{EncryptedKeyPair, KdfParams, KEYSTORE_VERSION, MAX_KDF_ITERATIONS},
+        },
+        OperationError, SecParam,
+    };
+    use tempfile::tempdir;
+
+    fn test_params() -> KdfParams {
+        KdfParams::new(16, SecParam::D256)
+    }
+
+    #[test]
+    fn save_and_load_encrypted() {
+        let key_pair = KeyPair::generate("owner".to_string());
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("key.json");
+        let path = path.to_str().unwrap();
+
+        key_pair.save_encrypted(path, b"passphrase").unwrap();
+        let contents = std::fs::read_to_string(path).unwrap();
+        assert!(!contents.contains("priv_key"));
+
+        assert_eq!(
+            KeyPair::load_encrypted(path, b"passphrase").unwrap(),
+            key_pair
+        );
+        assert!(KeyPair::load_encrypted(path, b"wrong").is_err());
+    }
+
+    #[test]
+    fn public_fields_in_the_clear() {
+        let key_pair = KeyPair::generate("owner".to_string());
+        let sealed = EncryptedKeyPair::new(&key_pair, b"passphrase", test_params()).unwrap();
+        assert_eq!(sealed.version, KEYSTORE_VERSION);
+        assert_eq!(sealed.owner, key_pair.owner);
+        assert_eq!(sealed.pub_key, key_pair.pub_key);
+        assert_eq!(sealed.kdf.iterations, 16);
+    }
+
+    #[test]
+    fn wrong_passphrase_fails() {
+        let key_pair = KeyPair::generate("owner".to_string());
+        let sealed = EncryptedKeyPair::new(&key_pair, b"passphrase", test_params()).unwrap();
+        assert_eq!(
+            sealed.decrypt(b"passphrase!").unwrap_err(),
+            OperationError::SHA3DecryptionFailure
+        );
+    }
+
+    #[test]
+    fn excessive_iterations_rejected() {
+        let key_pair = KeyPair::generate("owner".to_string());
+        let mut sealed = EncryptedKeyPair::new(&key_pair, b"passphrase", test_params()).unwrap();
+
+        // A file asking for more work than the cap fails before any stretching
+        sealed.kdf.iterations = u32::MAX;
+        assert_eq!(
+            sealed.decrypt(b"passphrase").unwrap_err(),
+            OperationError::KdfIterationsTooLarge
+        );
+        assert!(EncryptedKeyPair::new(
+            &key_pair,
+            b"passphrase",
+            KdfParams::new(MAX_KDF_ITERATIONS + 1, SecParam::D256)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn tampered_header_fails() {
+        let key_pair = KeyPair::generate("owner".to_string());
+        let other = KeyPair::generate("other".to_string());
+        let sealed = EncryptedKeyPair::new(&key_pair, b"passphrase", test_params()).unwrap();
+
+        let mut tampered = sealed.clone();
+        tampered.owner = "mallory".to_string();
+        assert!(tampered.decrypt(b"passphrase").is_err());
+
+        let mut tampered = sealed.clone();
+        tampered.pub_key = other.pub_key;
+        assert!(tampered.decrypt(b"passphrase").is_err());
+
+        let mut tampered = sealed.clone();
+        tampered.kdf.iterations += 1;
+        assert!(tampered.decrypt(b"passphrase").is_err());
+
+        let mut tampered = sealed;
+        tampered.version = KEYSTORE_VERSION + 1;
+        assert_eq!(
+            tampered.decrypt(b"passphrase").unwrap_err(),
+            OperationError::UnsupportedKeystoreVersion
+        );
+    }
+
+    #[test]
+    fn change_passphrase() {
+        let key_pair = KeyPair::generate("owner".to_string());
+        let mut sealed = EncryptedKeyPair::new(&key_pair, b"old", test_params()).unwrap();
+        let salt = sealed.kdf.salt.clone();
+
+        assert!(sealed.change_passphrase(b"wrong", b"new").is_err());
+        assert_eq!(sealed.decrypt(b"old").unwrap(), key_pair);
+
+        sealed.change_passphrase(b"old", b"new").unwrap();
+        assert_ne!(sealed.kdf.salt, salt);
+        assert!(sealed.decrypt(b"old").is_err());
+        assert_eq!(sealed.decrypt(b"new").unwrap(), key_pair);
+    }
+
+    #[test]
+    fn file_roundtrip() {
+        let key_pair = KeyPair::new(b"pw", "owner".to_string(), SecParam::D512);
+        let sealed = EncryptedKeyPair::new(&key_pair, b"passphrase", test_params()).unwrap();
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("key.json");
+        let path = path.to_str().unwrap();
+
+        sealed.write_to_file(path).unwrap();
+        let read = EncryptedKeyPair::read_from_file(path).unwrap();
+        assert_eq!(read.decrypt(b"passphrase").unwrap(), key_pair);
+    }
+}
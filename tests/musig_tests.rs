@@ -0,0 +1,169 @@
+#[cfg(test)]
+mod musig_tests {
+    use capycrypt::{
+        ecc::{
+            keypair::KeyPair,
+            musig::{KeyAggContext, PartialSignature, PublicNonce, SecretNonce},
+            signable::Signable,
+        },
+        sha3::aux_functions::byte_utils::get_random_bytes,
+        Message, OperationError,
+        SecParam::{self, D256, D512},
+    };
+    use tiny_ed448_goldilocks::curve::extended_edwards::ExtendedPoint;
+
+    fn signers(n: usize) -> Vec<KeyPair> {
+        (0..n)
+            .map(|i| KeyPair::generate(format!("maintainer {}", i)))
+            .collect()
+    }
+
+    fn co_sign(keys: &[KeyPair], msg: &mut Message, d: SecParam) -> ExtendedPoint {
+        let pub_keys: Vec<ExtendedPoint> = keys.iter().map(|k| k.pub_key).collect();
+        let ctx = KeyAggContext::new(&pub_keys, d).unwrap();
+
+        let (secret_nonces, public_nonces): (Vec<SecretNonce>, Vec<PublicNonce>) =
+            keys.iter().map(SecretNonce::generate).unzip();
+        let agg_nonce = PublicNonce::aggregate(&public_nonces);
+
+        let session = ctx.session(&agg_nonce, &msg.msg);
+        let partial_sigs: Vec<PartialSignature> = keys
+            .iter()
+            .zip(secret_nonces)
+            .map(|(key, nonce)| session.partial_sign(key, nonce).unwrap())
+            .collect();
+        for ((sig, nonce), key) in partial_sigs.iter().zip(&public_nonces).zip(keys) {
+            assert!(session.partial_verify(sig, nonce, &key.pub_key).is_ok());
+        }
+
+        msg.sig = Some(session.aggregate(&partial_sigs));
+        msg.d = Some(d);
+        ctx.aggregated_key()
+    }
+
+    #[test]
+    fn three_of_three_verifies() {
+        let keys = signers(3);
+        let mut msg = Message::new(get_random_bytes(1024));
+        let agg_key = co_sign(&keys, &mut msg, D512);
+
+        assert!(msg.verify(&agg_key).is_ok());
+        for key in &keys {
+            assert!(msg.verify(&key.pub_key).is_err());
+        }
+        msg.msg.push(0);
+        assert!(msg.verify(&agg_key).is_err());
+    }
+
+    #[test]
+    fn single_signer_and_other_params() {
+        let mut msg = Message::new(get_random_bytes(64));
+        let agg_key = co_sign(&signers(1), &mut msg, D256);
+        assert!(msg.verify(&agg_key).is_ok());
+
+        let mut msg = Message::new(vec![]);
+        let agg_key = co_sign(&signers(5), &mut msg, D512);
+        assert!(msg.verify(&agg_key).is_ok());
+    }
+
+    #[test]
+    fn key_aggregation_is_order_independent() {
+        let keys: Vec<ExtendedPoint> = signers(3).iter().map(|k| k.pub_key).collect();
+        let reversed: Vec<ExtendedPoint> = keys.iter().rev().copied().collect();
+        let agg = KeyAggContext::new(&keys, D512).unwrap().aggregated_key();
+        assert_eq!(
+            KeyAggContext::new(&reversed, D512)
+                .unwrap()
+                .aggregated_key(),
+            agg
+        );
+        // the coefficients keep the aggregate from being the plain sum
+        let sum = keys[0] + keys[1] + keys[2];
+        assert_ne!(agg, sum);
+
+        assert_eq!(
+            KeyAggContext::new(&[], D512).unwrap_err(),
+            OperationError::InvalidKeyList
+        );
+    }
+
+    #[test]
+    fn duplicate_keys_are_rejected() {
+        let keys: Vec<ExtendedPoint> = signers(2).iter().map(|k| k.pub_key).collect();
+        assert_eq!(
+            KeyAggContext::new(&[keys[0], keys[1], keys[0]], D512).unwrap_err(),
+            OperationError::InvalidKeyList
+        );
+    }
+
+    #[test]
+    fn rogue_key_does_not_cancel_honest_key() {
+        // 𝑉ₘ = 𝑉' - 𝑉ₕ would make the plain sum equal 𝑉', a key the attacker controls
+        let honest = KeyPair::generate("honest".to_string());
+        let attacker = KeyPair::generate("attacker".to_string());
+        let rogue = attacker.pub_key + honest.pub_key.negate();
+
+        let agg = KeyAggContext::new(&[honest.pub_key, rogue], D512)
+            .unwrap()
+            .aggregated_key();
+        assert_ne!(agg, attacker.pub_key);
+    }
+
+    #[test]
+    fn bad_partial_signature_is_detected() {
+        let keys = signers(3);
+        let pub_keys: Vec<ExtendedPoint> = keys.iter().map(|k| k.pub_key).collect();
+        let ctx = KeyAggContext::new(&pub_keys, D512).unwrap();
+        let msg = get_random_bytes(32);
+
+        let (secret_nonces, public_nonces): (Vec<SecretNonce>, Vec<PublicNonce>) =
+            keys.iter().map(SecretNonce::generate).unzip();
+        let agg_nonce = PublicNonce::aggregate(&public_nonces);
+        let session = ctx.session(&agg_nonce, &msg);
+        let partial_sigs: Vec<PartialSignature> = keys
+            .iter()
+            .zip(secret_nonces)
+            .map(|(key, nonce)| session.partial_sign(key, nonce).unwrap())
+            .collect();
+
+        // the partial signature of signer 0 does not verify for signer 1
+        assert_eq!(
+            session.partial_verify(&partial_sigs[0], &public_nonces[1], &keys[1].pub_key),
+            Err(OperationError::SignatureVerificationFailure)
+        );
+
+        let mut message = Message::new(msg);
+        message.sig = Some(session.aggregate(&partial_sigs[..2]));
+        message.d = Some(D512);
+        assert!(message.verify(&ctx.aggregated_key()).is_err());
+    }
+
+    #[test]
+    fn signer_and_nonce_checks() {
+        let keys = signers(2);
+        let outsider = KeyPair::generate("outsider".to_string());
+        let pub_keys: Vec<ExtendedPoint> = keys.iter().map(|k| k.pub_key).collect();
+        let ctx = KeyAggContext::new(&pub_keys, D512).unwrap();
+
+        let (nonce, public) = SecretNonce::generate(&keys[0]);
+        let session = ctx.session(&public, b"release");
+        assert_eq!(
+            session.partial_sign(&keys[1], nonce).unwrap_err(),
+            OperationError::NonceKeyMismatch
+        );
+
+        let (nonce, _) = SecretNonce::generate(&outsider);
+        assert_eq!(
+            session.partial_sign(&outsider, nonce).unwrap_err(),
+            OperationError::SignerNotInKeyList
+        );
+        assert!(!format!("{:?}", SecretNonce::generate(&keys[0]).0).contains("k1"));
+    }
+
+    #[test]
+    fn nonces_serialize() {
+        let (_, public) = SecretNonce::generate(&KeyPair::generate("owner".to_string()));
+        let json = serde_json::to_string(&public).unwrap();
+        assert_eq!(serde_json::from_str::<PublicNonce>(&json).unwrap(), public);
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod nizk_tests {
+    use capycrypt::{
+        ecc::{
+            hash_to_curve::hash_to_curve,
+            keypair::KeyPair,
+            nizk::{DleqProof, DlogProof, PROOF_BYTES},
+        },
+        OperationError,
+    };
+    use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+
+    fn random_scalar() -> Scalar {
+        *KeyPair::generate(String::new()).priv_key.expose()
+    }
+
+    #[test]
+    fn proves_key_possession() {
+        let key_pair = KeyPair::generate("prover".to_string());
+        let proof = DlogProof::prove(key_pair.priv_key.expose(), b"login 42");
+        assert!(proof.verify(&key_pair.pub_key, b"login 42").is_ok());
+
+        // bound to the context and the statement
+        assert_eq!(
+            proof.verify(&key_pair.pub_key, b"login 43"),
+            Err(OperationError::ProofVerificationFailure)
+        );
+        let other = KeyPair::generate("other".to_string());
+        assert!(proof.verify(&other.pub_key, b"login 42").is_err());
+
+        // proofs are randomized
+        assert_ne!(
+            DlogProof::prove(key_pair.priv_key.expose(), b"login 42"),
+            proof
+        );
+    }
+
+    #[test]
+    fn tampered_dlog_proof_is_rejected() {
+        let x = random_scalar();
+        let public = ExtendedPoint::generator() * x;
+        let mut proof = DlogProof::prove(&x, b"");
+        proof.s = proof.s.add_mod(&Scalar::from(1_u64));
+        assert!(proof.verify(&public, b"").is_err());
+    }
+
+    #[test]
+    fn proves_equal_discrete_logs() {
+        let x = random_scalar();
+        let g1 = ExtendedPoint::generator();
+        let g2 = hash_to_curve(b"second base", b"capycrypt nizk test");
+        let (a, b) = (g1 * x, g2 * x);
+
+        let proof = DleqProof::prove(&x, &g1, &g2, b"ctx");
+        assert!(proof.verify(&g1, &a, &g2, &b, b"ctx").is_ok());
+        assert!(proof.verify(&g1, &a, &g2, &b, b"other").is_err());
+        // swapping the bases changes the statement
+        assert!(proof.verify(&g2, &b, &g1, &a, b"ctx").is_err());
+
+        // B has a different discrete logarithm
+        let b_other = g2 * random_scalar();
+        assert_eq!(
+            proof.verify(&g1, &a, &g2, &b_other, b"ctx"),
+            Err(OperationError::ProofVerificationFailure)
+        );
+        let forged = DleqProof::prove(&x, &g1, &g2, b"ctx");
+        assert!(forged.verify(&g1, &a, &g2, &b_other, b"ctx").is_err());
+    }
+
+    #[test]
+    fn proofs_serialize() {
+        let x = random_scalar();
+        let g2 = hash_to_curve(b"base", b"capycrypt nizk test");
+        let dlog = DlogProof::prove(&x, b"ctx");
+        let dleq = DleqProof::prove(&x, &ExtendedPoint::generator(), &g2, b"ctx");
+
+        let bytes = dlog.to_bytes();
+        assert_eq!(bytes.len(), PROOF_BYTES);
+        assert_eq!(DlogProof::from_bytes(&bytes).unwrap(), dlog);
+        assert_eq!(DleqProof::from_bytes(&dleq.to_bytes()).unwrap(), dleq);
+
+        let json = serde_json::to_string(&dleq).unwrap();
+        let decoded: DleqProof = serde_json::from_str(&json).unwrap();
+        assert!(decoded
+            .verify(
+                &ExtendedPoint::generator(),
+                &(ExtendedPoint::generator() * x),
+                &g2,
+                &(g2 * x),
+                b"ctx"
+            )
+            .is_ok());
+
+        assert_eq!(
+            DlogProof::from_bytes(&bytes[..PROOF_BYTES - 1]),
+            Err(OperationError::InvalidProofLength)
+        );
+        let mut unreduced = bytes;
+        unreduced[..56].fill(0xFF);
+        assert_eq!(
+            DlogProof::from_bytes(&unreduced),
+            Err(OperationError::InvalidScalarEncoding)
+        );
+    }
+}
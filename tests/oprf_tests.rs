@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod oprf_tests {
+    use capycrypt::{
+        ecc::{
+            keypair::KeyPair,
+            oprf::{BlindedElement, Evaluation, Mode, OprfClient, OprfServer},
+        },
+        OperationError,
+    };
+    use tiny_ed448_goldilocks::curve::extended_edwards::ExtendedPoint;
+
+    /// Runs blind, blind_evaluate and finalize between a client and a server.
+    fn run(
+        client: &OprfClient,
+        server: &OprfServer,
+        input: &[u8],
+    ) -> Result<[u8; 64], OperationError> {
+        let (state, blinded) = client.blind(input)?;
+        let evaluation = server.blind_evaluate(&blinded)?;
+        client.finalize(state, &evaluation)
+    }
+
+    #[test]
+    fn oprf_loopback() {
+        let server = OprfServer::new(Mode::Oprf, KeyPair::generate("server".to_string()));
+        let client = OprfClient::oprf();
+
+        let output = run(&client, &server, b"hunter2").unwrap();
+        assert_eq!(output, server.evaluate(b"hunter2").unwrap());
+        assert_eq!(output, run(&client, &server, b"hunter2").unwrap());
+        assert_ne!(output, run(&client, &server, b"hunter3").unwrap());
+
+        let other = OprfServer::new(Mode::Oprf, KeyPair::generate("other".to_string()));
+        assert_ne!(output, run(&client, &other, b"hunter2").unwrap());
+    }
+
+    #[test]
+    fn voprf_loopback() {
+        let server = OprfServer::new(Mode::Voprf, KeyPair::generate("server".to_string()));
+        let client = OprfClient::voprf(&server.public_key());
+
+        let breached: Vec<[u8; 64]> = [b"123456".as_slice(), b"password", b"qwerty"]
+            .iter()
+            .map(|p| server.evaluate(p).unwrap())
+            .collect();
+        let output = run(&client, &server, b"password").unwrap();
+        assert!(breached.contains(&output));
+        let output = run(&client, &server, b"correct horse battery staple").unwrap();
+        assert!(!breached.contains(&output));
+    }
+
+    #[test]
+    fn modes_are_domain_separated() {
+        let key_pair = KeyPair::generate("server".to_string());
+        let oprf = OprfServer::new(Mode::Oprf, key_pair.clone());
+        let voprf = OprfServer::new(Mode::Voprf, key_pair);
+        assert_ne!(oprf.evaluate(b"input"), voprf.evaluate(b"input"));
+    }
+
+    #[test]
+    fn voprf_rejects_other_keys() {
+        let server = OprfServer::new(Mode::Voprf, KeyPair::generate("server".to_string()));
+        let other = OprfServer::new(Mode::Voprf, KeyPair::generate("other".to_string()));
+        let client = OprfClient::voprf(&server.public_key());
+        assert_eq!(
+            run(&client, &other, b"input"),
+            Err(OperationError::ProofVerificationFailure)
+        );
+
+        // an evaluation without its proof
+        let (state, blinded) = client.blind(b"input").unwrap();
+        let evaluation = Evaluation {
+            proof: None,
+            ..server.blind_evaluate(&blinded).unwrap()
+        };
+        assert_eq!(
+            client.finalize(state, &evaluation),
+            Err(OperationError::ProofVerificationFailure)
+        );
+
+        // a proof replayed for a different blinded element
+        let (_, blinded) = client.blind(b"input").unwrap();
+        let replayed = server.blind_evaluate(&blinded).unwrap();
+        let (state, blinded) = client.blind(b"input").unwrap();
+        let evaluation = Evaluation {
+            proof: replayed.proof,
+            ..server.blind_evaluate(&blinded).unwrap()
+        };
+        assert_eq!(
+            client.finalize(state, &evaluation),
+            Err(OperationError::ProofVerificationFailure)
+        );
+    }
+
+    #[test]
+    fn blinding_hides_the_input() {
+        let client = OprfClient::oprf();
+        let (_, first) = client.blind(b"input").unwrap();
+        let (_, second) = client.blind(b"input").unwrap();
+        assert_ne!(first, second);
+
+        let server = OprfServer::new(Mode::Oprf, KeyPair::generate("server".to_string()));
+        assert_eq!(
+            server.blind_evaluate(&BlindedElement(ExtendedPoint::id_point())),
+            Err(OperationError::InvalidPointEncoding)
+        );
+    }
+
+    #[test]
+    fn oversized_input_rejected() {
+        let server = OprfServer::new(Mode::Oprf, KeyPair::generate("server".to_string()));
+        let client = OprfClient::oprf();
+        let longest = vec![0xa5; u16::MAX as usize];
+        assert_eq!(
+            run(&client, &server, &longest).unwrap(),
+            server.evaluate(&longest).unwrap()
+        );
+
+        let too_long = vec![0xa5; u16::MAX as usize + 1];
+        assert!(matches!(
+            client.blind(&too_long),
+            Err(OperationError::InvalidOprfInput)
+        ));
+        assert_eq!(
+            server.evaluate(&too_long).unwrap_err(),
+            OperationError::InvalidOprfInput
+        );
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let server = OprfServer::new(Mode::Voprf, KeyPair::generate("server".to_string()));
+        let client = OprfClient::voprf(&server.public_key());
+        let (state, blinded) = client.blind(b"input").unwrap();
+
+        let blinded: BlindedElement =
+            serde_json::from_str(&serde_json::to_string(&blinded).unwrap()).unwrap();
+        let evaluation = server.blind_evaluate(&blinded).unwrap();
+        let evaluation: Evaluation =
+            serde_json::from_str(&serde_json::to_string(&evaluation).unwrap()).unwrap();
+        assert_eq!(
+            client.finalize(state, &evaluation).unwrap(),
+            server.evaluate(b"input").unwrap()
+        );
+    }
+}
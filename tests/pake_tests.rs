@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod pake_tests {
+    use capycrypt::{
+        aes::{aes_functions::AesKeySize, encryptable::AesEncryptable},
+        ecc::pake::{CPace, CPaceMessage, Role, SessionKey},
+        sha3::{aux_functions::byte_utils::get_random_bytes, encryptable::SpongeEncryptable},
+        Message, OperationError, SecParam,
+    };
+    use tiny_ed448_goldilocks::curve::extended_edwards::ExtendedPoint;
+
+    /// Runs a session where both messages are sent as bytes.
+    fn exchange(
+        initiator_password: &[u8],
+        responder_password: &[u8],
+        sid: &[u8],
+    ) -> (SessionKey, SessionKey) {
+        let (initiator, to_responder) = CPace::start(
+            Role::Initiator,
+            initiator_password,
+            sid,
+            b"pairing",
+            b"phone",
+        );
+        let (responder, to_initiator) = CPace::start(
+            Role::Responder,
+            responder_password,
+            sid,
+            b"pairing",
+            b"laptop",
+        );
+
+        let to_responder = CPaceMessage::from_bytes(&to_responder.to_bytes()).unwrap();
+        let to_initiator = CPaceMessage::from_bytes(&to_initiator.to_bytes()).unwrap();
+        assert_eq!(to_responder.associated_data, b"phone");
+        (
+            initiator.finish(&to_initiator).unwrap(),
+            responder.finish(&to_responder).unwrap(),
+        )
+    }
+
+    #[test]
+    fn matching_codes_agree() {
+        let sid = get_random_bytes(16);
+        let (initiator, responder) = exchange(b"482913", b"482913", &sid);
+        assert_eq!(initiator.sponge_key(), responder.sponge_key());
+        assert!(responder
+            .verify_confirmation(&initiator.confirmation())
+            .is_ok());
+        assert!(initiator
+            .verify_confirmation(&responder.confirmation())
+            .is_ok());
+        // each direction has its own tag, so a tag cannot be reflected
+        assert_ne!(initiator.confirmation(), responder.confirmation());
+        assert!(initiator
+            .verify_confirmation(&initiator.confirmation())
+            .is_err());
+    }
+
+    #[test]
+    fn mismatched_codes_disagree() {
+        let sid = get_random_bytes(16);
+        let (initiator, responder) = exchange(b"482913", b"482914", &sid);
+        assert_ne!(initiator.sponge_key(), responder.sponge_key());
+        assert_eq!(
+            responder.verify_confirmation(&initiator.confirmation()),
+            Err(OperationError::PakeConfirmationFailure)
+        );
+    }
+
+    #[test]
+    fn sessions_are_independent() {
+        let (first, _) = exchange(b"482913", b"482913", b"session 1");
+        let (second, _) = exchange(b"482913", b"482913", b"session 1");
+        assert_ne!(first.sponge_key(), second.sponge_key());
+
+        let (a, b) = exchange(b"482913", b"482913", b"session 1");
+        let (c, d) = exchange(b"482913", b"482913", b"session 2");
+        assert_eq!(a.sponge_key(), b.sponge_key());
+        assert_eq!(c.sponge_key(), d.sponge_key());
+        assert_ne!(a.sponge_key(), c.sponge_key());
+    }
+
+    #[test]
+    fn tampered_associated_data_is_detected() {
+        let (initiator, to_responder) = CPace::start(Role::Initiator, b"code", b"sid", b"", b"a");
+        let (responder, to_initiator) = CPace::start(Role::Responder, b"code", b"sid", b"", b"b");
+        let tampered = CPaceMessage {
+            associated_data: b"mallory".to_vec(),
+            ..to_responder
+        };
+        let initiator = initiator.finish(&to_initiator).unwrap();
+        let responder = responder.finish(&tampered).unwrap();
+        assert!(responder
+            .verify_confirmation(&initiator.confirmation())
+            .is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_messages() {
+        let (state, _) = CPace::start(Role::Initiator, b"code", b"sid", b"", b"");
+        let identity = CPaceMessage {
+            point: ExtendedPoint::id_point(),
+            associated_data: vec![],
+        };
+        assert!(matches!(
+            state.finish(&identity),
+            Err(OperationError::InvalidPointEncoding)
+        ));
+        assert_eq!(
+            CPaceMessage::from_bytes(&identity.to_bytes()),
+            Err(OperationError::InvalidPointEncoding)
+        );
+        assert_eq!(
+            CPaceMessage::from_bytes(&[0u8; 10]),
+            Err(OperationError::InvalidPointEncoding)
+        );
+    }
+
+    #[test]
+    fn session_keys_encrypt() {
+        let sid = get_random_bytes(16);
+        let (initiator, responder) = exchange(b"482913", b"482913", &sid);
+
+        let mut msg = Message::new(b"paired".to_vec());
+        msg.sha3_encrypt(&initiator.sponge_key(), SecParam::D512);
+        assert!(msg.sha3_decrypt(&responder.sponge_key()).is_ok());
+        assert_eq!(*msg.msg, b"paired");
+
+        for size in [AesKeySize::Aes128, AesKeySize::Aes192, AesKeySize::Aes256] {
+            let mut msg = Message::new(b"paired".to_vec());
+            msg.aes_encrypt_ctr(&initiator.aes_key(size)).unwrap();
+            assert!(msg.aes_decrypt_ctr(&responder.aes_key(size)).is_ok());
+            assert_eq!(*msg.msg, b"paired");
+        }
+        assert_ne!(
+            initiator.sponge_key()[..32],
+            initiator.aes_key(AesKeySize::Aes256)
+        );
+    }
+}
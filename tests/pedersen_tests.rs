@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod pedersen_tests {
+    use capycrypt::{
+        ecc::{
+            keypair::SecretScalar,
+            pedersen::{Commitment, Opening, RangeProof},
+        },
+        OperationError,
+    };
+    use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+
+    #[test]
+    fn commitments_open_and_hide() {
+        let (commitment, opening) = Commitment::commit(&Scalar::from(1000_u64));
+        assert!(commitment.verify_opening(&opening).is_ok());
+
+        let (again, _) = Commitment::commit(&Scalar::from(1000_u64));
+        assert_ne!(again, commitment);
+
+        let mut wrong = opening.clone();
+        wrong.value = Scalar::from(1001_u64);
+        assert_eq!(
+            commitment.verify_opening(&wrong),
+            Err(OperationError::InvalidOpening)
+        );
+        let mut wrong = opening;
+        wrong.blinding = SecretScalar::new(Scalar::from(1_u64));
+        assert!(commitment.verify_opening(&wrong).is_err());
+    }
+
+    #[test]
+    fn commitments_are_homomorphic() {
+        let (c1, o1) = Commitment::commit(&Scalar::from(700_u64));
+        let (c2, o2) = Commitment::commit(&Scalar::from(300_u64));
+
+        let sum = &o1 + &o2;
+        assert_eq!(sum.value, Scalar::from(1000_u64));
+        assert!((c1 + c2).verify_opening(&sum).is_ok());
+
+        let difference = &o1 - &o2;
+        assert_eq!(difference.value, Scalar::from(400_u64));
+        assert!((c1 - c2).verify_opening(&difference).is_ok());
+        assert_eq!(c1 + c2 - c2, c1);
+    }
+
+    #[test]
+    fn range_proofs_verify() {
+        for (value, bits) in [
+            (0_u64, 1),
+            (1, 1),
+            (200, 8),
+            (u32::MAX as u64, 32),
+            (u64::MAX, 64),
+        ] {
+            let (commitment, opening) = Commitment::commit(&Scalar::from(value));
+            let proof = RangeProof::prove(&opening, bits, b"quota").unwrap();
+            assert_eq!(proof.bit_commitments.len(), bits);
+            assert!(proof.verify(&commitment, b"quota").is_ok());
+            assert_eq!(
+                proof.verify(&commitment, b"other"),
+                Err(OperationError::ProofVerificationFailure)
+            );
+        }
+    }
+
+    #[test]
+    fn range_proof_of_sum() {
+        // a balance after a deposit stays within 16 bits
+        let (c1, o1) = Commitment::commit(&Scalar::from(40000_u64));
+        let (c2, o2) = Commitment::commit(&Scalar::from(25000_u64));
+        let proof = RangeProof::prove(&(&o1 + &o2), 16, b"").unwrap();
+        assert!(proof.verify(&(c1 + c2), b"").is_ok());
+        assert!(proof.verify(&c1, b"").is_err());
+    }
+
+    #[test]
+    fn out_of_range_values_are_refused() {
+        let (_, opening) = Commitment::commit(&Scalar::from(256_u64));
+        assert_eq!(
+            RangeProof::prove(&opening, 8, b"").unwrap_err(),
+            OperationError::ValueOutOfRange
+        );
+        for bits in [0, 65] {
+            assert_eq!(
+                RangeProof::prove(&opening, bits, b"").unwrap_err(),
+                OperationError::UnsupportedRangeSize
+            );
+        }
+
+        // -1 = r - 1 is a huge value
+        let (_, zero) = Commitment::commit(&Scalar::from(0_u64));
+        let (_, one) = Commitment::commit(&Scalar::from(1_u64));
+        assert_eq!(
+            RangeProof::prove(&(&zero - &one), 64, b"").unwrap_err(),
+            OperationError::ValueOutOfRange
+        );
+    }
+
+    #[test]
+    fn tampered_range_proofs_are_rejected() {
+        let (commitment, opening) = Commitment::commit(&Scalar::from(5_u64));
+        let proof = RangeProof::prove(&opening, 4, b"").unwrap();
+
+        // a bit commitment that holds 2 instead of 0 or 1, compensated in another
+        let mut forged = proof.clone();
+        let g = ExtendedPoint::generator();
+        forged.bit_commitments[1] = forged.bit_commitments[1] + g + g;
+        forged.bit_commitments[2] = forged.bit_commitments[2] + g.negate();
+        assert_eq!(
+            forged.verify(&commitment, b""),
+            Err(OperationError::ProofVerificationFailure)
+        );
+
+        let mut truncated = proof.clone();
+        truncated.bit_proofs.pop();
+        assert_eq!(
+            truncated.verify(&commitment, b""),
+            Err(OperationError::UnsupportedRangeSize)
+        );
+
+        let json = serde_json::to_string(&proof).unwrap();
+        let decoded: RangeProof = serde_json::from_str(&json).unwrap();
+        assert!(decoded.verify(&commitment, b"").is_ok());
+        let json = serde_json::to_string(&opening).unwrap();
+        assert_eq!(serde_json::from_str::<Opening>(&json).unwrap(), opening);
+    }
+}
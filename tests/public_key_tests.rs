@@ -0,0 +1,77 @@
+#[cfg(test)]
+mod public_key_tests {
+    use capycrypt::ecc::{
+        encryptable::KeyEncryptable,
+        keypair::KeyPair,
+        public_key::{PublicKey, FINGERPRINT_EMOJI, FINGERPRINT_WORDS},
+    };
+    use capycrypt::{Message, SecParam};
+    use tempfile::tempdir;
+
+    // RFC 8032 Ed448 test 1 secret key
+    const SEED: &str = "6c82a562cb808d10d632be89c8513ebf6c929f34ddfa8c9f63c9960ef6e348a3528c8a3fcc2f044e39a3fc5b94492f8f032e7549a20098f95b";
+
+    #[test]
+    fn public_key_file_roundtrip() {
+        let key_pair = KeyPair::generate("alice".to_string());
+        let public_key = key_pair.public_key();
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("alice.pub.json");
+        let path = path.to_str().unwrap();
+        public_key.write_to_file(path).unwrap();
+
+        let contents = std::fs::read_to_string(path).unwrap();
+        assert!(!contents.contains("priv_key"));
+        let read = PublicKey::read_from_file(path).unwrap();
+        assert_eq!(read, public_key);
+
+        // the imported key encrypts to the key pair
+        let mut msg = Message::new(vec![42; 64]);
+        msg.key_encrypt(&read.pub_key, SecParam::D256);
+        assert!(msg.key_decrypt(&key_pair).is_ok());
+    }
+
+    #[test]
+    fn fingerprint_known_answer() {
+        // TupleHash256((A, "alice"), 256, "FP") with the RFC 8032 public key A
+        let key_pair =
+            KeyPair::from_seed(&hex::decode(SEED).unwrap(), "alice".to_string()).unwrap();
+        let fingerprint = key_pair.fingerprint();
+        assert_eq!(
+            hex::encode(fingerprint.to_bytes()),
+            "2dad86a8cd704c435ab4d23496412e5c3d64bffc90b10378919798e498ac6779"
+        );
+        assert_eq!(
+            fingerprint.to_hex(),
+            "2dad 86a8 cd70 4c43 5ab4 d234 9641 2e5c 3d64 bffc 90b1 0378 9197 98e4 98ac 6779"
+        );
+        assert_eq!(fingerprint.to_string(), fingerprint.to_hex());
+    }
+
+    #[test]
+    fn fingerprint_binds_owner() {
+        let key_pair = KeyPair::generate("alice".to_string());
+        let mut public_key = key_pair.public_key();
+        let fingerprint = public_key.fingerprint();
+        assert_eq!(fingerprint, key_pair.fingerprint());
+
+        public_key.owner = "mallory".to_string();
+        assert_ne!(public_key.fingerprint(), fingerprint);
+        // the creation date is not part of the fingerprint
+        public_key.owner = "alice".to_string();
+        public_key.date_created = String::new();
+        assert_eq!(public_key.fingerprint(), fingerprint);
+    }
+
+    #[test]
+    fn fingerprint_words_and_emoji() {
+        let a = KeyPair::generate("alice".to_string()).fingerprint();
+        let b = KeyPair::generate("alice".to_string()).fingerprint();
+
+        assert_eq!(a.to_words().len(), FINGERPRINT_WORDS);
+        assert_eq!(a.to_emoji().chars().count(), FINGERPRINT_EMOJI);
+        assert_ne!(a.to_words(), b.to_words());
+        assert_ne!(a.to_emoji(), b.to_emoji());
+    }
+}
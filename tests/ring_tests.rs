@@ -0,0 +1,106 @@
+#[cfg(test)]
+mod ring_tests {
+    use capycrypt::{
+        ecc::{
+            keypair::KeyPair,
+            ring::{KeyImage, RingSignature},
+        },
+        OperationError,
+    };
+    use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+
+    fn registered(n: usize) -> (Vec<KeyPair>, Vec<ExtendedPoint>) {
+        let keys: Vec<KeyPair> = (0..n)
+            .map(|i| KeyPair::generate(format!("source {}", i)))
+            .collect();
+        let ring = keys.iter().map(|k| k.pub_key).collect();
+        (keys, ring)
+    }
+
+    #[test]
+    fn every_member_can_sign() {
+        let (keys, ring) = registered(5);
+        for key in &keys {
+            let sig = RingSignature::sign(b"submission", &ring, key).unwrap();
+            assert!(sig.verify(b"submission", &ring).is_ok());
+            assert_eq!(sig.key_image, KeyImage::new(key));
+        }
+    }
+
+    #[test]
+    fn ring_sizes() {
+        for n in [1, 2, 3, 16] {
+            let (keys, ring) = registered(n);
+            let sig = RingSignature::sign(b"submission", &ring, &keys[n - 1]).unwrap();
+            assert!(sig.verify(b"submission", &ring).is_ok());
+        }
+        let (keys, ring) = registered(2);
+        let sig = RingSignature::sign(b"submission", &ring, &keys[0]).unwrap();
+        assert_eq!(
+            sig.verify(b"submission", &[]),
+            Err(OperationError::InvalidKeyList)
+        );
+    }
+
+    #[test]
+    fn tampering_is_detected() {
+        let (keys, ring) = registered(4);
+        let sig = RingSignature::sign(b"submission", &ring, &keys[2]).unwrap();
+        assert!(sig.verify(b"other submission", &ring).is_err());
+
+        // a reordered, shrunk or extended ring
+        let mut reordered = ring.clone();
+        reordered.swap(0, 1);
+        assert!(sig.verify(b"submission", &reordered).is_err());
+        assert!(sig.verify(b"submission", &ring[..3]).is_err());
+        let mut extended = ring.clone();
+        extended.push(KeyPair::generate("outsider".to_string()).pub_key);
+        assert!(sig.verify(b"submission", &extended).is_err());
+
+        let mut bad = sig.clone();
+        bad.responses[1] = bad.responses[1].add_mod(&Scalar::from(1_u64));
+        assert!(bad.verify(b"submission", &ring).is_err());
+
+        // a key image of another key
+        let mut bad = sig.clone();
+        bad.key_image = KeyImage::new(&keys[0]);
+        assert_eq!(
+            bad.verify(b"submission", &ring),
+            Err(OperationError::SignatureVerificationFailure)
+        );
+    }
+
+    #[test]
+    fn outsiders_cannot_sign() {
+        let (_, ring) = registered(3);
+        let outsider = KeyPair::generate("outsider".to_string());
+        assert_eq!(
+            RingSignature::sign(b"submission", &ring, &outsider),
+            Err(OperationError::SignerNotInKeyList)
+        );
+    }
+
+    #[test]
+    fn key_images_link_double_signing() {
+        let (keys, ring) = registered(4);
+        let first = RingSignature::sign(b"first", &ring, &keys[1]).unwrap();
+        let second = RingSignature::sign(b"second", &ring[1..], &keys[1]).unwrap();
+        let other = RingSignature::sign(b"first", &ring, &keys[3]).unwrap();
+        assert!(first.is_linked(&second));
+        assert!(!first.is_linked(&other));
+        // the same key and message still give a fresh signature
+        let again = RingSignature::sign(b"first", &ring, &keys[1]).unwrap();
+        assert_ne!(first.responses, again.responses);
+        assert!(first.is_linked(&again));
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let (keys, ring) = registered(3);
+        let sig = RingSignature::sign(b"submission", &ring, &keys[0]).unwrap();
+        let json = serde_json::to_string(&sig).unwrap();
+        let decoded: RingSignature = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, sig);
+        assert!(decoded.verify(b"submission", &ring).is_ok());
+    }
+}
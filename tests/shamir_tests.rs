@@ -0,0 +1,181 @@
+#[cfg(test)]
+mod shamir_tests {
+    use capycrypt::{
+        ecc::{
+            keypair::{KeyPair, SecretScalar},
+            shamir::{
+                combine, combine_bytes, split, split_bytes, split_feldman, split_pedersen,
+                ByteShare, FeldmanCommitment, PedersenCommitment, ScalarShare,
+            },
+        },
+        sha3::aux_functions::byte_utils::get_random_bytes,
+        OperationError,
+    };
+    use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+
+    fn random_scalar() -> Scalar {
+        *KeyPair::generate(String::new()).priv_key.expose()
+    }
+
+    #[test]
+    fn any_threshold_subset_recovers_scalar() {
+        let secret = random_scalar();
+        let shares = split(&secret, 3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+        for subset in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+            let chosen: Vec<ScalarShare> = subset.iter().map(|&i| shares[i].clone()).collect();
+            assert_eq!(combine(&chosen).unwrap(), secret);
+        }
+        assert_eq!(combine(&shares).unwrap(), secret);
+        assert_ne!(combine(&shares[..2]).unwrap(), secret);
+
+        let single = split(&secret, 1, 1).unwrap();
+        assert_eq!(combine(&single).unwrap(), secret);
+    }
+
+    #[test]
+    fn parameters_and_indices_are_checked() {
+        let secret = random_scalar();
+        for (threshold, count) in [(0, 3), (4, 3)] {
+            assert!(matches!(
+                split(&secret, threshold, count),
+                Err(OperationError::InvalidThresholdParameters)
+            ));
+        }
+        let shares = split(&secret, 2, 3).unwrap();
+        assert_eq!(
+            combine(&[shares[0].clone(), shares[0].clone()]).unwrap_err(),
+            OperationError::InvalidParticipantIdentifier
+        );
+        assert_eq!(
+            combine(&[]).unwrap_err(),
+            OperationError::InconsistentShares
+        );
+    }
+
+    #[test]
+    fn feldman_shares_verify() {
+        let key_pair = KeyPair::generate("custodian".to_string());
+        let (mut shares, commitment) = split_feldman(key_pair.priv_key.expose(), 2, 3).unwrap();
+        assert_eq!(commitment.public_key(), key_pair.pub_key);
+        for share in &shares {
+            assert!(commitment.verify(share).is_ok());
+        }
+
+        shares[1].value = SecretScalar::new(random_scalar());
+        assert_eq!(
+            commitment.verify(&shares[1]),
+            Err(OperationError::InvalidSecretShare(2))
+        );
+        assert_eq!(
+            combine(&[shares[0].clone(), shares[2].clone()]).unwrap(),
+            *key_pair.priv_key.expose()
+        );
+    }
+
+    #[test]
+    fn pedersen_shares_verify() {
+        let secret = random_scalar();
+        let (mut shares, commitment) = split_pedersen(&secret, 3, 4).unwrap();
+        for share in &shares {
+            assert!(commitment.verify(share).is_ok());
+        }
+        // the commitment does not expose secret*𝑮
+        assert_ne!(commitment.0[0], ExtendedPoint::generator() * secret);
+
+        let scalar_shares: Vec<ScalarShare> = shares[1..].iter().map(|s| s.share.clone()).collect();
+        assert_eq!(combine(&scalar_shares).unwrap(), secret);
+
+        shares[0].blinding = SecretScalar::new(random_scalar());
+        assert_eq!(
+            commitment.verify(&shares[0]),
+            Err(OperationError::InvalidSecretShare(1))
+        );
+    }
+
+    #[test]
+    fn commitments_serialize() {
+        let (shares, feldman) = split_feldman(&random_scalar(), 2, 2).unwrap();
+        let json = serde_json::to_string(&feldman).unwrap();
+        assert_eq!(
+            serde_json::from_str::<FeldmanCommitment>(&json).unwrap(),
+            feldman
+        );
+        let json = serde_json::to_string(&shares[0]).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ScalarShare>(&json).unwrap(),
+            shares[0]
+        );
+
+        let (_, pedersen) = split_pedersen(&random_scalar(), 2, 2).unwrap();
+        let json = serde_json::to_string(&pedersen).unwrap();
+        assert_eq!(
+            serde_json::from_str::<PedersenCommitment>(&json).unwrap(),
+            pedersen
+        );
+    }
+
+    #[test]
+    fn passphrase_roundtrip() {
+        let passphrase = b"correct horse battery staple".to_vec();
+        let shares = split_bytes(&passphrase, 3, 5).unwrap();
+        assert!(shares.iter().all(|s| s.data.len() == passphrase.len()));
+        assert!(shares.iter().all(|s| s.data != passphrase));
+
+        assert_eq!(combine_bytes(&shares[..3]).unwrap(), passphrase);
+        assert_eq!(
+            combine_bytes(&[shares[4].clone(), shares[1].clone(), shares[3].clone()]).unwrap(),
+            passphrase
+        );
+        assert_ne!(combine_bytes(&shares[..2]).unwrap(), passphrase);
+
+        let secret = get_random_bytes(1000);
+        let shares = split_bytes(&secret, 255, 255).unwrap();
+        assert_eq!(combine_bytes(&shares).unwrap(), secret);
+        assert_eq!(
+            combine_bytes(&split_bytes(&[], 2, 3).unwrap()).unwrap(),
+            Vec::<u8>::new()
+        );
+    }
+
+    #[test]
+    fn byte_shares_serialize() {
+        let shares = split_bytes(b"hunter2", 2, 3).unwrap();
+        let encoded: Vec<Vec<u8>> = shares.iter().map(|s| s.to_bytes()).collect();
+        let decoded: Vec<ByteShare> = encoded
+            .iter()
+            .map(|b| ByteShare::from_bytes(b).unwrap())
+            .collect();
+        assert_eq!(decoded, shares);
+
+        let json = serde_json::to_string(&shares[1]).unwrap();
+        assert!(json.contains(&hex::encode(&shares[1].data)));
+        let share: ByteShare = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            combine_bytes(&[shares[0].clone(), share]).unwrap(),
+            b"hunter2"
+        );
+
+        assert!(ByteShare::from_bytes(&[]).is_err());
+        assert!(ByteShare::from_bytes(&[0, 1, 2]).is_err());
+    }
+
+    #[test]
+    fn byte_share_errors() {
+        let shares = split_bytes(b"secret", 2, 3).unwrap();
+        let mut short = shares[1].clone();
+        short.data.pop();
+        assert_eq!(
+            combine_bytes(&[shares[0].clone(), short]).unwrap_err(),
+            OperationError::InconsistentShares
+        );
+        assert_eq!(
+            combine_bytes(&[shares[0].clone(), shares[0].clone()]).unwrap_err(),
+            OperationError::InvalidParticipantIdentifier
+        );
+        assert!(matches!(
+            split_bytes(b"secret", 0, 3),
+            Err(OperationError::InvalidThresholdParameters)
+        ));
+    }
+}
@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod vrf_tests {
+    use capycrypt::{
+        ecc::{
+            keypair::KeyPair,
+            vrf::{prove, verify, VrfProof, PROOF_BYTES},
+        },
+        OperationError,
+    };
+    use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+
+    #[test]
+    fn proof_verifies_and_yields_output() {
+        let key_pair = KeyPair::generate("validator".to_string());
+        let proof = prove(&key_pair, b"epoch 17");
+        let beta = verify(&key_pair.pub_key, b"epoch 17", &proof).unwrap();
+        assert_eq!(beta, proof.proof_to_hash());
+
+        // proofs are deterministic and outputs depend on the input
+        assert_eq!(prove(&key_pair, b"epoch 17"), proof);
+        let other = prove(&key_pair, b"epoch 18");
+        assert_ne!(other.proof_to_hash(), beta);
+        assert!(verify(&key_pair.pub_key, b"", &prove(&key_pair, b"")).is_ok());
+    }
+
+    #[test]
+    fn known_answers() {
+        // secret key of RFC 8032 7.4, TEST 1
+        let seed = hex::decode(
+            "6c82a562cb808d10d632be89c8513ebf6c929f34ddfa8c9f63c9960ef6e348a3\
+             528c8a3fcc2f044e39a3fc5b94492f8f032e7549a20098f95b",
+        )
+        .unwrap();
+        let key_pair = KeyPair::from_seed(&seed, "kat".to_string()).unwrap();
+        // No official edwards448 vectors exist; these pin the suite's outputs
+        let vectors: [(&[u8], &str, &str); 2] = [
+            (
+                b"",
+                "0651bdb2706be250f2c6a5e02c881dd0327fe101820807ad4bd07ecfa9650323\
+                 4b285cd4ca858d4f83ecb85a76aac8ca70b2fe9ac0d1c6180065af61c8edac5b\
+                 2b8b6a24918fac6739af08807be936a73aabe2b9111945fc9d6dd9bfc382fd7f\
+                 14e64be6a2c319ee70ac38ce13d0389ad23f44fa2db589865cd2af93d0008cc6\
+                 8005d18fc654d75fc2991d472000",
+                "06eba432398823164be59becebe1d781e79b4f966e1e156f2a145ce0e1978a14\
+                 49425fbf3b3b4e69f3b7e3dddeb5ba10a385933355748b935b759eb56c58da6c\
+                 2e67362c5a03cff903d7eca2518d27b0249fb4734f6dee14ef4793cae1072f63\
+                 fe0d5585d5b542a5b947fe805c4ecfb5cf98",
+            ),
+            (
+                b"sample",
+                "88b9433732c595ba91f7ebdb84d558a3daaf3a5d744deaa1bb3cc91d2acf2205\
+                 c79f4fa15e0af38e35d08eafa74ceed3e487511fea6ddcce002af539a5e430a2\
+                 e8fe603c0758aae635f79c5dfde20fdddfa693ece318e370de2c45455ae51a12\
+                 5c87a5886e8c69b54c95c3e90983a81bc6cbe18997950b5b15540fafd96e5dad\
+                 4302fbbc738ac1666dee95ec1200",
+                "0a3a5896b02b139d2778facdf7180ce349b66662446a801eb3de096b2c1ef5cb\
+                 12414a0f1a9a4bf786295f5273b7f35c7d9e0f23694fff479922e012a2f7c9a0\
+                 b2ca5890f3a02de0497ac8b882f9d694beb38753c5e6ae4a688e781e3eddac86\
+                 51a9c8387276570114ae9edda137d1b8fe79",
+            ),
+        ];
+        for (alpha, pi, beta) in vectors {
+            let proof = prove(&key_pair, alpha);
+            assert_eq!(hex::encode(proof.to_bytes()), pi);
+            let output = verify(&key_pair.pub_key, alpha, &proof).unwrap();
+            assert_eq!(hex::encode(output), beta);
+        }
+    }
+
+    #[test]
+    fn outputs_differ_between_keys() {
+        let alice = KeyPair::generate("alice".to_string());
+        let bob = KeyPair::generate("bob".to_string());
+        let a = prove(&alice, b"round 1");
+        let b = prove(&bob, b"round 1");
+        assert_ne!(a.proof_to_hash(), b.proof_to_hash());
+        assert_eq!(
+            verify(&bob.pub_key, b"round 1", &a),
+            Err(OperationError::ProofVerificationFailure)
+        );
+    }
+
+    #[test]
+    fn tampered_proofs_are_rejected() {
+        let key_pair = KeyPair::generate("validator".to_string());
+        let proof = prove(&key_pair, b"draw");
+        assert!(verify(&key_pair.pub_key, b"drew", &proof).is_err());
+
+        let mut forged = proof;
+        forged.gamma = forged.gamma + ExtendedPoint::generator();
+        assert!(verify(&key_pair.pub_key, b"draw", &forged).is_err());
+
+        let mut forged = proof;
+        forged.s = forged.s.add_mod(&Scalar::from(1_u64));
+        assert!(verify(&key_pair.pub_key, b"draw", &forged).is_err());
+
+        assert_eq!(
+            verify(&ExtendedPoint::id_point(), b"draw", &proof),
+            Err(OperationError::ProofVerificationFailure)
+        );
+    }
+
+    #[test]
+    fn proofs_serialize() {
+        let key_pair = KeyPair::generate("validator".to_string());
+        let proof = prove(&key_pair, b"lottery");
+        let bytes = proof.to_bytes();
+        assert_eq!(bytes.len(), PROOF_BYTES);
+        let decoded = VrfProof::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(verify(&key_pair.pub_key, b"lottery", &decoded).is_ok());
+
+        let json = serde_json::to_string(&proof).unwrap();
+        assert_eq!(serde_json::from_str::<VrfProof>(&json).unwrap(), proof);
+
+        assert_eq!(
+            VrfProof::from_bytes(&bytes[1..]),
+            Err(OperationError::InvalidProofLength)
+        );
+        let mut bad_s = bytes;
+        bad_s[PROOF_BYTES - 1] = 0xFF;
+        assert_eq!(
+            VrfProof::from_bytes(&bad_s),
+            Err(OperationError::InvalidScalarEncoding)
+        );
+    }
+}
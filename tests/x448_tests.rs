@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod x448_tests {
+    use capycrypt::{
+        ecc::{
+            edwards448::encode_point,
+            x448::{
+                edwards_diffie_hellman, x448, X448PublicKey, X448SecretKey, X448_BASEPOINT,
+                X448_BYTES,
+            },
+        },
+        sha3::aux_functions::byte_utils::get_random_bytes,
+        OperationError,
+    };
+    use tiny_ed448_goldilocks::curve::{extended_edwards::ExtendedPoint, field::scalar::Scalar};
+
+    fn decode(hex: &str) -> [u8; X448_BYTES] {
+        hex::decode(hex).unwrap().try_into().unwrap()
+    }
+
+    // RFC 7748 Section 5.2
+    #[test]
+    fn rfc_7748_vectors() {
+        let k = decode("3d262fddf9ec8e88495266fea19a34d28882acef045104d0d1aae121700a779c984c24f8cdd78fbff44943eba368f54b29259a4f1c600ad3");
+        let u = decode("06fce640fa3487bfda5f6cf2d5263f8aad88334cbd07437f020f08f9814dc031ddbdc38c19c6da2583fa5429db94ada18aa7a7fb4ef8a086");
+        assert_eq!(
+            hex::encode(x448(&k, &u)),
+            "ce3e4ff95a60dc6697da1db1d85e6afbdf79b50a2412d7546d5f239fe14fbaadeb445fc66a01b0779d98223961111e21766282f73dd96b6f"
+        );
+
+        let k = decode("203d494428b8399352665ddca42f9de8fef600908e0d461cb021f8c538345dd77c3e4806e25f46d3315c44e0a5b4371282dd2c8d5be3095f");
+        let u = decode("0fbcc2f993cd56d3305b0b7d9e55d4c1a8fb5dbb52f8e9a1e9b6201b165d015894e56c4d3570bee52fe205e28a78b91cdfbde71ce8d157db");
+        assert_eq!(
+            hex::encode(x448(&k, &u)),
+            "884a02576239ff7a2f2f63b2db6a9ff37047ac13568e1e30fe63c4a7ad1b3ee3a5700df34321d62077e63633c575c1c954514e99da7c179d"
+        );
+    }
+
+    fn iterate(n: usize) -> String {
+        let mut k = X448_BASEPOINT;
+        let mut u = X448_BASEPOINT;
+        for _ in 0..n {
+            let old_k = k;
+            k = x448(&k, &u);
+            u = old_k;
+        }
+        hex::encode(k)
+    }
+
+    // RFC 7748 Section 5.2, iterated
+    #[test]
+    fn rfc_7748_iterated() {
+        assert_eq!(
+            iterate(1),
+            "3f482c8a9f19b01e6c46ee9711d9dc14fd4bf67af30765c2ae2b846a4d23a8cd0db897086239492caf350b51f833868b9bc2b3bca9cf4113"
+        );
+        assert_eq!(
+            iterate(1000),
+            "aa3b4749d55b9daf1e5b00288826c467274ce3ebbdd5c17b975e09d4af6c67cf10d087202db88286e2b79fceea3ec353ef54faa26e219f38"
+        );
+    }
+
+    #[test]
+    #[ignore = "takes several minutes"]
+    fn rfc_7748_iterated_one_million() {
+        assert_eq!(
+            iterate(1_000_000),
+            "077f453681caca3693198420bbe515cae0002472519b3e67661a7e89cab94695c8f4bcd66e61b9b9c946da8d524de3d69bd9d9d66b997e37"
+        );
+    }
+
+    // RFC 7748 Section 6.2
+    #[test]
+    fn rfc_7748_diffie_hellman() {
+        let alice = X448SecretKey::from_bytes(&hex::decode("9a8f4925d1519f5775cf46b04b5800d4ee9ee8bae8bc5565d498c28dd9c9baf574a9419744897391006382a6f127ab1d9ac2d8c0a598726b").unwrap()).unwrap();
+        let bob = X448SecretKey::from_bytes(&hex::decode("1c306a7ac2a0e2e0990b294470cba339e6453772b075811d8fad0d1d6927c120bb5ee8972b0d3e21374c9c921b09d1b0366f10b65173992d").unwrap()).unwrap();
+        assert_eq!(
+            hex::encode(alice.public_key().to_bytes()),
+            "9b08f7cc31b7e3e67d22d5aea121074a273bd2b83de09c63faa73d2c22c5d9bbc836647241d953d40c5b12da88120d53177f80e532c41fa0"
+        );
+        assert_eq!(
+            hex::encode(bob.public_key().to_bytes()),
+            "3eb7a829b0cd20f5bcfc0b599b6feccf6da4627107bdb0d4f345b43027d8b972fc3e34fb4232a13ca706dcb57aec3dae07bdc1c67bf33609"
+        );
+
+        let shared = alice.diffie_hellman(&bob.public_key()).unwrap();
+        assert_eq!(shared, bob.diffie_hellman(&alice.public_key()).unwrap());
+        assert_eq!(
+            hex::encode(shared),
+            "07fff4181ac6cc95ec1c16a94a0f74d12da232ce40a77552281d282bb60c0b56fd2464c335543936521c24403085d59a449a5037514a879d"
+        );
+    }
+
+    #[test]
+    fn low_order_public_key() {
+        let key = X448SecretKey::generate();
+        for u in [[0u8; X448_BYTES], {
+            let mut one = [0u8; X448_BYTES];
+            one[0] = 1;
+            one
+        }] {
+            assert_eq!(
+                key.diffie_hellman(&X448PublicKey::from_bytes(&u).unwrap()),
+                Err(OperationError::NonContributoryKeyAgreement)
+            );
+        }
+    }
+
+    #[test]
+    fn edwards_base_point_converts_to_five() {
+        let u = X448PublicKey::from_edwards(&ExtendedPoint::generator());
+        assert_eq!(u.to_bytes(), X448_BASEPOINT);
+    }
+
+    #[test]
+    fn edwards_conversion_roundtrip() {
+        let s = Scalar::from(0xc0ffee_u64).mul_mod(&Scalar::from(0x1234567890abcdef_u64));
+        let p = ExtendedPoint::generator() * s;
+        let u = X448PublicKey::from_edwards(&p);
+        let sign = encode_point(&p)[56] >> 7;
+
+        assert_eq!(u.to_edwards(sign).unwrap(), p);
+        assert_eq!(u.to_edwards(sign ^ 1).unwrap(), p.negate());
+
+        // u-coordinates of the twist are rejected
+        let mut twist = [0u8; X448_BYTES];
+        twist[0] = 2;
+        assert!(X448PublicKey::from_bytes(&twist)
+            .unwrap()
+            .to_edwards(0)
+            .is_err());
+    }
+
+    #[test]
+    fn edwards_and_x448_keys_agree() {
+        let s = Scalar::from(0xdecafbad_u64).mul_mod(&Scalar::from(0xfeedface_u64));
+        let edwards_public = X448PublicKey::from_edwards(&(ExtendedPoint::generator() * s));
+        let x448_key = X448SecretKey::from_bytes(&get_random_bytes(56)).unwrap();
+
+        let ours = edwards_diffie_hellman(&s, &x448_key.public_key()).unwrap();
+        let theirs = x448_key.diffie_hellman(&edwards_public).unwrap();
+        assert_eq!(ours, theirs);
+    }
+}